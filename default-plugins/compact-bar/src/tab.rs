@@ -92,6 +92,9 @@ pub fn tab_style(
     if tab.is_sync_panes_active {
         tabname.push_str(" (Sync)");
     }
+    if tab.has_unseen_notification {
+        tabname.push_str(" (!)");
+    }
     // we only color alternate tabs differently if we can't use the arrow fonts to separate them
     if !capabilities.arrow_fonts {
         is_alternate_tab = false;
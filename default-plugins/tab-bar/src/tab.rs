@@ -92,6 +92,18 @@ pub fn tab_style(
     if tab.is_sync_panes_active {
         tabname.push_str(" (Sync)");
     }
+    if tab.has_unseen_notification {
+        tabname.push_str(" (!)");
+    }
+    if tab.has_bell {
+        tabname.push_str(" (Bell)");
+    }
+    if tab.has_activity {
+        tabname.push_str(" (Activity)");
+    }
+    if tab.has_silence {
+        tabname.push_str(" (Silence)");
+    }
     // we only color alternate tabs differently if we can't use the arrow fonts to separate them
     if !capabilities.arrow_fonts {
         is_alternate_tab = false;
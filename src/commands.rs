@@ -1,5 +1,7 @@
 use dialoguer::Confirm;
-use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
+use std::{
+    collections::BTreeMap, fs::File, io::prelude::*, path::PathBuf, process, time::Duration,
+};
 
 use crate::sessions::{
     assert_dead_session, assert_session, assert_session_ne, delete_session as delete_session_impl,
@@ -18,7 +20,7 @@ use zellij_client::{
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
-    data::{ConnectToSession, LayoutInfo},
+    data::{ConnectToSession, LayoutInfo, PaneId},
     envs,
     input::{
         actions::Action,
@@ -172,6 +174,8 @@ fn find_indexed_session(
 /// Checks session to send the action to and attaches with client
 pub(crate) fn send_action_to_session(
     cli_action: zellij_utils::cli::CliAction,
+    pane_id: Option<PaneId>,
+    tab: Option<u32>,
     requested_session_name: Option<String>,
     config: Option<Config>,
 ) {
@@ -191,7 +195,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            attach_with_cli_client(cli_action, pane_id, tab, &session_name, config);
         },
         ActiveSession::Many => {
             let existing_sessions: Vec<String> = get_sessions()
@@ -201,20 +205,20 @@ pub(crate) fn send_action_to_session(
                 .collect();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    attach_with_cli_client(cli_action, pane_id, tab, &session_name, config);
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
                         session_name
                     );
-                    list_sessions(false, false, true);
+                    list_sessions(false, false, true, false);
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                attach_with_cli_client(cli_action, pane_id, tab, &session_name, config);
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, false);
                 std::process::exit(1);
             }
         },
@@ -291,12 +295,20 @@ pub(crate) fn convert_old_theme_file(old_theme_file: PathBuf) {
 
 fn attach_with_cli_client(
     cli_action: zellij_utils::cli::CliAction,
+    pane_id: Option<PaneId>,
+    tab: Option<u32>,
     session_name: &str,
     config: Option<Config>,
 ) {
     let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
     let get_current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    match Action::actions_from_cli(cli_action, Box::new(get_current_dir), config) {
+    match Action::targeted_actions_from_cli(
+        cli_action,
+        pane_id,
+        tab,
+        Box::new(get_current_dir),
+        config,
+    ) {
         Ok(actions) => {
             zellij_client::cli_client::start_cli_client(Box::new(os_input), session_name, actions);
             std::process::exit(0);
@@ -375,7 +387,7 @@ fn attach_with_session_name(
             ActiveSession::One(session_name) => ClientInfo::Attach(session_name, config_options),
             ActiveSession::Many => {
                 println!("Please specify the session to attach to, either by using the full name or a unique prefix.\nThe following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, false);
                 process::exit(1);
             },
         },
@@ -422,6 +434,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                     force_run_commands: false,
                     index: None,
                     options: None,
+                    read_only: false,
                 }));
             } else {
                 opts.command = None;
@@ -443,8 +456,11 @@ pub(crate) fn start_client(opts: CliArgs) {
                         Some(&PathBuf::from(layout_name)),
                         layout_dir.clone(),
                         config_without_layout.clone(),
+                        BTreeMap::new(),
                     ),
-                    LayoutInfo::Url(url) => Layout::from_url(&url, config_without_layout.clone()),
+                    LayoutInfo::Url(url) => {
+                        Layout::from_url(&url, config_without_layout.clone(), BTreeMap::new())
+                    },
                 };
                 match new_session_layout {
                     Ok(new_session_layout) => {
@@ -483,6 +499,7 @@ pub(crate) fn start_client(opts: CliArgs) {
             force_run_commands,
             index,
             options,
+            ..
         })) = opts.command.clone()
         {
             let config_options = match options.as_deref() {
@@ -16,8 +16,13 @@ fn main() {
 
     {
         let config = Config::try_from(&opts).ok();
-        if let Some(Command::Sessions(Sessions::Action(cli_action))) = opts.command {
-            commands::send_action_to_session(cli_action, opts.session, config);
+        if let Some(Command::Sessions(Sessions::Action {
+            pane_id,
+            tab,
+            action,
+        })) = opts.command
+        {
+            commands::send_action_to_session(action, pane_id, tab, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Run {
@@ -29,6 +34,7 @@ fn main() {
             name,
             close_on_exit,
             start_suspended,
+            env,
             x,
             y,
             width,
@@ -47,6 +53,7 @@ fn main() {
                 name,
                 close_on_exit,
                 start_suspended,
+                env,
                 configuration: None,
                 skip_plugin_cache,
                 x,
@@ -54,7 +61,7 @@ fn main() {
                 width,
                 height,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
@@ -80,6 +87,7 @@ fn main() {
                 name: None,
                 close_on_exit: false,
                 start_suspended: false,
+                env: vec![],
                 configuration,
                 skip_plugin_cache,
                 x,
@@ -87,7 +95,7 @@ fn main() {
                 width,
                 height,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Edit {
@@ -122,7 +130,7 @@ fn main() {
                 width,
                 height,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
@@ -159,7 +167,7 @@ fn main() {
                 plugin_cwd: None,
                 plugin_title: None,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, None, opts.session, config);
             std::process::exit(0);
         }
     }
@@ -168,9 +176,10 @@ fn main() {
         no_formatting,
         short,
         reverse,
+        json,
     })) = opts.command
     {
-        commands::list_sessions(no_formatting, short, reverse);
+        commands::list_sessions(no_formatting, short, reverse, json);
     } else if let Some(Command::Sessions(Sessions::ListAliases)) = opts.command {
         commands::list_aliases(opts);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
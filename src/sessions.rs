@@ -6,14 +6,17 @@ use suggest::Suggest;
 use zellij_utils::{
     anyhow,
     consts::{
-        session_info_folder_for_session, session_layout_cache_file_name,
-        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        session_info_cache_file_name, session_info_folder_for_session,
+        session_layout_cache_file_name, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
     },
+    data::SessionInfo,
     envs,
     humantime::format_duration,
     input::layout::Layout,
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    serde::{self, Serialize},
+    serde_json,
 };
 
 pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
@@ -190,6 +193,66 @@ pub(crate) fn print_sessions(
         })
 }
 
+#[derive(Serialize)]
+#[serde(crate = "self::serde")]
+struct SerializedSession {
+    #[serde(flatten)]
+    session_info: SessionInfo,
+    created_at: u64, // seconds since the unix epoch
+    is_exited: bool,
+}
+
+pub(crate) fn print_sessions_as_json(sessions: Vec<(String, Duration, bool)>, reverse: bool) {
+    let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    match sessions_as_json(sessions, reverse, &curr_session, now) {
+        Ok(serialized_sessions) => println!("{}", serialized_sessions),
+        Err(e) => {
+            eprintln!("Failed to serialize sessions: {}", e);
+            process::exit(1);
+        },
+    }
+}
+
+pub(crate) fn sessions_as_json(
+    mut sessions: Vec<(String, Duration, bool)>,
+    reverse: bool,
+    curr_session: &str,
+    now: Duration, // since the unix epoch
+) -> serde_json::Result<String> {
+    // (session_name, timestamp, is_dead)
+    if reverse {
+        sessions.sort_by(|a, b| a.1.cmp(&b.1));
+    } else {
+        sessions.sort_by(|a, b| b.1.cmp(&a.1));
+    }
+    let serialized_sessions: Vec<SerializedSession> = sessions
+        .into_iter()
+        .map(|(session_name, timestamp, is_dead)| {
+            // the cached metadata of exited sessions is removed when they exit
+            let session_info = if is_dead {
+                None
+            } else {
+                fs::read_to_string(session_info_cache_file_name(&session_name))
+                    .ok()
+                    .and_then(|raw| SessionInfo::from_string(&raw, curr_session).ok())
+            };
+            SerializedSession {
+                session_info: session_info.unwrap_or_else(|| SessionInfo {
+                    is_current_session: curr_session == session_name,
+                    name: session_name,
+                    ..Default::default()
+                }),
+                created_at: now.saturating_sub(timestamp).as_secs(),
+                is_exited: is_dead,
+            }
+        })
+        .collect();
+    serde_json::to_string(&serialized_sessions)
+}
+
 pub(crate) fn print_sessions_with_index(sessions: Vec<String>) {
     let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
     for (i, session) in sessions.iter().enumerate() {
@@ -254,7 +317,7 @@ pub(crate) fn delete_session(name: &str, force: bool) {
     }
 }
 
-pub(crate) fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
+pub(crate) fn list_sessions(no_formatting: bool, short: bool, reverse: bool, json: bool) {
     let exit_code = match get_sessions() {
         Ok(running_sessions) => {
             let resurrectable_sessions = get_resurrectable_sessions();
@@ -265,7 +328,18 @@ pub(crate) fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
             for (session_name, duration) in running_sessions {
                 all_sessions.insert(session_name.clone(), (duration, false));
             }
-            if all_sessions.is_empty() {
+            if json {
+                print_sessions_as_json(
+                    all_sessions
+                        .iter()
+                        .map(|(name, (timestamp, is_dead))| {
+                            (name.clone(), timestamp.clone(), *is_dead)
+                        })
+                        .collect(),
+                    reverse,
+                );
+                0
+            } else if all_sessions.is_empty() {
                 eprintln!("No active zellij sessions found.");
                 1
             } else {
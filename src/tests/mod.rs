@@ -1 +1,2 @@
 pub mod e2e;
+mod sessions_tests;
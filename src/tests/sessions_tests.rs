@@ -0,0 +1,58 @@
+use crate::sessions::sessions_as_json;
+use std::time::Duration;
+use zellij_utils::serde_json::{self, Value};
+
+#[test]
+fn list_sessions_as_json() {
+    let now = Duration::from_secs(1_000_000);
+    let sessions = vec![
+        (
+            String::from("sessions-json-test-older"),
+            Duration::from_secs(3600),
+            true,
+        ),
+        (
+            String::from("sessions-json-test-newer"),
+            Duration::from_secs(60),
+            false,
+        ),
+    ];
+    let reverse = false;
+    let json = sessions_as_json(sessions, reverse, "sessions-json-test-newer", now).unwrap();
+    let sessions: Value = serde_json::from_str(&json).unwrap();
+    let sessions = sessions.as_array().unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(
+        sessions[0]["name"], "sessions-json-test-older",
+        "oldest session listed first"
+    );
+    assert_eq!(sessions[0]["created_at"], 1_000_000 - 3600);
+    assert_eq!(sessions[0]["is_exited"], true);
+    assert_eq!(sessions[0]["is_current_session"], false);
+    assert_eq!(sessions[1]["name"], "sessions-json-test-newer");
+    assert_eq!(sessions[1]["created_at"], 1_000_000 - 60);
+    assert_eq!(sessions[1]["is_exited"], false);
+    assert_eq!(sessions[1]["is_current_session"], true);
+}
+
+#[test]
+fn list_sessions_as_json_in_reverse() {
+    let now = Duration::from_secs(1_000_000);
+    let sessions = vec![
+        (
+            String::from("sessions-json-test-older"),
+            Duration::from_secs(3600),
+            true,
+        ),
+        (
+            String::from("sessions-json-test-newer"),
+            Duration::from_secs(60),
+            true,
+        ),
+    ];
+    let reverse = true;
+    let json = sessions_as_json(sessions, reverse, "", now).unwrap();
+    let sessions: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(sessions[0]["name"], "sessions-json-test-newer");
+    assert_eq!(sessions[1]["name"], "sessions-json-test-older");
+}
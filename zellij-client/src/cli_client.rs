@@ -3,12 +3,14 @@
 use std::collections::BTreeMap;
 use std::io::BufRead;
 use std::process;
+use std::thread;
+use std::time::Duration;
 use std::{fs, path::PathBuf};
 
 use crate::os_input_output::ClientOsApi;
 use zellij_utils::{
     errors::prelude::*,
-    input::actions::Action,
+    input::actions::{Action, WaitCondition},
     ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
     uuid::Uuid,
 };
@@ -63,6 +65,9 @@ pub fn start_cli_client(
                     pane_title,
                 );
             },
+            action @ Action::WaitFor { .. } => {
+                wait_for_client(&mut os_input, action, pane_id);
+            },
             action => {
                 individual_messages_client(&mut os_input, action, pane_id);
             },
@@ -199,6 +204,61 @@ fn pipe_client(
     }
 }
 
+fn wait_for_client(os_input: &mut Box<dyn ClientOsApi>, action: Action, pane_id: Option<u32>) {
+    let (pipe_id, exits_with_status, timeout) = match &action {
+        Action::WaitFor {
+            pipe_id,
+            condition,
+            timeout,
+            ..
+        } => (pipe_id.clone(), *condition == WaitCondition::Exit, *timeout),
+        _ => return,
+    };
+    if let Some(timeout) = timeout {
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(timeout));
+            eprintln!("Timed out after {} seconds", timeout);
+            // same as timeout(1)
+            process::exit(124);
+        });
+    }
+    os_input.send_to_server(ClientToServerMsg::Action(action, pane_id, None));
+    let mut exit_status = 0;
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::CliPipeOutput(pipe_name, output), _)) => {
+                if pipe_name == pipe_id {
+                    println!("{}", output);
+                    if exits_with_status {
+                        exit_status = output.trim().parse().unwrap_or(1);
+                    }
+                }
+            },
+            Some((ServerToClientMsg::UnblockCliPipeInput(pipe_name), _)) => {
+                if pipe_name == pipe_id {
+                    process::exit(exit_status);
+                }
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => {
+                match exit_reason {
+                    ExitReason::Error(e) => eprintln!("{}", e),
+                    _ => eprintln!("The session ended while waiting"),
+                }
+                process::exit(2);
+            },
+            None => {
+                eprintln!("Lost connection to the session while waiting");
+                process::exit(2);
+            },
+            _ => {},
+        }
+    }
+}
+
 fn individual_messages_client(
     os_input: &mut Box<dyn ClientOsApi>,
     action: Action,
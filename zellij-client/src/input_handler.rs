@@ -11,6 +11,7 @@ use zellij_utils::{
         actions::Action,
         cast_termwiz_key,
         config::Config,
+        kitty_keyboard::{KittyKeyEvent, KittyKeyboardFlags},
         mouse::{MouseButton, MouseEvent},
         options::Options,
     },
@@ -140,11 +141,8 @@ impl InputHandler {
                         _ => {},
                     }
                 },
-                Ok((
-                    InputInstruction::KeyWithModifierEvent(key_with_modifier, raw_bytes),
-                    _error_context,
-                )) => {
-                    self.handle_key(&key_with_modifier, raw_bytes, true);
+                Ok((InputInstruction::KittyKeyEvent(key_event, raw_bytes), _error_context)) => {
+                    self.handle_kitty_key_event(key_event, raw_bytes);
                 },
                 Ok((InputInstruction::SwitchToMode(input_mode), _error_context)) => {
                     self.mode = input_mode;
@@ -193,6 +191,40 @@ impl InputHandler {
             }
         }
     }
+    fn handle_kitty_key_event(&mut self, key_event: KittyKeyEvent, raw_bytes: Vec<u8>) {
+        let mode_writes_to_panes = matches!(self.mode, InputMode::Normal | InputMode::Locked);
+        match key_event.to_key_with_modifier() {
+            Some(key) if !key_event.is_release() => {
+                if mode_writes_to_panes {
+                    self.handle_key(&key, raw_bytes, true);
+                } else {
+                    // eg. the tab name input expects text rather than key events
+                    let raw_bytes = key_event
+                        .serialize(KittyKeyboardFlags::empty())
+                        .unwrap_or_default();
+                    self.handle_key(&key, raw_bytes, false);
+                }
+            },
+            Some(key) => {
+                // key releases are only sent to panes, and only for keys Zellij did not handle
+                // itself
+                let key_is_bound = self
+                    .config
+                    .keybinds
+                    .get_actions_for_key_in_mode(&self.mode, &key)
+                    .is_some();
+                if mode_writes_to_panes && !key_is_bound {
+                    self.dispatch_action(Action::Write(Some(key), raw_bytes, true), None);
+                }
+            },
+            None => {
+                // keys Zellij has no representation for (eg. modifier keys on their own)
+                if mode_writes_to_panes {
+                    self.dispatch_action(Action::Write(None, raw_bytes, true), None);
+                }
+            },
+        }
+    }
     fn handle_stdin_ansi_instruction(&mut self, ansi_stdin_instructions: AnsiStdinInstruction) {
         match ansi_stdin_instructions {
             AnsiStdinInstruction::PixelDimensions(pixel_dimensions) => {
@@ -25,15 +25,18 @@ use crate::{
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     consts::{set_permissions, ZELLIJ_SOCK_DIR},
-    data::{ClientId, ConnectToSession, InputMode, KeyWithModifier, Style},
+    data::{ClientId, ConnectToSession, InputMode, Style},
     envs,
     errors::{ClientContext, ContextType, ErrorInstruction},
-    input::{config::Config, options::Options},
-    ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
+    input::{config::Config, kitty_keyboard::KittyKeyEvent, options::Options},
+    ipc::{ClientAttributes, ClientToServerMsg, ClientType, ExitReason, ServerToClientMsg},
     pane_size::Size,
     termwiz::input::InputEvent,
 };
-use zellij_utils::{cli::CliArgs, input::layout::Layout};
+use zellij_utils::{
+    cli::{self, CliArgs, Sessions},
+    input::layout::Layout,
+};
 
 /// Instructions related to the client-side application
 #[derive(Debug, Clone)]
@@ -153,7 +156,7 @@ impl ClientInfo {
 #[derive(Debug, Clone)]
 pub(crate) enum InputInstruction {
     KeyEvent(InputEvent, Vec<u8>),
-    KeyWithModifierEvent(KeyWithModifier, Vec<u8>),
+    KittyKeyEvent(KittyKeyEvent, Vec<u8>),
     SwitchToMode(InputMode),
     AnsiStdinInstructions(Vec<AnsiStdinInstruction>),
     StartedParsing,
@@ -187,7 +190,9 @@ pub fn start_client(
     let clear_client_terminal_attributes = "\u{1b}[?1l\u{1b}=\u{1b}[r\u{1b}[?1000l\u{1b}[?1002l\u{1b}[?1003l\u{1b}[?1005l\u{1b}[?1006l\u{1b}[?12l";
     let take_snapshot = "\u{1b}[?1049h";
     let bracketed_paste = "\u{1b}[?2004h";
-    let enter_kitty_keyboard_mode = "\u{1b}[>1u";
+    // we ask for all the progressive enhancements of the kitty keyboard protocol, so that we can
+    // pass on to each pane the ones it asked for
+    let enter_kitty_keyboard_mode = "\u{1b}[>31u";
     os_input.unset_raw_mode(0).unwrap();
 
     if !is_a_reconnect {
@@ -240,6 +245,12 @@ pub fn start_client(
             envs::set_session_name(name.clone());
             os_input.update_session_name(name);
             let ipc_pipe = create_ipc_pipe();
+            let client_type = match opts.command {
+                Some(cli::Command::Sessions(Sessions::Attach {
+                    read_only: true, ..
+                })) => ClientType::Reader,
+                _ => ClientType::Writer,
+            };
 
             (
                 ClientToServerMsg::AttachClient(
@@ -247,6 +258,7 @@ pub fn start_client(
                     config_options,
                     tab_position_to_focus,
                     pane_id_to_focus,
+                    client_type,
                 ),
                 ipc_pipe,
             )
@@ -1,4 +1,4 @@
-use crate::keyboard_parser::KittyKeyboardParser;
+use crate::keyboard_parser::{parse_kitty_keyboard_input, KittyKeyboardInput};
 use crate::os_input_output::ClientOsApi;
 use crate::stdin_ansi_parser::StdinAnsiParser;
 use crate::InputInstruction;
@@ -89,59 +89,45 @@ pub(crate) fn stdin_loop(
                 current_buffer.append(&mut buf.to_vec());
 
                 if !explicitly_disable_kitty_keyboard_protocol {
-                    // first we try to parse with the KittyKeyboardParser
-                    // if we fail, we try to parse normally
-                    match KittyKeyboardParser::new().parse(&buf) {
-                        Some(key_with_modifier) => {
-                            send_input_instructions
-                                .send(InputInstruction::KeyWithModifierEvent(
-                                    key_with_modifier,
-                                    current_buffer.drain(..).collect(),
-                                ))
-                                .unwrap();
-                            continue;
-                        },
-                        None => {},
+                    // first we try to parse kitty keyboard protocol key events
+                    // whatever it cannot parse, we try to parse normally
+                    let input = parse_kitty_keyboard_input(&buf);
+                    let has_key_events = input
+                        .iter()
+                        .any(|i| matches!(i, KittyKeyboardInput::KeyEvent(..)));
+                    if has_key_events {
+                        current_buffer.clear();
+                        for input in input {
+                            match input {
+                                KittyKeyboardInput::KeyEvent(key_event, raw_bytes) => {
+                                    send_input_instructions
+                                        .send(InputInstruction::KittyKeyEvent(key_event, raw_bytes))
+                                        .unwrap();
+                                },
+                                KittyKeyboardInput::Other(other_bytes) => {
+                                    parse_input_events(
+                                        &other_bytes,
+                                        &mut other_bytes.clone(),
+                                        &mut input_parser,
+                                        &mut holding_mouse,
+                                        &mut os_input,
+                                        &send_input_instructions,
+                                    );
+                                },
+                            }
+                        }
+                        continue;
                     }
                 }
 
-                let maybe_more = false; // read_from_stdin should (hopefully) always empty the STDIN buffer completely
-                let mut events = vec![];
-                input_parser.parse(
+                parse_input_events(
                     &buf,
-                    |input_event: InputEvent| {
-                        events.push(input_event);
-                    },
-                    maybe_more,
+                    &mut current_buffer,
+                    &mut input_parser,
+                    &mut holding_mouse,
+                    &mut os_input,
+                    &send_input_instructions,
                 );
-
-                let event_count = events.len();
-                for (i, input_event) in events.into_iter().enumerate() {
-                    if holding_mouse && is_mouse_press_or_hold(&input_event) && i == event_count - 1
-                    {
-                        let mut poller = os_input.stdin_poller();
-                        loop {
-                            if poller.ready() {
-                                break;
-                            }
-                            send_input_instructions
-                                .send(InputInstruction::KeyEvent(
-                                    input_event.clone(),
-                                    current_buffer.clone(),
-                                ))
-                                .unwrap();
-                        }
-                    }
-
-                    holding_mouse = is_mouse_press_or_hold(&input_event);
-
-                    send_input_instructions
-                        .send(InputInstruction::KeyEvent(
-                            input_event,
-                            current_buffer.drain(..).collect(),
-                        ))
-                        .unwrap();
-                }
             },
             Err(e) => {
                 if e == "Session ended" {
@@ -156,6 +142,52 @@ pub(crate) fn stdin_loop(
     }
 }
 
+fn parse_input_events(
+    buf: &[u8],
+    current_buffer: &mut Vec<u8>,
+    input_parser: &mut InputParser,
+    holding_mouse: &mut bool,
+    os_input: &mut Box<dyn ClientOsApi>,
+    send_input_instructions: &SenderWithContext<InputInstruction>,
+) {
+    let maybe_more = false; // read_from_stdin should (hopefully) always empty the STDIN buffer completely
+    let mut events = vec![];
+    input_parser.parse(
+        buf,
+        |input_event: InputEvent| {
+            events.push(input_event);
+        },
+        maybe_more,
+    );
+
+    let event_count = events.len();
+    for (i, input_event) in events.into_iter().enumerate() {
+        if *holding_mouse && is_mouse_press_or_hold(&input_event) && i == event_count - 1 {
+            let mut poller = os_input.stdin_poller();
+            loop {
+                if poller.ready() {
+                    break;
+                }
+                send_input_instructions
+                    .send(InputInstruction::KeyEvent(
+                        input_event.clone(),
+                        current_buffer.clone(),
+                    ))
+                    .unwrap();
+            }
+        }
+
+        *holding_mouse = is_mouse_press_or_hold(&input_event);
+
+        send_input_instructions
+            .send(InputInstruction::KeyEvent(
+                input_event,
+                current_buffer.drain(..).collect(),
+            ))
+            .unwrap();
+    }
+}
+
 fn is_mouse_press_or_hold(input_event: &InputEvent) -> bool {
     if let InputEvent::Mouse(mouse_event) = input_event {
         if mouse_event.mouse_buttons.contains(MouseButtons::LEFT)
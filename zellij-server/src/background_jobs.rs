@@ -30,10 +30,12 @@ use crate::ClientId;
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
     DisplayPaneError(Vec<PaneId>, String),
-    AnimatePluginLoading(u32),                            // u32 - plugin_id
-    StopPluginLoadingAnimation(u32),                      // u32 - plugin_id
-    ReadAllSessionInfosOnMachine,                         // u32 - plugin_id
-    ReportSessionInfo(String, SessionInfo),               // String - session name
+    AnimatePluginLoading(u32),       // u32 - plugin_id
+    StopPluginLoadingAnimation(u32), // u32 - plugin_id
+    ReadAllSessionInfosOnMachine,    // u32 - plugin_id
+    MonitorPaneSilence,
+    StopMonitoringPaneSilence,
+    ReportSessionInfo(String, SessionInfo), // String - session name
     ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
     RunCommand(
         PluginId,
@@ -67,6 +69,10 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 BackgroundJobContext::ReadAllSessionInfosOnMachine
             },
+            BackgroundJob::MonitorPaneSilence => BackgroundJobContext::MonitorPaneSilence,
+            BackgroundJob::StopMonitoringPaneSilence => {
+                BackgroundJobContext::StopMonitoringPaneSilence
+            },
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
@@ -79,6 +85,7 @@ impl From<&BackgroundJob> for BackgroundJobContext {
 static FLASH_DURATION_MS: u64 = 1000;
 static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
+static PANE_SILENCE_CHECK_DURATION: u64 = 1000;
 static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
 
 pub(crate) fn background_jobs_main(
@@ -89,6 +96,7 @@ pub(crate) fn background_jobs_main(
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
     let mut loading_plugins: HashMap<u32, Arc<AtomicBool>> = HashMap::new(); // u32 - plugin_id
+    let mut monitoring_pane_silence: Option<Arc<AtomicBool>> = None;
     let current_session_name = Arc::new(Mutex::new(String::default()));
     let current_session_info = Arc::new(Mutex::new(SessionInfo::default()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
@@ -209,6 +217,33 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::MonitorPaneSilence => {
+                // this job periodically asks the screen to check whether panes monitoring
+                // silence have been silent for long enough, until it is stopped once no pane
+                // monitors silence anymore
+                if monitoring_pane_silence.is_some() {
+                    continue;
+                }
+                let is_monitoring = Arc::new(AtomicBool::new(true));
+                monitoring_pane_silence = Some(is_monitoring.clone());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        while is_monitoring.load(Ordering::SeqCst) {
+                            let _ = senders.send_to_screen(ScreenInstruction::CheckPaneSilence);
+                            task::sleep(std::time::Duration::from_millis(
+                                PANE_SILENCE_CHECK_DURATION,
+                            ))
+                            .await;
+                        }
+                    }
+                });
+            },
+            BackgroundJob::StopMonitoringPaneSilence => {
+                if let Some(is_monitoring) = monitoring_pane_silence.take() {
+                    is_monitoring.store(false, Ordering::SeqCst);
+                }
+            },
             BackgroundJob::RunCommand(
                 plugin_id,
                 client_id,
@@ -322,6 +357,9 @@ pub(crate) fn background_jobs_main(
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
+                if let Some(is_monitoring) = monitoring_pane_silence.take() {
+                    is_monitoring.store(false, Ordering::SeqCst);
+                }
 
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
@@ -41,7 +41,10 @@ use route::route_thread_main;
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
+    consts::{
+        scrollback_folder_for_session, DEFAULT_SCROLL_BUFFER_SIZE, SCROLLBACK_TO_DISK,
+        SCROLL_BUFFER_SIZE,
+    },
     data::{ConnectToSession, Event, PluginCapabilities},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
     home::{default_layout_dir, get_default_data_dir},
@@ -52,7 +55,7 @@ use zellij_utils::{
         options::Options,
         plugins::PluginAliases,
     },
-    ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
+    ipc::{ClientAttributes, ClientType, ExitReason, ServerToClientMsg},
 };
 
 pub type ClientId = u16;
@@ -80,6 +83,7 @@ pub enum ServerInstruction {
         Options,
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
+        ClientType,
         ClientId,
     ),
     ConnStatus(ClientId),
@@ -467,27 +471,29 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 options,
                 tab_position_to_focus,
                 pane_id_to_focus,
+                client_type,
                 client_id,
             ) => {
                 let rlock = session_data.read().unwrap();
                 let session_data = rlock.as_ref().unwrap();
-                session_state
-                    .write()
-                    .unwrap()
-                    .set_client_size(client_id, attrs.size);
-                let min_size = session_state
-                    .read()
-                    .unwrap()
-                    .min_client_terminal_size()
-                    .unwrap();
-                session_data
-                    .senders
-                    .send_to_screen(ScreenInstruction::TerminalResize(min_size))
-                    .unwrap();
+                // read-only clients are left without a size so that they don't resize the session
+                if client_type != ClientType::Reader {
+                    session_state
+                        .write()
+                        .unwrap()
+                        .set_client_size(client_id, attrs.size);
+                }
+                if let Some(min_size) = session_state.read().unwrap().min_client_terminal_size() {
+                    session_data
+                        .senders
+                        .send_to_screen(ScreenInstruction::TerminalResize(min_size))
+                        .unwrap();
+                }
                 session_data
                     .senders
                     .send_to_screen(ScreenInstruction::AddClient(
                         client_id,
+                        client_type == ClientType::Reader,
                         tab_position_to_focus,
                         pane_id_to_focus,
                     ))
@@ -833,6 +839,13 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
     // Drop cached session data before exit.
     *session_data.write().unwrap() = None;
 
+    // Remove scrollback that was spilled to disk by this session's panes.
+    if let Ok(session_name) = envs::get_session_name() {
+        drop(std::fs::remove_dir_all(scrollback_folder_for_session(
+            &session_name,
+        )));
+    }
+
     drop(std::fs::remove_file(&socket_path));
 }
 
@@ -860,6 +873,7 @@ fn init_session(
             .scroll_buffer_size
             .unwrap_or(DEFAULT_SCROLL_BUFFER_SIZE),
     );
+    let _ = SCROLLBACK_TO_DISK.set(config_options.scrollback_to_disk.unwrap_or(false));
 
     let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
     let to_screen = SenderWithContext::new(to_screen);
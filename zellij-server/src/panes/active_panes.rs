@@ -55,6 +55,14 @@ impl ActivePanes {
         }
         self.active_panes.remove(client_id)
     }
+    // change the focus of a client without sending focus events to the panes, for clients that
+    // only focus a pane for the duration of a single action
+    pub fn insert_without_focus_events(&mut self, client_id: ClientId, pane_id: PaneId) {
+        self.active_panes.insert(client_id, pane_id);
+    }
+    pub fn remove_without_focus_events(&mut self, client_id: &ClientId) -> Option<PaneId> {
+        self.active_panes.remove(client_id)
+    }
     pub fn unfocus_all_panes(&self, panes: &mut BTreeMap<PaneId, Box<dyn Pane>>) {
         for (_client_id, pane_id) in &self.active_panes {
             self.unfocus_pane(*pane_id, panes);
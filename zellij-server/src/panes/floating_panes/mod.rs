@@ -1,6 +1,6 @@
 mod floating_pane_grid;
 use zellij_utils::{
-    data::{Direction, PaneInfo, ResizeStrategy},
+    data::{Direction, FloatingPaneCoordinates, PaneInfo, ResizeStrategy},
     position::Position,
 };
 
@@ -25,7 +25,7 @@ use zellij_utils::{
     data::{ModeInfo, Style},
     errors::prelude::*,
     input::command::RunCommand,
-    input::layout::{FloatingPaneLayout, Run, RunPluginOrAlias},
+    input::layout::{FloatingPaneLayout, PercentOrFixed, Run, RunPluginOrAlias},
     pane_size::{Dimension, Offset, PaneGeom, Size, SizeInPixels, Viewport},
 };
 
@@ -47,6 +47,7 @@ pub struct FloatingPanes {
     z_indices: Vec<PaneId>,
     active_panes: ActivePanes,
     show_panes: bool,
+    show_pinned_panes_only: bool, // a pinned pane was focused while the others were hidden
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
     senders: ThreadSenders,
 }
@@ -81,19 +82,29 @@ impl FloatingPanes {
             desired_pane_positions: HashMap::new(),
             z_indices: vec![],
             show_panes: false,
+            show_pinned_panes_only: false,
             active_panes: ActivePanes::new(&os_input),
             pane_being_moved_with_mouse: None,
             senders,
         }
     }
     pub fn stack(&self) -> Option<FloatingPanesStack> {
-        if self.panes_are_visible() {
+        if self.panes_are_visible() && !self.show_pinned_panes_only {
             let layers = self
                 .z_indices
                 .iter()
                 .map(|pane_id| self.panes.get(pane_id).unwrap().position_and_size())
                 .collect();
             Some(FloatingPanesStack { layers })
+        } else if self.has_pinned_panes() {
+            let layers = self
+                .z_indices
+                .iter()
+                .filter_map(|pane_id| self.panes.get(pane_id))
+                .filter(|pane| pane.is_pinned())
+                .map(|pane| pane.position_and_size())
+                .collect();
+            Some(FloatingPanesStack { layers })
         } else {
             None
         }
@@ -201,6 +212,23 @@ impl FloatingPanes {
     pub fn has_panes(&self) -> bool {
         !self.panes.is_empty()
     }
+    pub fn has_pinned_panes(&self) -> bool {
+        self.panes.values().any(|p| p.is_pinned())
+    }
+    fn only_pinned_panes_are_shown(&self) -> bool {
+        !self.show_panes || self.show_pinned_panes_only
+    }
+    pub fn toggle_pane_pinned(&mut self, client_id: ClientId) {
+        if let Some(pane) = self.get_active_pane_mut(client_id) {
+            let is_pinned = pane.is_pinned();
+            pane.set_pinned(!is_pinned);
+            if is_pinned {
+                // the focused pane would otherwise disappear
+                self.show_pinned_panes_only = false;
+            }
+            self.set_force_render();
+        }
+    }
     pub fn active_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
         self.active_panes.get(&client_id).copied()
     }
@@ -214,12 +242,21 @@ impl FloatingPanes {
     }
     pub fn toggle_show_panes(&mut self, should_show_floating_panes: bool) {
         self.show_panes = should_show_floating_panes;
+        self.show_pinned_panes_only = false;
         if should_show_floating_panes {
             self.active_panes.focus_all_panes(&mut self.panes);
         } else {
             self.active_panes.unfocus_all_panes(&mut self.panes);
         }
     }
+    pub fn show_pinned_panes(&mut self) {
+        // focuses the floating layer while only showing its pinned panes
+        self.toggle_show_panes(true);
+        self.show_pinned_panes_only = true;
+    }
+    pub fn is_showing_pinned_panes_only(&self) -> bool {
+        self.show_panes && self.show_pinned_panes_only
+    }
     pub fn active_panes_contain(&self, client_id: &ClientId) -> bool {
         self.active_panes.contains_key(client_id)
     }
@@ -249,36 +286,8 @@ impl FloatingPanes {
             display_area,
             viewport,
         );
-        let mut position = floating_pane_grid.find_room_for_new_pane().unwrap(); // TODO: no unwrap
-        if let Some(x) = &floating_pane_layout.x {
-            position.x = x.to_position(viewport.cols);
-        }
-        if let Some(y) = &floating_pane_layout.y {
-            position.y = y.to_position(viewport.rows);
-        }
-        if let Some(width) = &floating_pane_layout.width {
-            position.cols = Dimension::fixed(width.to_position(viewport.cols));
-        }
-        if let Some(height) = &floating_pane_layout.height {
-            position.rows = Dimension::fixed(height.to_position(viewport.rows));
-        }
-        if position.cols.as_usize() > viewport.cols {
-            position.cols = Dimension::fixed(viewport.cols);
-        }
-        if position.rows.as_usize() > viewport.rows {
-            position.rows = Dimension::fixed(viewport.rows);
-        }
-        if position.x + position.cols.as_usize() > viewport.cols {
-            position.x = position
-                .x
-                .saturating_sub((position.x + position.cols.as_usize()) - viewport.cols);
-        }
-        if position.y + position.rows.as_usize() > viewport.rows {
-            position.y = position
-                .y
-                .saturating_sub((position.y + position.rows.as_usize()) - viewport.rows);
-        }
-        position
+        let position = floating_pane_grid.find_room_for_new_pane().unwrap(); // TODO: no unwrap
+        position_inside_viewport(position, floating_pane_layout, viewport)
     }
     pub fn first_floating_pane_id(&self) -> Option<PaneId> {
         self.panes.keys().next().copied()
@@ -289,6 +298,13 @@ impl FloatingPanes {
     pub fn first_active_floating_pane_id(&self) -> Option<PaneId> {
         self.active_panes.values().next().copied()
     }
+    pub fn set_action_target(&mut self, pane_id: PaneId, client_id: ClientId) {
+        self.active_panes
+            .insert_without_focus_events(client_id, pane_id);
+    }
+    pub fn clear_action_target(&mut self, client_id: ClientId) {
+        self.active_panes.remove_without_focus_events(&client_id);
+    }
     pub fn set_force_render(&mut self) {
         for pane in self.panes.values_mut() {
             pane.set_should_render(true);
@@ -317,6 +333,7 @@ impl FloatingPanes {
         let err_context = || "failed to render output";
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
+        let only_pinned_panes_are_shown = self.only_pinned_panes_are_shown();
         let mut floating_panes: Vec<_> = self.panes.iter_mut().collect();
         floating_panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             self.z_indices
@@ -334,8 +351,18 @@ impl FloatingPanes {
                 )
         });
 
+        if only_pinned_panes_are_shown {
+            // only pinned panes are rendered when the floating panes are hidden, or when a
+            // pinned pane was focused while they were
+            floating_panes.retain(|(_, pane)| pane.is_pinned());
+        }
+
         for (z_index, (kind, pane)) in floating_panes.iter_mut().enumerate() {
-            let mut active_panes = self.active_panes.clone_active_panes();
+            let mut active_panes = if self.show_panes {
+                self.active_panes.clone_active_panes()
+            } else {
+                HashMap::new()
+            };
             let multiple_users_exist_in_session =
                 { self.connected_clients_in_app.borrow().len() > 1 };
             active_panes.retain(|c_id, _| self.connected_clients.borrow().contains(c_id));
@@ -692,6 +719,15 @@ impl FloatingPanes {
         self.set_force_render();
     }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
+        if self
+            .panes
+            .get(&pane_id)
+            .map(|p| !p.is_pinned())
+            .unwrap_or(false)
+        {
+            // focusing a pane that is not pinned brings back the rest of the floating panes
+            self.show_pinned_panes_only = false;
+        }
         self.active_panes
             .insert(client_id, pane_id, &mut self.panes);
         self.focus_pane_for_all_clients(pane_id);
@@ -726,6 +762,9 @@ impl FloatingPanes {
         } else {
             self.panes.iter().collect()
         };
+        if self.only_pinned_panes_are_shown() {
+            panes.retain(|(_, p)| p.is_pinned());
+        }
         panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             // TODO: continue
             Ord::cmp(
@@ -899,6 +938,63 @@ impl FloatingPanes {
         }
         pane_infos
     }
+    pub fn change_pane_coordinates(
+        &mut self,
+        pane_id: PaneId,
+        new_coordinates: FloatingPaneCoordinates,
+    ) -> Result<()> {
+        let err_context = || format!("failed to change coordinates of floating pane {pane_id:?}");
+        let current_geom = self
+            .panes
+            .get(&pane_id)
+            .with_context(err_context)?
+            .position_and_size();
+        // coordinates that were not given stay as they are, and the pane is then kept inside the
+        // viewport like the ones placed by a layout
+        let floating_pane_layout = FloatingPaneLayout {
+            x: Some(
+                new_coordinates
+                    .x
+                    .map(|x| x.into())
+                    .unwrap_or(PercentOrFixed::Fixed(current_geom.x)),
+            ),
+            y: Some(
+                new_coordinates
+                    .y
+                    .map(|y| y.into())
+                    .unwrap_or(PercentOrFixed::Fixed(current_geom.y)),
+            ),
+            width: Some(
+                new_coordinates
+                    .width
+                    .map(|width| width.into())
+                    .unwrap_or(PercentOrFixed::Fixed(current_geom.cols.as_usize())),
+            ),
+            height: Some(
+                new_coordinates
+                    .height
+                    .map(|height| height.into())
+                    .unwrap_or(PercentOrFixed::Fixed(current_geom.rows.as_usize())),
+            ),
+            ..Default::default()
+        };
+        // unlike a new pane, this one already has a place, so we don't look for room for it
+        let viewport = *self.viewport.borrow();
+        let pane_geom = position_inside_viewport(current_geom, &floating_pane_layout, viewport);
+        self.set_pane_geom(pane_id, pane_geom)
+            .with_context(err_context)
+    }
+    pub fn set_pane_geom(&mut self, pane_id: PaneId, pane_geom: PaneGeom) -> Result<()> {
+        let err_context = || format!("failed to set geometry of floating pane {pane_id:?}");
+        let pane = self.panes.get_mut(&pane_id).with_context(err_context)?;
+        pane.set_geom(pane_geom);
+        resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+            .with_context(err_context)?;
+        // this is an intentional move, so we want the pane to stay here when the screen is resized
+        self.desired_pane_positions.insert(pane_id, pane_geom);
+        self.set_force_render();
+        Ok(())
+    }
     pub fn set_geom_for_pane_with_run(&mut self, run: Option<Run>, geom: PaneGeom) {
         match self
             .panes
@@ -914,3 +1010,41 @@ impl FloatingPanes {
         }
     }
 }
+
+// places a floating pane at the coordinates of the layout (those it does not give are taken from
+// the position), making sure it fits inside the viewport
+fn position_inside_viewport(
+    mut position: PaneGeom,
+    floating_pane_layout: &FloatingPaneLayout,
+    viewport: Viewport,
+) -> PaneGeom {
+    if let Some(x) = &floating_pane_layout.x {
+        position.x = x.to_position(viewport.cols);
+    }
+    if let Some(y) = &floating_pane_layout.y {
+        position.y = y.to_position(viewport.rows);
+    }
+    if let Some(width) = &floating_pane_layout.width {
+        position.cols = Dimension::fixed(width.to_position(viewport.cols));
+    }
+    if let Some(height) = &floating_pane_layout.height {
+        position.rows = Dimension::fixed(height.to_position(viewport.rows));
+    }
+    if position.cols.as_usize() > viewport.cols {
+        position.cols = Dimension::fixed(viewport.cols);
+    }
+    if position.rows.as_usize() > viewport.rows {
+        position.rows = Dimension::fixed(viewport.rows);
+    }
+    if position.x + position.cols.as_usize() > viewport.cols {
+        position.x = position
+            .x
+            .saturating_sub((position.x + position.cols.as_usize()) - viewport.cols);
+    }
+    if position.y + position.rows.as_usize() > viewport.rows {
+        position.y = position
+            .y
+            .saturating_sub((position.y + position.rows.as_usize()) - viewport.rows);
+    }
+    position
+}
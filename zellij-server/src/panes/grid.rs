@@ -1,3 +1,6 @@
+use super::kitty_graphics::{
+    ApcParser, KittyGraphics, KittyGraphicsCommand, KittyImage, KittyPlacement,
+};
 use super::sixel::{PixelRect, SixelGrid, SixelImageStore};
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -11,19 +14,23 @@ use std::{
     cmp::Ordering,
     collections::{BTreeSet, VecDeque},
     fmt::{self, Debug, Formatter},
+    path::PathBuf,
     str,
 };
+use url::Url;
 
 use zellij_utils::{
     consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
     data::{Palette, PaletteColor},
+    input::kitty_keyboard::KittyKeyboardFlags,
     pane_size::SizeInPixels,
-    position::Position,
+    position::{Column, Line, Position},
     vte,
 };
 
 const TABSTOP_WIDTH: usize = 8; // TODO: is this always right?
 pub const MAX_TITLE_STACK_SIZE: usize = 1000;
+pub const MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE: usize = 16;
 
 use vte::{Params, Perform};
 use zellij_utils::{consts::VERSION, shared::version_number};
@@ -31,6 +38,7 @@ use zellij_utils::{consts::VERSION, shared::version_number};
 use crate::output::{CharacterChunk, OutputBuffer, SixelImageChunk};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
 use crate::panes::link_handler::LinkHandler;
+use crate::panes::scrollback::Scrollback;
 use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::{
@@ -56,20 +64,16 @@ fn get_top_non_canonical_rows(rows: &mut Vec<Row>) -> Vec<Row> {
     }
 }
 
-fn get_lines_above_bottom_canonical_row_and_wraps(rows: &mut VecDeque<Row>) -> Vec<Row> {
-    let mut index_of_last_non_canonical_row = None;
-    for (i, row) in rows.iter().enumerate().rev() {
-        index_of_last_non_canonical_row = Some(i);
-        if row.is_canonical {
+fn get_lines_above_bottom_canonical_row_and_wraps(lines_above: &mut Scrollback) -> Vec<Row> {
+    let mut rows = vec![];
+    while let Some(row) = lines_above.pop_back() {
+        let is_canonical = row.is_canonical;
+        rows.insert(0, row);
+        if is_canonical {
             break;
         }
     }
-    match index_of_last_non_canonical_row {
-        Some(index_of_last_non_canonical_row) => {
-            rows.drain(index_of_last_non_canonical_row..).collect()
-        },
-        None => vec![],
-    }
+    rows
 }
 
 fn get_viewport_bottom_canonical_row_and_wraps(viewport: &mut Vec<Row>) -> Vec<Row> {
@@ -116,7 +120,7 @@ fn get_top_canonical_row_and_wraps(rows: &mut Vec<Row>) -> Vec<Row> {
 }
 
 fn transfer_rows_from_lines_above_to_viewport(
-    lines_above: &mut VecDeque<Row>,
+    lines_above: &mut Scrollback,
     viewport: &mut Vec<Row>,
     sixel_grid: &mut SixelGrid,
     count: usize,
@@ -160,7 +164,7 @@ fn transfer_rows_from_lines_above_to_viewport(
 
 fn transfer_rows_from_viewport_to_lines_above(
     viewport: &mut Vec<Row>,
-    lines_above: &mut VecDeque<Row>,
+    lines_above: &mut Scrollback,
     sixel_grid: &mut SixelGrid,
     count: usize,
     max_viewport_width: usize,
@@ -226,16 +230,21 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, sixel_grid: &mut SixelGrid, value: Row) -> Option<usize> {
+fn bounded_push(
+    lines_above: &mut Scrollback,
+    sixel_grid: &mut SixelGrid,
+    value: Row,
+) -> Option<usize> {
     let mut dropped_line_width = None;
-    if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
-        let line = vec.pop_front();
-        if let Some(line) = line {
+    if lines_above.rows_in_memory() >= *SCROLL_BUFFER_SIZE.get().unwrap()
+        && !lines_above.spill_front()
+    {
+        if let Some(width) = lines_above.drop_front() {
             sixel_grid.offset_grid_top();
-            dropped_line_width = Some(line.width());
+            dropped_line_width = Some(width);
         }
     }
-    vec.push_back(value);
+    lines_above.push_back(value);
     dropped_line_width
 }
 
@@ -309,9 +318,8 @@ fn utf8_mouse_coordinates(column: usize, line: isize) -> Vec<u8> {
     coordinates
 }
 
-#[derive(Clone)]
 pub struct Grid {
-    pub(crate) lines_above: VecDeque<Row>,
+    pub(crate) lines_above: Scrollback,
     pub(crate) viewport: Vec<Row>,
     pub(crate) lines_below: Vec<Row>,
     horizontal_tabstops: BTreeSet<usize>,
@@ -332,6 +340,8 @@ pub struct Grid {
     title_stack: Vec<String>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     sixel_grid: SixelGrid,
+    apc_parser: ApcParser,
+    kitty_graphics: KittyGraphics,
     pub changed_colors: Option<[Option<AnsiCode>; 256]>,
     pub should_render: bool,
     pub lock_renders: bool,
@@ -351,6 +361,12 @@ pub struct Grid {
     pub is_scrolled: bool,
     pub link_handler: Rc<RefCell<LinkHandler>>,
     pub ring_bell: bool,
+    pub pending_bell: bool, // the bell rang since last drained, regardless of rendering
+    pub last_command_exit_code: Option<i32>, // as reported by the shell through OSC 133
+    pub selected_command_exit_code: Option<i32>, // of the command whose output was last selected
+    pub cwd: Option<PathBuf>, // as reported by the shell through OSC 7
+    pub pending_cwd_update: Option<PathBuf>,
+    pub pending_notifications: Vec<(Option<String>, String)>, // (title, body)
     scrollback_buffer_lines: usize,
     pub mouse_mode: MouseMode,
     pub mouse_tracking: MouseTracking,
@@ -362,9 +378,9 @@ pub struct Grid {
     debug: bool,
     arrow_fonts: bool,
     styled_underlines: bool,
-    pub supports_kitty_keyboard_protocol: bool, // has the app requested kitty keyboard support?
+    kitty_keyboard_flags_stack: Vec<KittyKeyboardFlags>, // enhancements requested by the app
     explicitly_disable_kitty_keyboard_protocol: bool, // has kitty keyboard support been explicitly
-                                                // disabled by user config?
+                                                      // disabled by user config?
 }
 
 #[derive(Clone, Debug)]
@@ -455,6 +471,7 @@ impl Grid {
         styled_underlines: bool,
         explicitly_disable_kitty_keyboard_protocol: bool,
     ) -> Self {
+        let kitty_graphics = KittyGraphics::new(sixel_image_store.clone());
         let sixel_grid = SixelGrid::new(character_cell_size.clone(), sixel_image_store);
         // make sure this is initialized as it is used internally
         // if it was already initialized (which should happen normally unless this is a test or
@@ -462,7 +479,7 @@ impl Grid {
         // I don't know why this needs to be a OneCell, but whatevs
         let _ = SCROLL_BUFFER_SIZE.set(DEFAULT_SCROLL_BUFFER_SIZE);
         Grid {
-            lines_above: VecDeque::new(),
+            lines_above: Scrollback::new(),
             viewport: vec![Row::new().canonical()],
             lines_below: vec![],
             horizontal_tabstops: create_horizontal_tabstops(columns),
@@ -495,6 +512,8 @@ impl Grid {
             is_scrolled: false,
             link_handler,
             ring_bell: false,
+            last_command_exit_code: None,
+            selected_command_exit_code: None,
             scrollback_buffer_lines: 0,
             mouse_mode: MouseMode::default(),
             mouse_tracking: MouseTracking::default(),
@@ -502,14 +521,20 @@ impl Grid {
             character_cell_size,
             search_results: Default::default(),
             sixel_grid,
+            apc_parser: ApcParser::default(),
+            kitty_graphics,
             pending_clipboard_update: None,
+            cwd: None,
+            pending_cwd_update: None,
+            pending_notifications: vec![],
+            pending_bell: false,
             ui_component_bytes: None,
             style,
             debug,
             arrow_fonts,
             styled_underlines,
             lock_renders: false,
-            supports_kitty_keyboard_protocol: false,
+            kitty_keyboard_flags_stack: vec![],
             explicitly_disable_kitty_keyboard_protocol,
         }
     }
@@ -567,8 +592,7 @@ impl Grid {
 
     fn recalculate_scrollback_buffer_count(&self) -> usize {
         let mut scrollback_buffer_count = 0;
-        for row in &self.lines_above {
-            let row_width = row.width();
+        for row_width in self.lines_above.widths() {
             // rows in lines_above are unwrapped, so we need to account for that
             if row_width > self.width {
                 scrollback_buffer_count += calculate_row_display_height(row_width, self.width);
@@ -825,7 +849,11 @@ impl Grid {
             for mut canonical_line in viewport_canonical_lines {
                 let mut canonical_line_parts: Vec<Row> = vec![];
                 if canonical_line.columns.is_empty() {
-                    canonical_line_parts.push(Row::new().canonical());
+                    canonical_line_parts.push(
+                        Row::new()
+                            .canonical()
+                            .with_semantic_prompt_marks(canonical_line.semantic_prompt_marks),
+                    );
                 }
                 while !canonical_line.columns.is_empty() {
                     let next_wrap = canonical_line.drain_until(new_columns);
@@ -843,6 +871,11 @@ impl Grid {
                     } else {
                         row
                     };
+                    let row = if canonical_line_parts.is_empty() {
+                        row.with_semantic_prompt_marks(canonical_line.semantic_prompt_marks)
+                    } else {
+                        row
+                    };
                     canonical_line_parts.push(row);
                 }
                 new_viewport_rows.append(&mut canonical_line_parts);
@@ -1057,7 +1090,7 @@ impl Grid {
                 };
                 let mut to_serialize = vec![];
                 for line in self.lines_above.iter().skip(first_index) {
-                    to_serialize.push(line.clone());
+                    to_serialize.push(line.into_owned());
                 }
                 for line in &self.viewport {
                     to_serialize.push(line.clone())
@@ -1177,7 +1210,8 @@ impl Grid {
         self.output_buffer.update_all_lines();
     }
     pub fn reset_viewport(&mut self) {
-        let max_lines_to_scroll = *SCROLL_BUFFER_SIZE.get().unwrap() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
+        let max_lines_to_scroll =
+            (*SCROLL_BUFFER_SIZE.get().unwrap()).max(self.lines_below.len()) * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
         let mut lines_scrolled = 0;
         let should_clear_output_buffer = self.is_scrolled;
         while self.is_scrolled && lines_scrolled < max_lines_to_scroll {
@@ -1188,6 +1222,49 @@ impl Grid {
             self.output_buffer.update_all_lines();
         }
     }
+    pub fn scroll_to_previous_prompt(&mut self) {
+        // the top rows of the viewport might be wrapped parts of the last line above it
+        let wrapped_rows_in_viewport = self
+            .viewport
+            .iter()
+            .take_while(|row| !row.is_canonical)
+            .count();
+        let mut lines_to_scroll = 0;
+        for (i, row) in self.lines_above.iter().rev().enumerate() {
+            let row_height = calculate_row_display_height(row.width(), self.width);
+            lines_to_scroll += if i == 0 {
+                row_height.saturating_sub(wrapped_rows_in_viewport)
+            } else {
+                row_height
+            };
+            if row.semantic_prompt_marks.prompt_start {
+                self.move_viewport_up(lines_to_scroll);
+                return;
+            }
+        }
+    }
+    pub fn scroll_to_next_prompt(&mut self) {
+        let next_prompt_in_viewport = self
+            .viewport
+            .iter()
+            .skip(1)
+            .position(|row| row.semantic_prompt_marks.prompt_start)
+            .map(|index| index + 1);
+        let next_prompt = next_prompt_in_viewport.or_else(|| {
+            // a row below the viewport might hold several wrapped rows joined together
+            let mut rows_to_scroll = self.viewport.len();
+            for row in self.lines_below.iter() {
+                if row.semantic_prompt_marks.prompt_start {
+                    return Some(rows_to_scroll);
+                }
+                rows_to_scroll += calculate_row_display_height(row.width(), self.width);
+            }
+            None
+        });
+        if let Some(lines_to_scroll) = next_prompt {
+            self.move_viewport_down(lines_to_scroll);
+        }
+    }
     pub fn rotate_scroll_region_up(&mut self, count: usize) {
         if let Some((scroll_region_top, scroll_region_bottom)) = self
             .scroll_region
@@ -1693,7 +1770,7 @@ impl Grid {
         self.should_render = true;
     }
     pub fn reset_terminal_state(&mut self) {
-        self.lines_above = VecDeque::new();
+        self.lines_above = Scrollback::new();
         self.lines_below = vec![];
         self.viewport = vec![Row::new().canonical()];
         self.alternate_screen_state = None;
@@ -1719,6 +1796,52 @@ impl Grid {
         if let Some(images_to_reap) = self.sixel_grid.clear() {
             self.sixel_grid.reap_images(images_to_reap);
         }
+        self.kitty_graphics = KittyGraphics::new(self.sixel_grid.sixel_image_store.clone());
+        self.kitty_keyboard_flags_stack.clear();
+    }
+    fn semantic_prompt_marks_at_cursor(&mut self) -> Option<&mut SemanticPromptMarks> {
+        for _ in self.viewport.len()..=self.cursor.y {
+            self.viewport.push(Row::new().canonical());
+        }
+        let cursor_y = self.cursor.y;
+        match self
+            .viewport
+            .iter_mut()
+            .take(cursor_y + 1)
+            .rev()
+            .find(|row| row.is_canonical)
+        {
+            Some(canonical_row) => Some(&mut canonical_row.semantic_prompt_marks),
+            None => self
+                .lines_above
+                .back_mut()
+                .map(|row| &mut row.semantic_prompt_marks),
+        }
+    }
+    fn add_semantic_prompt_mark(&mut self, params: &[&[u8]]) {
+        let mark = match params.get(1).and_then(|mark| mark.first()) {
+            Some(mark) => *mark,
+            None => return,
+        };
+        let exit_code = params
+            .get(2)
+            .and_then(|exit_code| str::from_utf8(exit_code).ok())
+            .and_then(|exit_code| exit_code.parse().ok());
+        if let Some(semantic_prompt_marks) = self.semantic_prompt_marks_at_cursor() {
+            match mark {
+                b'A' => semantic_prompt_marks.prompt_start = true,
+                b'B' => semantic_prompt_marks.command_start = true,
+                b'C' => semantic_prompt_marks.output_start = true,
+                b'D' => {
+                    semantic_prompt_marks.command_end = true;
+                    semantic_prompt_marks.exit_code = exit_code;
+                },
+                _ => {},
+            }
+        }
+        if mark == b'D' {
+            self.last_command_exit_code = exit_code;
+        }
     }
     fn set_preceding_character(&mut self, terminal_character: TerminalCharacter) {
         self.preceding_char = Some(terminal_character);
@@ -1766,8 +1889,12 @@ impl Grid {
             let start_column = if l == start.line.0 { start.column.0 } else { 0 };
 
             // same thing on the last line, but with the selection end column
+            // rows in lines_above are unwrapped and so might be wider than the viewport, which
+            // matters when selecting whole rows (eg. the output of a command)
             let end_column = if l == end.line.0 {
                 end.column.0
+            } else if self.selection.selects_whole_rows() {
+                usize::MAX
             } else {
                 self.width
             };
@@ -1780,19 +1907,23 @@ impl Grid {
                 Row::from_columns(VecDeque::from(vec![EMPTY_TERMINAL_CHARACTER; self.width]));
 
             // get the row from lines_above, viewport, or lines below depending on index
-            let row = if l < 0 && self.lines_above.len() > l.abs() as usize {
+            let row = if l < 0 && self.lines_above.len() >= l.abs() as usize {
                 let offset_from_end = l.abs();
-                &self.lines_above[self
-                    .lines_above
-                    .len()
-                    .saturating_sub(offset_from_end as usize)]
+                match self.lines_above.get(
+                    self.lines_above
+                        .len()
+                        .saturating_sub(offset_from_end as usize),
+                ) {
+                    Some(row) => row,
+                    None => continue,
+                }
             } else if l >= 0 && (l as usize) < self.viewport.len() {
-                &self.viewport[l as usize]
+                Cow::Borrowed(&self.viewport[l as usize])
             } else if (l as usize) < self.height {
                 // index is in viewport but there is no line
-                &empty_row
+                Cow::Owned(empty_row)
             } else if self.lines_below.len() > (l as usize).saturating_sub(self.viewport.len()) {
-                &self.lines_below[(l as usize) - self.viewport.len()]
+                Cow::Borrowed(&self.lines_below[(l as usize) - self.viewport.len()])
             } else {
                 // can't find the line, this probably it's on the pane border
                 // is on the pane border
@@ -1830,6 +1961,98 @@ impl Grid {
             Some(selection.join("\n"))
         }
     }
+    /// Selects the output of the most recent command - or if the pane is scrolled, that of the
+    /// command whose prompt is at the top of the viewport or above it - as delimited by OSC 133
+    /// marks. Returns true if such an output was found.
+    pub fn select_command_output(&mut self) -> bool {
+        let first_line = -(self.lines_above.len() as isize);
+        let last_line = if self.is_scrolled {
+            (self.viewport.len() + self.lines_below.len()) as isize - 1
+        } else {
+            self.cursor.y as isize
+        };
+        let marks_at = |line: isize| self.semantic_prompt_marks_at_selection_line(line);
+        let output_start = if self.is_scrolled {
+            (first_line..=0)
+                .rev()
+                .find(|line| marks_at(*line).prompt_start)
+                .and_then(|prompt_line| {
+                    (prompt_line..=last_line)
+                        .take_while(|line| *line == prompt_line || !marks_at(*line).prompt_start)
+                        .find(|line| marks_at(*line).output_start)
+                })
+        } else {
+            (first_line..=last_line)
+                .rev()
+                .find(|line| marks_at(*line).output_start)
+        };
+        let output_start = match output_start {
+            Some(output_start) => output_start,
+            None => return false,
+        };
+        let output_end = (output_start..=last_line)
+            .find(|line| {
+                let marks = marks_at(*line);
+                marks.command_end || (*line != output_start && marks.prompt_start)
+            })
+            .unwrap_or(last_line + 1);
+        if output_end <= output_start {
+            // the command did not output anything
+            return false;
+        }
+        let last_output_line = output_end - 1;
+        self.selected_command_exit_code = self
+            .row_at_selection_line(output_end)
+            .and_then(|row| row.semantic_prompt_marks.exit_code);
+        let last_output_line_width = self
+            .row_at_selection_line(last_output_line)
+            .map(|row| row.width())
+            .unwrap_or(0);
+        let old_selection = self.selection;
+        self.selection.select_whole_rows(
+            Position {
+                line: Line(output_start),
+                column: Column(0),
+            },
+            Position {
+                line: Line(last_output_line),
+                column: Column(last_output_line_width),
+            },
+        );
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+        true
+    }
+    fn row_at_selection_line(&self, line: isize) -> Option<Cow<'_, Row>> {
+        // same indexing as the selection: negative lines are in lines_above, the rest are in the
+        // viewport and then lines_below
+        if line < 0 {
+            self.lines_above
+                .len()
+                .checked_sub(line.unsigned_abs())
+                .and_then(|index| self.lines_above.get(index))
+        } else if (line as usize) < self.viewport.len() {
+            self.viewport.get(line as usize).map(Cow::Borrowed)
+        } else {
+            self.lines_below
+                .get(line as usize - self.viewport.len())
+                .map(Cow::Borrowed)
+        }
+    }
+    fn semantic_prompt_marks_at_selection_line(&self, line: isize) -> SemanticPromptMarks {
+        // rows above the viewport are not decompressed for this
+        if line < 0 {
+            self.lines_above
+                .len()
+                .checked_sub(line.unsigned_abs())
+                .and_then(|index| self.lines_above.semantic_prompt_marks(index))
+                .unwrap_or_default()
+        } else {
+            self.row_at_selection_line(line)
+                .map(|row| row.semantic_prompt_marks)
+                .unwrap_or_default()
+        }
+    }
     pub fn absolute_position_in_scrollback(&self) -> usize {
         self.lines_above.len() + self.cursor.y
     }
@@ -1855,6 +2078,38 @@ impl Grid {
             self.title = Some(popped_title);
         }
     }
+    pub fn kitty_keyboard_flags(&self) -> KittyKeyboardFlags {
+        self.kitty_keyboard_flags_stack
+            .last()
+            .copied()
+            .unwrap_or(KittyKeyboardFlags::empty())
+    }
+    pub fn supports_kitty_keyboard_protocol(&self) -> bool {
+        !self.kitty_keyboard_flags().is_empty()
+    }
+    fn push_kitty_keyboard_flags(&mut self, flags: KittyKeyboardFlags) {
+        if self.kitty_keyboard_flags_stack.len() >= MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE {
+            self.kitty_keyboard_flags_stack.remove(0);
+        }
+        self.kitty_keyboard_flags_stack.push(flags);
+    }
+    fn pop_kitty_keyboard_flags(&mut self, count: usize) {
+        let remaining = self.kitty_keyboard_flags_stack.len().saturating_sub(count);
+        self.kitty_keyboard_flags_stack.truncate(remaining);
+    }
+    fn set_kitty_keyboard_flags(&mut self, flags: KittyKeyboardFlags, mode: usize) {
+        let current_flags = self.kitty_keyboard_flags();
+        let flags = match mode {
+            1 => flags,
+            2 => current_flags | flags,
+            3 => current_flags & !flags,
+            _ => return,
+        };
+        match self.kitty_keyboard_flags_stack.last_mut() {
+            Some(current_flags) => *current_flags = flags,
+            None => self.kitty_keyboard_flags_stack.push(flags),
+        }
+    }
     fn transfer_rows_to_lines_above(&mut self, count: usize) {
         let transferred_rows_count = transfer_rows_from_viewport_to_lines_above(
             &mut self.viewport,
@@ -1918,6 +2173,207 @@ impl Grid {
             }
         }
     }
+    pub fn handle_apc_byte(&mut self, byte: u8) {
+        if let Some(apc) = self.apc_parser.advance(byte) {
+            if let Some(kitty_graphics_command) = KittyGraphicsCommand::parse(&apc) {
+                self.handle_kitty_graphics_command(kitty_graphics_command);
+            }
+        }
+    }
+    fn handle_kitty_graphics_command(&mut self, command: KittyGraphicsCommand) {
+        let mut command = match self.kitty_graphics.add_chunk(command) {
+            Some(command) => command,
+            None => return, // waiting for more chunks
+        };
+        let result = match command.action {
+            'q' => self
+                .kitty_graphics_cell_size()
+                .and_then(|_| KittyImage::load(&command).map(|_| ())),
+            't' | 'T' => self.transmit_kitty_image(&mut command),
+            'p' => {
+                command.image_id = self.kitty_graphics.image_id(&command);
+                command
+                    .image_id
+                    .ok_or_else(|| "ENOENT:image not found".to_owned())
+                    .and_then(|image_id| self.place_kitty_image(&command, image_id))
+            },
+            'd' => {
+                self.delete_kitty_images(&command);
+                Ok(())
+            },
+            _ => Ok(()), // animations are not supported
+        };
+        if let Some(response) = command.response(&result) {
+            self.pending_messages_to_pty.push(response.into_bytes());
+        }
+    }
+    fn kitty_graphics_cell_size(&self) -> Result<SizeInPixels, String> {
+        // like with sixel, we can only display images if we know the pixel size of each
+        // character cell
+        let character_cell_size = *self.character_cell_size.borrow();
+        character_cell_size
+            .ok_or_else(|| "ENOTSUP:the pixel size of the terminal is unknown".to_owned())
+    }
+    fn transmit_kitty_image(&mut self, command: &mut KittyGraphicsCommand) -> Result<(), String> {
+        let image = KittyImage::load(command)?;
+        let image_id = self.kitty_graphics.add_image(command, image);
+        if command.action == 'T' {
+            self.place_kitty_image(command, image_id)?;
+        }
+        Ok(())
+    }
+    fn place_kitty_image(
+        &mut self,
+        command: &KittyGraphicsCommand,
+        image_id: u32,
+    ) -> Result<(), String> {
+        let character_cell_size = self.kitty_graphics_cell_size()?;
+        let image = self
+            .kitty_graphics
+            .placed_image(image_id, command, character_cell_size)?;
+        // the offsets and the size of the image come from the application, so they are not
+        // trusted not to overflow
+        let invalid_placement = || "EINVAL:invalid placement dimensions".to_owned();
+        let x_pixel_coordinates = self
+            .cursor
+            .x
+            .checked_mul(character_cell_size.width)
+            .and_then(|x| x.checked_add(command.x_offset))
+            .ok_or_else(invalid_placement)?;
+        let y_pixel_coordinates = (self.lines_above.len() + self.cursor.y)
+            .checked_mul(character_cell_size.height)
+            .and_then(|y| y.checked_add(command.y_offset))
+            .ok_or_else(invalid_placement)?;
+        // the cursor is placed after the last column of the image, on its last line
+        let rows = image
+            .height
+            .checked_add(command.y_offset)
+            .map(|height| height.div_ceil(character_cell_size.height))
+            .ok_or_else(invalid_placement)?;
+        let columns = image
+            .width
+            .checked_add(command.x_offset)
+            .map(|width| width.div_ceil(character_cell_size.width))
+            .ok_or_else(invalid_placement)?;
+        let sixel_image = image
+            .to_sixel_image()
+            .ok_or_else(|| "EINVAL:failed to encode image".to_owned())?;
+        self.prune_kitty_placements();
+        let new_image_id = self.sixel_grid.next_image_id();
+        // the sixel image height is rounded up to a multiple of 6 pixels, so we place it with
+        // the exact size of the kitty image instead
+        self.sixel_grid.place_image(
+            new_image_id,
+            (image.height, image.width),
+            x_pixel_coordinates,
+            y_pixel_coordinates,
+        );
+        self.sixel_grid.new_sixel_image(new_image_id, sixel_image);
+        if let Some(replaced_placement) = self.kitty_graphics.add_placement(KittyPlacement {
+            image_id,
+            placement_id: command.placement_id,
+            sixel_image_id: new_image_id,
+        }) {
+            self.sixel_grid
+                .remove_image(replaced_placement.sixel_image_id);
+        }
+        if command.move_cursor {
+            for _ in 1..rows {
+                self.add_canonical_line();
+            }
+            self.move_cursor_forward_until_edge(columns);
+        }
+        self.render_full_viewport();
+        Ok(())
+    }
+    fn delete_kitty_images(&mut self, command: &KittyGraphicsCommand) {
+        self.prune_kitty_placements();
+        let character_cell_size = match *self.character_cell_size.borrow() {
+            Some(character_cell_size) => character_cell_size,
+            None => return,
+        };
+        let line_count_in_scrollback = self.lines_above.len();
+        let total_height = (line_count_in_scrollback + self.height) * character_cell_size.height;
+        let total_width = self.width * character_cell_size.width;
+        // x/y are 1-based cell coordinates in the viewport
+        let cell_x = |x: usize| x.saturating_sub(1) * character_cell_size.width;
+        let cell_y = |y: usize| {
+            (line_count_in_scrollback + y.saturating_sub(1)) * character_cell_size.height
+        };
+        let rect_to_delete = match command.delete.to_ascii_lowercase() {
+            'c' => Some(PixelRect::new(
+                cell_x(self.cursor.x + 1),
+                cell_y(self.cursor.y + 1),
+                character_cell_size.height,
+                character_cell_size.width,
+            )),
+            'p' => Some(PixelRect::new(
+                cell_x(command.source_x),
+                cell_y(command.source_y),
+                character_cell_size.height,
+                character_cell_size.width,
+            )),
+            'x' => Some(PixelRect::new(
+                cell_x(command.source_x),
+                0,
+                total_height,
+                character_cell_size.width,
+            )),
+            'y' => Some(PixelRect::new(
+                0,
+                cell_y(command.source_y),
+                character_cell_size.height,
+                total_width,
+            )),
+            _ => None,
+        };
+        let image_id = self.kitty_graphics.image_id(command);
+        let sixel_grid = &self.sixel_grid;
+        let removed_placements = self.kitty_graphics.remove_placements(|placement| {
+            match command.delete.to_ascii_lowercase() {
+                'a' => true,
+                'i' | 'n' => {
+                    Some(placement.image_id) == image_id
+                        && (command.placement_id.is_none()
+                            || command.placement_id == placement.placement_id)
+                },
+                'c' | 'p' | 'x' | 'y' => sixel_grid
+                    .image_location(placement.sixel_image_id)
+                    .zip(rect_to_delete)
+                    .and_then(|(location, rect_to_delete)| {
+                        location.intersecting_rect(&rect_to_delete)
+                    })
+                    .is_some(),
+                _ => false, // deleting by z-index or animation frames is not supported
+            }
+        });
+        if command.delete.is_ascii_uppercase() {
+            // upper case deletions also free the image data
+            let image_ids_to_free = match command.delete {
+                'I' | 'N' => image_id.into_iter().collect(),
+                'A' => self.kitty_graphics.image_ids(),
+                _ => removed_placements.iter().map(|p| p.image_id).collect(),
+            };
+            self.kitty_graphics
+                .remove_images_without_placements(image_ids_to_free.into_iter());
+        }
+        for placement in &removed_placements {
+            self.sixel_grid.remove_image(placement.sixel_image_id);
+        }
+        if !removed_placements.is_empty() {
+            self.render_full_viewport();
+        }
+    }
+    fn prune_kitty_placements(&mut self) {
+        // images might have been removed from the sixel grid (eg. when scrolled off or
+        // overwritten)
+        let sixel_grid = &self.sixel_grid;
+        self.kitty_graphics.retain_placements(|placement| {
+            sixel_grid
+                .image_location(placement.sixel_image_id)
+                .is_some()
+        });
+    }
     pub fn mouse_left_click_signal(&self, position: &Position, is_held: bool) -> Option<String> {
         let utf8_event = || -> Option<String> {
             let button_code = if is_held { b'@' } else { b' ' };
@@ -2168,6 +2624,7 @@ impl Perform for Grid {
         match byte {
             7 => {
                 self.ring_bell = true;
+                self.pending_bell = true;
             },
             8 => {
                 // backspace
@@ -2370,6 +2827,54 @@ impl Perform for Grid {
                 }
             },
 
+            // Set current working directory.
+            b"7" => {
+                // the url itself may contain semicolons
+                let url = params[1..].join(&b';');
+                if let Some(cwd) = str::from_utf8(&url)
+                    .ok()
+                    .and_then(|url| Url::parse(url).ok())
+                    .filter(|url| url.scheme() == "file")
+                    .and_then(|mut url| {
+                        // shells report their hostname, which would otherwise fail the
+                        // conversion to a local path
+                        url.set_host(Some("localhost")).ok()?;
+                        url.to_file_path().ok()
+                    })
+                {
+                    self.cwd = Some(cwd.clone());
+                    self.pending_cwd_update = Some(cwd);
+                }
+            },
+
+            // Desktop notification (iTerm2).
+            b"9" => {
+                if params.len() < 2 {
+                    return;
+                }
+                // ConEmu uses OSC 9 with a numeric first parameter for its own extensions (eg.
+                // progress reports), these are not notifications
+                if parse_number(params[1]).is_some() {
+                    return;
+                }
+                let body = String::from_utf8_lossy(&params[1..].join(&b';')).to_string();
+                self.pending_notifications.push((None, body));
+            },
+
+            // Desktop notification (urxvt).
+            b"777" => {
+                if params.len() < 3 || params[1] != b"notify" {
+                    return;
+                }
+                let title = String::from_utf8_lossy(params[2]).to_string();
+                let body = if params.len() > 3 {
+                    String::from_utf8_lossy(&params[3..].join(&b';')).to_string()
+                } else {
+                    String::new()
+                };
+                self.pending_notifications.push((Some(title), body));
+            },
+
             // Set clipboard.
             b"52" => {
                 if params.len() < 3 {
@@ -2437,6 +2942,11 @@ impl Perform for Grid {
                 // TBD - reset text cursor color - currently unimplemented
             },
 
+            // Semantic prompt marks (FinalTerm).
+            b"133" => {
+                self.add_semantic_prompt_mark(params);
+            },
+
             _ => {
                 if self.debug {
                     log::warn!("Unhandled osc: {:?}", params);
@@ -2549,7 +3059,7 @@ impl Perform for Grid {
                                     &mut self.viewport,
                                     &mut self.cursor,
                                     &mut self.sixel_grid,
-                                    &mut self.supports_kitty_keyboard_protocol,
+                                    &mut self.kitty_keyboard_flags_stack,
                                 );
                             }
                             self.alternate_screen_state = None;
@@ -2636,17 +3146,16 @@ impl Perform for Grid {
                         1049 => {
                             // enter alternate buffer
                             let current_lines_above =
-                                std::mem::replace(&mut self.lines_above, VecDeque::new());
+                                std::mem::replace(&mut self.lines_above, Scrollback::new());
                             let current_viewport =
                                 std::mem::replace(&mut self.viewport, vec![Row::new().canonical()]);
                             let current_cursor = std::mem::replace(
                                 &mut self.cursor,
                                 Cursor::new(0, 0, self.styled_underlines),
                             );
-                            let current_supports_kitty_keyboard_protocol = std::mem::replace(
-                                &mut self.supports_kitty_keyboard_protocol,
-                                false,
-                            );
+                            // the alternate screen has its own stack of keyboard enhancements
+                            let current_kitty_keyboard_flags_stack =
+                                std::mem::take(&mut self.kitty_keyboard_flags_stack);
                             let sixel_image_store = self.sixel_grid.sixel_image_store.clone();
                             let alternate_sixelgrid = std::mem::replace(
                                 &mut self.sixel_grid,
@@ -2657,7 +3166,7 @@ impl Perform for Grid {
                                 current_viewport,
                                 current_cursor,
                                 alternate_sixelgrid,
-                                current_supports_kitty_keyboard_protocol,
+                                current_kitty_keyboard_flags_stack,
                             ));
                             self.clear_viewport_before_rendering = true;
                             self.scrollback_buffer_lines =
@@ -2846,25 +3355,25 @@ impl Perform for Grid {
         } else if c == 's' {
             self.save_cursor_position();
         } else if c == 'u' && intermediates == &[b'>'] {
-            // Zellij only supports the first "progressive enhancement" layer of the kitty keyboard
-            // protocol
+            // push the requested kitty keyboard protocol enhancements
             if !self.explicitly_disable_kitty_keyboard_protocol {
-                self.supports_kitty_keyboard_protocol = true;
+                let flags = KittyKeyboardFlags::from_bits_truncate(next_param_or(0) as u8);
+                self.push_kitty_keyboard_flags(flags);
             }
         } else if c == 'u' && intermediates == &[b'<'] {
-            // Zellij only supports the first "progressive enhancement" layer of the kitty keyboard
-            // protocol
+            // pop the given number of kitty keyboard protocol enhancements
             if !self.explicitly_disable_kitty_keyboard_protocol {
-                self.supports_kitty_keyboard_protocol = false;
+                self.pop_kitty_keyboard_flags(next_param_or(1) as usize);
+            }
+        } else if c == 'u' && intermediates == &[b'='] {
+            // replace the current kitty keyboard protocol enhancements
+            if !self.explicitly_disable_kitty_keyboard_protocol {
+                let flags = KittyKeyboardFlags::from_bits_truncate(next_param_or(0) as u8);
+                let mode = next_param_or(1);
+                self.set_kitty_keyboard_flags(flags, mode);
             }
         } else if c == 'u' && intermediates == &[b'?'] {
-            // Zellij only supports the first "progressive enhancement" layer of the kitty keyboard
-            // protocol
-            let reply = if self.supports_kitty_keyboard_protocol {
-                "\u{1b}[?1u"
-            } else {
-                "\u{1b}[?0u"
-            };
+            let reply = format!("\u{1b}[?{}u", self.kitty_keyboard_flags().bits());
             self.pending_messages_to_pty.push(reply.as_bytes().to_vec());
         } else if c == 'u' {
             self.restore_cursor_position();
@@ -3119,53 +3628,79 @@ impl Perform for Grid {
     }
 }
 
-#[derive(Clone)]
 pub struct AlternateScreenState {
-    lines_above: VecDeque<Row>,
+    lines_above: Scrollback,
     viewport: Vec<Row>,
     cursor: Cursor,
     sixel_grid: SixelGrid,
-    supports_kitty_keyboard_protocol: bool,
+    kitty_keyboard_flags_stack: Vec<KittyKeyboardFlags>,
 }
 impl AlternateScreenState {
     pub fn new(
-        lines_above: VecDeque<Row>,
+        lines_above: Scrollback,
         viewport: Vec<Row>,
         cursor: Cursor,
         sixel_grid: SixelGrid,
-        supports_kitty_keyboard_protocol: bool,
+        kitty_keyboard_flags_stack: Vec<KittyKeyboardFlags>,
     ) -> Self {
         AlternateScreenState {
             lines_above,
             viewport,
             cursor,
             sixel_grid,
-            supports_kitty_keyboard_protocol,
+            kitty_keyboard_flags_stack,
         }
     }
     pub fn apply_contents_to(
         &mut self,
-        lines_above: &mut VecDeque<Row>,
+        lines_above: &mut Scrollback,
         viewport: &mut Vec<Row>,
         cursor: &mut Cursor,
         sixel_grid: &mut SixelGrid,
-        supports_kitty_keyboard_protocol: &mut bool,
+        kitty_keyboard_flags_stack: &mut Vec<KittyKeyboardFlags>,
     ) {
         std::mem::swap(&mut self.lines_above, lines_above);
         std::mem::swap(&mut self.viewport, viewport);
         std::mem::swap(&mut self.cursor, cursor);
         std::mem::swap(&mut self.sixel_grid, sixel_grid);
         std::mem::swap(
-            &mut self.supports_kitty_keyboard_protocol,
-            supports_kitty_keyboard_protocol,
+            &mut self.kitty_keyboard_flags_stack,
+            kitty_keyboard_flags_stack,
         );
     }
 }
 
+/// OSC 133 (FinalTerm) semantic prompt marks, recorded on the canonical row in which the shell
+/// emitted them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SemanticPromptMarks {
+    pub prompt_start: bool,  // A
+    pub command_start: bool, // B
+    pub output_start: bool,  // C
+    pub command_end: bool,   // D
+    pub exit_code: Option<i32>,
+}
+
+impl SemanticPromptMarks {
+    pub fn is_empty(&self) -> bool {
+        !self.prompt_start && !self.command_start && !self.output_start && !self.command_end
+    }
+    pub fn merge(&mut self, other: SemanticPromptMarks) {
+        self.prompt_start |= other.prompt_start;
+        self.command_start |= other.command_start;
+        self.output_start |= other.output_start;
+        if other.command_end {
+            self.command_end = true;
+            self.exit_code = other.exit_code;
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub semantic_prompt_marks: SemanticPromptMarks,
     width: Option<usize>,
 }
 
@@ -3183,6 +3718,7 @@ impl Row {
         Row {
             columns: VecDeque::new(),
             is_canonical: false,
+            semantic_prompt_marks: Default::default(),
             width: None,
         }
     }
@@ -3190,6 +3726,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            semantic_prompt_marks: Default::default(),
             width: None,
         }
     }
@@ -3200,6 +3737,9 @@ impl Row {
             let mut first_row = rows.remove(0);
             for row in &mut rows {
                 first_row.append(&mut row.columns);
+                first_row
+                    .semantic_prompt_marks
+                    .merge(row.semantic_prompt_marks);
             }
             first_row
         }
@@ -3213,6 +3753,13 @@ impl Row {
         self.is_canonical = true;
         self
     }
+    pub fn with_semantic_prompt_marks(
+        mut self,
+        semantic_prompt_marks: SemanticPromptMarks,
+    ) -> Self {
+        self.semantic_prompt_marks = semantic_prompt_marks;
+        self
+    }
     pub fn width_cached(&mut self) -> usize {
         if self.width.is_some() {
             self.width.unwrap()
@@ -3490,6 +4037,9 @@ impl Row {
                 part.is_canonical = true;
             }
         }
+        if let Some(part) = parts.get_mut(0) {
+            part.semantic_prompt_marks = self.semantic_prompt_marks;
+        }
         if parts.is_empty() {
             parts.push(self.clone());
         }
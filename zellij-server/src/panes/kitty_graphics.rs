@@ -0,0 +1,701 @@
+// Support for the kitty graphics protocol (https://sw.kovidgoyal.net/kitty/graphics-protocol/)
+//
+// Images are transmitted to us inside APC sequences, decoded to RGBA pixels and stored here. Once
+// placed, they are re-encoded as sixel images and handed over to the `SixelGrid`, which takes
+// care of scrolling them, cutting them when text is written over them and rendering them to the
+// outer terminal.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use sixel_image::SixelImage;
+use zellij_utils::pane_size::SizeInPixels;
+
+use crate::panes::sixel::SixelImageStore;
+
+const MAX_APC_LENGTH: usize = 64 * 1024 * 1024;
+const MAX_IMAGE_PIXELS: usize = 4096 * 4096;
+const DEFAULT_STORAGE_QUOTA: usize = 320 * 1024 * 1024; // bytes, same as kitty
+const PNG_FORMAT: u32 = 100;
+const RGB_FORMAT: u32 = 24;
+const RGBA_FORMAT: u32 = 32;
+const FIRST_AUTOMATIC_IMAGE_ID: u32 = 1 << 31; // so as not to collide with ids chosen by the application
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApcParserState {
+    Ground,
+    Escape,
+    Apc,
+    ApcEscape,
+}
+
+// our vte parser ignores APC sequences, so we look for them in the byte stream ourselves
+#[derive(Debug, Clone)]
+pub struct ApcParser {
+    state: ApcParserState,
+    buffer: Vec<u8>,
+}
+
+impl Default for ApcParser {
+    fn default() -> Self {
+        ApcParser {
+            state: ApcParserState::Ground,
+            buffer: vec![],
+        }
+    }
+}
+
+impl ApcParser {
+    // returns the contents of an APC sequence once it has been terminated
+    pub fn advance(&mut self, byte: u8) -> Option<Vec<u8>> {
+        match (self.state, byte) {
+            (ApcParserState::Apc, 0x1b) => {
+                self.state = ApcParserState::ApcEscape;
+            },
+            (ApcParserState::Apc, 0x18 | 0x1a) => {
+                // CAN/SUB abort the sequence
+                self.state = ApcParserState::Ground;
+                self.buffer.clear();
+            },
+            (ApcParserState::Apc, _) => {
+                if self.buffer.len() < MAX_APC_LENGTH {
+                    self.buffer.push(byte);
+                } else {
+                    self.state = ApcParserState::Ground;
+                    self.buffer.clear();
+                }
+            },
+            (ApcParserState::ApcEscape, b'\\') => {
+                self.state = ApcParserState::Ground;
+                return Some(std::mem::take(&mut self.buffer));
+            },
+            (_, 0x1b) => {
+                self.state = ApcParserState::Escape;
+            },
+            (ApcParserState::Escape | ApcParserState::ApcEscape, b'_') => {
+                self.state = ApcParserState::Apc;
+                self.buffer.clear();
+            },
+            _ => {
+                self.state = ApcParserState::Ground;
+            },
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KittyGraphicsCommand {
+    pub action: char,
+    pub quiet: u32,
+    pub format: u32,
+    pub transmission_medium: char,
+    pub compressed: bool,
+    pub more_chunks: bool,
+    pub image_id: Option<u32>,
+    pub image_number: Option<u32>,
+    pub placement_id: Option<u32>,
+    pub pixel_width: Option<usize>,
+    pub pixel_height: Option<usize>,
+    pub source_x: usize,
+    pub source_y: usize,
+    pub source_width: Option<usize>,
+    pub source_height: Option<usize>,
+    pub columns: Option<usize>,
+    pub rows: Option<usize>,
+    pub x_offset: usize,
+    pub y_offset: usize,
+    pub move_cursor: bool,
+    pub delete: char,
+    pub payload: Vec<u8>, // base64 encoded
+}
+
+impl KittyGraphicsCommand {
+    pub fn parse(apc: &[u8]) -> Option<Self> {
+        let apc = apc.strip_prefix(b"G")?;
+        let (control_data, payload) = match apc.iter().position(|b| *b == b';') {
+            Some(separator_index) => (&apc[..separator_index], &apc[separator_index + 1..]),
+            None => (apc, &[][..]),
+        };
+        let mut command = KittyGraphicsCommand {
+            action: 't',
+            format: RGBA_FORMAT,
+            transmission_medium: 'd',
+            move_cursor: true,
+            delete: 'a',
+            payload: payload.to_vec(),
+            ..Default::default()
+        };
+        for key_value in std::str::from_utf8(control_data).ok()?.split(',') {
+            let (key, value) = match key_value.split_once('=') {
+                Some(key_value) => key_value,
+                None => continue,
+            };
+            let character = value.chars().next();
+            let number = value.parse::<u32>().ok();
+            let size = value.parse::<usize>().ok();
+            match key {
+                "a" => command.action = character.unwrap_or(command.action),
+                "q" => command.quiet = number.unwrap_or(0),
+                "f" => command.format = number.unwrap_or(command.format),
+                "t" => command.transmission_medium = character.unwrap_or('d'),
+                "o" => command.compressed = character == Some('z'),
+                "m" => command.more_chunks = number == Some(1),
+                "i" => command.image_id = number.filter(|id| *id > 0),
+                "I" => command.image_number = number.filter(|number| *number > 0),
+                "p" => command.placement_id = number.filter(|id| *id > 0),
+                "s" => command.pixel_width = size,
+                "v" => command.pixel_height = size,
+                "x" => command.source_x = size.unwrap_or(0),
+                "y" => command.source_y = size.unwrap_or(0),
+                "w" => command.source_width = size.filter(|width| *width > 0),
+                "h" => command.source_height = size.filter(|height| *height > 0),
+                "c" => command.columns = size.filter(|columns| *columns > 0),
+                "r" => command.rows = size.filter(|rows| *rows > 0),
+                "X" => command.x_offset = size.unwrap_or(0),
+                "Y" => command.y_offset = size.unwrap_or(0),
+                "C" => command.move_cursor = number != Some(1),
+                "d" => command.delete = character.unwrap_or('a'),
+                _ => {}, // z-index, animation and unicode placeholders are not supported
+            }
+        }
+        Some(command)
+    }
+    pub fn response(&self, result: &Result<(), String>) -> Option<String> {
+        // the protocol only expects a response if the application gave the image an id or a number
+        if self.image_id.is_none() && self.image_number.is_none() {
+            return None;
+        }
+        let message = match result {
+            Ok(()) if self.quiet < 1 => "OK",
+            Err(error) if self.quiet < 2 => error.as_str(),
+            _ => return None,
+        };
+        let mut keys = vec![];
+        if let Some(image_id) = self.image_id {
+            keys.push(format!("i={}", image_id));
+        }
+        if let Some(image_number) = self.image_number {
+            keys.push(format!("I={}", image_number));
+        }
+        if let Some(placement_id) = self.placement_id {
+            keys.push(format!("p={}", placement_id));
+        }
+        Some(format!("\u{1b}_G{};{}\u{1b}\\", keys.join(","), message))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KittyImage {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<u8>, // RGBA
+}
+
+impl KittyImage {
+    pub fn load(command: &KittyGraphicsCommand) -> Result<Self, String> {
+        let payload = base64::decode(&command.payload)
+            .map_err(|_| "EINVAL:payload is not valid base64".to_owned())?;
+        let data = match command.transmission_medium {
+            'd' => payload,
+            't' => String::from_utf8(payload)
+                .ok()
+                .and_then(|path| read_temporary_file(Path::new(&path)))
+                .ok_or_else(|| "EBADF:failed to read image file".to_owned())?,
+            // regular files (f) are not supported, since they would allow anything running
+            // inside a pane to read any file the server can read
+            _ => return Err("EINVAL:unsupported transmission medium".to_owned()),
+        };
+        let data = if command.compressed {
+            miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&data, MAX_IMAGE_PIXELS * 4)
+                .map_err(|_| "EINVAL:failed to decompress image data".to_owned())?
+        } else {
+            data
+        };
+        match command.format {
+            RGB_FORMAT | RGBA_FORMAT => {
+                let bytes_per_pixel = if command.format == RGB_FORMAT { 3 } else { 4 };
+                let (width, height) = match (command.pixel_width, command.pixel_height) {
+                    (Some(width), Some(height)) => (width, height),
+                    _ => return Err("EINVAL:image dimensions are missing".to_owned()),
+                };
+                KittyImage::from_raw_pixels(width, height, bytes_per_pixel, &data)
+            },
+            PNG_FORMAT => decode_png(&data),
+            _ => Err("EINVAL:unsupported image format".to_owned()),
+        }
+    }
+    fn from_raw_pixels(
+        width: usize,
+        height: usize,
+        bytes_per_pixel: usize,
+        data: &[u8],
+    ) -> Result<Self, String> {
+        let pixel_count = pixel_count(width, height)
+            .ok_or_else(|| "EINVAL:invalid image dimensions".to_owned())?;
+        if data.len() < pixel_count * bytes_per_pixel {
+            return Err("ENODATA:insufficient image data".to_owned());
+        }
+        let pixels = data
+            .chunks_exact(bytes_per_pixel)
+            .take(pixel_count)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], *pixel.get(3).unwrap_or(&255)])
+            .collect();
+        Ok(KittyImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+    fn pixel(&self, x: usize, y: usize) -> &[u8] {
+        let index = (y * self.width + x) * 4;
+        &self.pixels[index..index + 4]
+    }
+    pub fn placed(
+        &self,
+        command: &KittyGraphicsCommand,
+        character_cell_size: SizeInPixels,
+    ) -> Result<KittyImage, String> {
+        // crops and scales the image as requested by the placement command
+        let source_x = std::cmp::min(command.source_x, self.width);
+        let source_y = std::cmp::min(command.source_y, self.height);
+        let source_width = command
+            .source_width
+            .unwrap_or(self.width)
+            .min(self.width - source_x);
+        let source_height = command
+            .source_height
+            .unwrap_or(self.height)
+            .min(self.height - source_y);
+        if source_width == 0 || source_height == 0 {
+            return Err("EINVAL:source rectangle is outside of the image".to_owned());
+        }
+        // as per the protocol, the image is offset within the cell the cursor is on
+        if command.x_offset >= character_cell_size.width
+            || command.y_offset >= character_cell_size.height
+        {
+            return Err("EINVAL:the offset is larger than a character cell".to_owned());
+        }
+        let invalid_placement = || "EINVAL:invalid placement dimensions".to_owned();
+        let (width, height) = match (command.columns, command.rows) {
+            (Some(columns), Some(rows)) => (
+                columns.checked_mul(character_cell_size.width),
+                rows.checked_mul(character_cell_size.height),
+            ),
+            (Some(columns), None) => {
+                let width = columns.checked_mul(character_cell_size.width);
+                let height = width.and_then(|width| source_height.checked_mul(width));
+                (width, height.map(|height| height / source_width))
+            },
+            (None, Some(rows)) => {
+                let height = rows.checked_mul(character_cell_size.height);
+                let width = height.and_then(|height| source_width.checked_mul(height));
+                (width.map(|width| width / source_height), height)
+            },
+            (None, None) => (Some(source_width), Some(source_height)),
+        };
+        let (width, height) = width.zip(height).ok_or_else(invalid_placement)?;
+        let pixel_count = pixel_count(width, height).ok_or_else(invalid_placement)?;
+        let mut pixels = Vec::with_capacity(pixel_count * 4);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.extend_from_slice(self.pixel(
+                    source_x + x * source_width / width,
+                    source_y + y * source_height / height,
+                ));
+            }
+        }
+        Ok(KittyImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+    pub fn to_sixel_image(&self) -> Option<SixelImage> {
+        // colors are quantized to a 6x6x6 color cube, transparent pixels are left out
+        let color_level = |channel: u8| (channel as usize * 5 + 127) / 255;
+        let color_registers: Vec<Option<usize>> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let pixel = self.pixel(x, y);
+                if pixel[3] < 128 {
+                    None
+                } else {
+                    Some(
+                        color_level(pixel[0]) * 36
+                            + color_level(pixel[1]) * 6
+                            + color_level(pixel[2]),
+                    )
+                }
+            })
+            .collect();
+        let used_color_registers: BTreeSet<usize> =
+            color_registers.iter().flatten().copied().collect();
+        let mut serialized = String::from("\u{1b}P0;1q");
+        for color_register in &used_color_registers {
+            let _ = write!(
+                serialized,
+                "#{};2;{};{};{}",
+                color_register,
+                color_register / 36 * 20,
+                color_register / 6 % 6 * 20,
+                color_register % 6 * 20
+            );
+        }
+        for band_top in (0..self.height).step_by(6) {
+            let band_height = std::cmp::min(6, self.height - band_top);
+            let band =
+                &color_registers[band_top * self.width..(band_top + band_height) * self.width];
+            let colors_in_band: BTreeSet<usize> = band.iter().flatten().copied().collect();
+            if colors_in_band.is_empty() {
+                // we still need to fill the band so that all pixel lines have the same width
+                let _ = write!(serialized, "#0!{}?", self.width);
+            }
+            for color_register in colors_in_band {
+                let _ = write!(serialized, "#{}", color_register);
+                let sixels = (0..self.width).map(|x| {
+                    let bits = (0..band_height)
+                        .filter(|y| band[y * self.width + x] == Some(color_register))
+                        .fold(0u8, |bits, y| bits | 1 << y);
+                    (63 + bits) as char
+                });
+                write_run_length_encoded(&mut serialized, sixels);
+                serialized.push('$');
+            }
+            serialized.push('-');
+        }
+        serialized.push_str("\u{1b}\\");
+        SixelImage::new(serialized.as_bytes()).ok()
+    }
+}
+
+// the number of pixels of an image with these dimensions, if it is not empty and not too large -
+// the dimensions come from the application, so they might overflow when multiplied
+fn pixel_count(width: usize, height: usize) -> Option<usize> {
+    width
+        .checked_mul(height)
+        .filter(|pixel_count| *pixel_count > 0 && *pixel_count <= MAX_IMAGE_PIXELS)
+}
+
+fn read_temporary_file(path: &Path) -> Option<Vec<u8>> {
+    // only files inside the temporary directory are read, the errors are deliberately not
+    // reported so as not to reveal anything about the file system to the application
+    let temp_dir = fs::canonicalize(std::env::temp_dir()).ok()?;
+    let path = fs::canonicalize(path).ok()?;
+    let metadata = fs::metadata(&path).ok()?;
+    if !path.starts_with(temp_dir) || !metadata.is_file() || metadata.len() > MAX_APC_LENGTH as u64
+    {
+        return None;
+    }
+    let data = fs::read(&path).ok()?;
+    // as per the protocol, temporary files are only deleted if they're clearly meant for us
+    if path
+        .file_name()
+        .map(|file_name| {
+            file_name
+                .to_string_lossy()
+                .contains("tty-graphics-protocol")
+        })
+        .unwrap_or(false)
+    {
+        let _ = fs::remove_file(&path);
+    }
+    Some(data)
+}
+
+fn write_run_length_encoded(serialized: &mut String, characters: impl Iterator<Item = char>) {
+    let mut write_run = |character: char, count: usize| {
+        if count > 3 {
+            let _ = write!(serialized, "!{}{}", count, character);
+        } else {
+            for _ in 0..count {
+                serialized.push(character);
+            }
+        }
+    };
+    let mut current_run: Option<(char, usize)> = None;
+    for character in characters {
+        current_run = match current_run {
+            Some((run_character, count)) if run_character == character => {
+                Some((run_character, count + 1))
+            },
+            Some((run_character, count)) => {
+                write_run(run_character, count);
+                Some((character, 1))
+            },
+            None => Some((character, 1)),
+        };
+    }
+    if let Some((run_character, count)) = current_run {
+        write_run(run_character, count);
+    }
+}
+
+fn decode_png(data: &[u8]) -> Result<KittyImage, String> {
+    let invalid_png = |_| "EBADPNG:failed to decode PNG image".to_owned();
+    let limits = png::Limits {
+        bytes: MAX_IMAGE_PIXELS * 4,
+    };
+    let mut decoder = png::Decoder::new_with_limits(data, limits);
+    // palettes, transparency chunks and bit depths other than 8 are converted to 8 bit samples
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(invalid_png)?;
+    let (width, height) = (reader.info().width as usize, reader.info().height as usize);
+    pixel_count(width, height).ok_or_else(|| "EINVAL:invalid image dimensions".to_owned())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(invalid_png)?;
+    let bytes_per_pixel = match frame.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => return Err("EBADPNG:failed to decode PNG image".to_owned()),
+    };
+    let pixels = buffer[..frame.buffer_size()]
+        .chunks_exact(frame.line_size)
+        .flat_map(|line| line[..width * bytes_per_pixel].chunks_exact(bytes_per_pixel))
+        .flat_map(|pixel| match pixel {
+            [gray] => [*gray, *gray, *gray, 255],
+            [gray, alpha] => [*gray, *gray, *gray, *alpha],
+            [red, green, blue] => [*red, *green, *blue, 255],
+            _ => [pixel[0], pixel[1], pixel[2], pixel[3]],
+        })
+        .collect();
+    Ok(KittyImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct KittyPlacement {
+    pub image_id: u32,
+    pub placement_id: Option<u32>,
+    pub sixel_image_id: usize,
+}
+
+// the data of the images transmitted in all the panes of the session, which share a storage quota
+// (like all the windows of kitty do) so that opening more panes does not allow using more memory
+#[derive(Debug, Clone)]
+pub struct KittyImageStore {
+    images: HashMap<(usize, u32), KittyImage>, // (owner id, image id) => image
+    image_order: VecDeque<(usize, u32)>,       // least recently transmitted first
+    stored_bytes: usize,
+    storage_quota: usize, // bytes
+    next_owner_id: usize,
+}
+
+impl Default for KittyImageStore {
+    fn default() -> Self {
+        KittyImageStore::new(DEFAULT_STORAGE_QUOTA)
+    }
+}
+
+impl KittyImageStore {
+    pub fn new(storage_quota: usize) -> Self {
+        KittyImageStore {
+            images: HashMap::new(),
+            image_order: VecDeque::new(),
+            stored_bytes: 0,
+            storage_quota,
+            next_owner_id: 0,
+        }
+    }
+    fn new_owner_id(&mut self) -> usize {
+        let owner_id = self.next_owner_id;
+        self.next_owner_id += 1;
+        owner_id
+    }
+    fn add_image(&mut self, owner_id: usize, image_id: u32, image: KittyImage) {
+        self.remove_image(owner_id, image_id);
+        self.stored_bytes += image.pixels.len();
+        self.images.insert((owner_id, image_id), image);
+        self.image_order.push_back((owner_id, image_id));
+        // like kitty, we free the oldest images once their data takes up too much memory - their
+        // placements are kept, since they were already handed over to the sixel grid
+        while self.stored_bytes > self.storage_quota && self.image_order.len() > 1 {
+            if let Some((owner_id, image_id)) = self.image_order.front().copied() {
+                self.remove_image(owner_id, image_id);
+            }
+        }
+    }
+    fn remove_image(&mut self, owner_id: usize, image_id: u32) -> Option<KittyImage> {
+        let image = self.images.remove(&(owner_id, image_id))?;
+        self.image_order.retain(|key| *key != (owner_id, image_id));
+        self.stored_bytes -= image.pixels.len();
+        Some(image)
+    }
+    fn remove_images_of(&mut self, owner_id: usize) {
+        for image_id in self.image_ids_of(owner_id) {
+            self.remove_image(owner_id, image_id);
+        }
+    }
+    fn image(&self, owner_id: usize, image_id: u32) -> Option<&KittyImage> {
+        self.images.get(&(owner_id, image_id))
+    }
+    fn image_ids_of(&self, owner_id: usize) -> Vec<u32> {
+        self.images
+            .keys()
+            .filter(|(owner, _)| *owner == owner_id)
+            .map(|(_, image_id)| *image_id)
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct KittyGraphics {
+    owner_id: usize, // of the images of this grid in the image store of the session
+    image_store: Rc<RefCell<SixelImageStore>>,
+    image_numbers: HashMap<u32, u32>, // image number => image id
+    placements: Vec<KittyPlacement>,
+    chunked_command: Option<KittyGraphicsCommand>,
+    next_image_id: u32,
+}
+
+impl Drop for KittyGraphics {
+    fn drop(&mut self) {
+        if let Ok(mut image_store) = self.image_store.try_borrow_mut() {
+            image_store.kitty_images.remove_images_of(self.owner_id);
+        }
+    }
+}
+
+impl KittyGraphics {
+    pub fn new(image_store: Rc<RefCell<SixelImageStore>>) -> Self {
+        let owner_id = image_store.borrow_mut().kitty_images.new_owner_id();
+        KittyGraphics {
+            owner_id,
+            image_store,
+            image_numbers: HashMap::new(),
+            placements: vec![],
+            chunked_command: None,
+            next_image_id: 0,
+        }
+    }
+    // returns the complete command once its last chunk has arrived
+    pub fn add_chunk(&mut self, command: KittyGraphicsCommand) -> Option<KittyGraphicsCommand> {
+        match self.chunked_command.as_mut() {
+            Some(chunked_command) => {
+                if chunked_command.payload.len() + command.payload.len() <= MAX_APC_LENGTH {
+                    chunked_command.payload.extend(command.payload);
+                }
+                if command.more_chunks {
+                    None
+                } else {
+                    self.chunked_command.take()
+                }
+            },
+            None if command.more_chunks => {
+                self.chunked_command = Some(command);
+                None
+            },
+            None => Some(command),
+        }
+    }
+    pub fn add_image(&mut self, command: &mut KittyGraphicsCommand, image: KittyImage) -> u32 {
+        // images transmitted without an id are given one, which is also reported back to the
+        // application if it identified the image by number
+        let image_id = match command.image_id {
+            Some(image_id) => image_id,
+            None => {
+                let image_id = self.next_automatic_image_id();
+                if let Some(image_number) = command.image_number {
+                    self.image_numbers.insert(image_number, image_id);
+                    command.image_id = Some(image_id);
+                }
+                image_id
+            },
+        };
+        self.image_store
+            .borrow_mut()
+            .kitty_images
+            .add_image(self.owner_id, image_id, image);
+        image_id
+    }
+    fn remove_image(&mut self, image_id: u32) {
+        self.image_numbers.retain(|_, id| *id != image_id);
+        self.image_store
+            .borrow_mut()
+            .kitty_images
+            .remove_image(self.owner_id, image_id);
+    }
+    fn next_automatic_image_id(&mut self) -> u32 {
+        let image_store = self.image_store.borrow();
+        loop {
+            let image_id = FIRST_AUTOMATIC_IMAGE_ID.wrapping_add(self.next_image_id);
+            self.next_image_id = self.next_image_id.wrapping_add(1);
+            if image_store
+                .kitty_images
+                .image(self.owner_id, image_id)
+                .is_none()
+            {
+                return image_id;
+            }
+        }
+    }
+    pub fn image_id(&self, command: &KittyGraphicsCommand) -> Option<u32> {
+        command.image_id.or_else(|| {
+            command
+                .image_number
+                .and_then(|image_number| self.image_numbers.get(&image_number).copied())
+        })
+    }
+    // the image cropped and scaled as requested by the placement command
+    pub fn placed_image(
+        &self,
+        image_id: u32,
+        command: &KittyGraphicsCommand,
+        character_cell_size: SizeInPixels,
+    ) -> Result<KittyImage, String> {
+        self.image_store
+            .borrow()
+            .kitty_images
+            .image(self.owner_id, image_id)
+            .ok_or_else(|| "ENOENT:image not found".to_owned())?
+            .placed(command, character_cell_size)
+    }
+    // returns the placement this one replaced, if any
+    pub fn add_placement(&mut self, placement: KittyPlacement) -> Option<KittyPlacement> {
+        let replaced_placement = match placement.placement_id {
+            Some(placement_id) => self
+                .placements
+                .iter()
+                .position(|p| {
+                    p.image_id == placement.image_id && p.placement_id == Some(placement_id)
+                })
+                .map(|index| self.placements.remove(index)),
+            None => None,
+        };
+        self.placements.push(placement);
+        replaced_placement
+    }
+    pub fn retain_placements(&mut self, predicate: impl Fn(&KittyPlacement) -> bool) {
+        self.placements.retain(predicate);
+    }
+    pub fn remove_placements(
+        &mut self,
+        predicate: impl Fn(&KittyPlacement) -> bool,
+    ) -> Vec<KittyPlacement> {
+        let (removed, retained) = self.placements.drain(..).partition(predicate);
+        self.placements = retained;
+        removed
+    }
+    pub fn remove_images_without_placements(&mut self, image_ids: impl Iterator<Item = u32>) {
+        for image_id in image_ids {
+            if !self.placements.iter().any(|p| p.image_id == image_id) {
+                self.remove_image(image_id);
+            }
+        }
+    }
+    pub fn image_ids(&self) -> Vec<u32> {
+        self.image_store
+            .borrow()
+            .kitty_images
+            .image_ids_of(self.owner_id)
+    }
+}
@@ -1,12 +1,15 @@
 pub mod alacritty_functions;
 pub mod grid;
+pub mod kitty_graphics;
 pub mod link_handler;
+pub mod scrollback;
 pub mod selection;
 pub mod sixel;
 pub mod terminal_character;
 
 mod active_panes;
 mod floating_panes;
+mod pane_monitor;
 mod plugin_pane;
 mod search;
 mod terminal_pane;
@@ -16,8 +19,11 @@ pub use active_panes::*;
 pub use alacritty_functions::*;
 pub use floating_panes::*;
 pub use grid::*;
+pub use kitty_graphics::*;
 pub use link_handler::*;
+pub use pane_monitor::*;
 pub(crate) use plugin_pane::*;
+pub use scrollback::*;
 pub use sixel::*;
 pub(crate) use terminal_character::*;
 pub use terminal_pane::*;
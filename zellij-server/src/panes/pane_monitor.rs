@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+/// Keeps track of the bell, activity and silence alerts of a pane, these are cleared once the
+/// pane is focused
+#[derive(Debug, Clone)]
+pub struct PaneMonitor {
+    pub monitor_activity: bool,
+    pub monitor_silence: Option<Duration>,
+    pub has_bell: bool,
+    pub has_activity: bool,
+    pub has_silence: bool,
+    last_output_at: Instant,
+}
+
+impl Default for PaneMonitor {
+    fn default() -> Self {
+        PaneMonitor {
+            monitor_activity: false,
+            monitor_silence: None,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
+            last_output_at: Instant::now(),
+        }
+    }
+}
+
+impl PaneMonitor {
+    // all the methods below return true if the alerts of the pane changed
+    pub fn record_output(&mut self, rang_bell: bool, is_focused: bool) -> bool {
+        self.last_output_at = Instant::now();
+        let had_alerts = (self.has_bell, self.has_activity, self.has_silence);
+        self.has_silence = false;
+        if !is_focused {
+            self.has_bell = self.has_bell || rang_bell;
+            self.has_activity = self.has_activity || self.monitor_activity;
+        }
+        had_alerts != (self.has_bell, self.has_activity, self.has_silence)
+    }
+    pub fn check_silence(&mut self, is_focused: bool) -> bool {
+        match self.monitor_silence {
+            Some(monitor_silence)
+                if !is_focused
+                    && !self.has_silence
+                    && self.last_output_at.elapsed() >= monitor_silence =>
+            {
+                self.has_silence = true;
+                true
+            },
+            _ => false,
+        }
+    }
+    pub fn clear(&mut self) -> bool {
+        let had_alerts = self.has_alerts();
+        if self.has_silence {
+            // start counting again, so that we alert once more if the pane stays silent
+            self.last_output_at = Instant::now();
+        }
+        self.has_bell = false;
+        self.has_activity = false;
+        self.has_silence = false;
+        had_alerts
+    }
+    pub fn has_alerts(&self) -> bool {
+        self.has_bell || self.has_activity || self.has_silence
+    }
+}
@@ -21,6 +21,7 @@ use std::rc::Rc;
 use zellij_utils::data::{
     BareKey, KeyWithModifier, PermissionStatus, PermissionType, PluginPermission,
 };
+use zellij_utils::input::kitty_keyboard::KittyKeyEvent;
 use zellij_utils::pane_size::{Offset, SizeInPixels};
 use zellij_utils::position::Position;
 use zellij_utils::{
@@ -92,6 +93,7 @@ pub(crate) struct PluginPane {
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
     exclude_from_sync: bool,
+    pinned: bool,
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
     loading_indication: LoadingIndication,
@@ -139,6 +141,7 @@ impl PluginPane {
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             exclude_from_sync: false,
+            pinned: false,
             link_handler,
             character_cell_size,
             sixel_image_store,
@@ -245,9 +248,16 @@ impl Pane for PluginPane {
         &mut self,
         key_with_modifier: &Option<KeyWithModifier>,
         raw_input_bytes: Vec<u8>,
-        _raw_input_bytes_are_kitty: bool,
+        raw_input_bytes_are_kitty: bool,
     ) -> Option<AdjustedInput> {
-        if let Some(requesting_permissions) = &self.requesting_permissions {
+        if raw_input_bytes_are_kitty
+            && KittyKeyEvent::from_bytes(&raw_input_bytes)
+                .map(|key_event| key_event.is_release() || key_with_modifier.is_none())
+                .unwrap_or(false)
+        {
+            // plugins only get key presses of keys they can represent
+            None
+        } else if let Some(requesting_permissions) = &self.requesting_permissions {
             let permissions = requesting_permissions.permissions.clone();
             if let Some(key_with_modifier) = key_with_modifier {
                 match key_with_modifier.bare_key {
@@ -398,6 +408,9 @@ impl Pane for PluginPane {
                 pane_title,
                 frame_params,
             );
+            if self.pinned {
+                frame.indicate_pinned();
+            }
             if let Some((frame_color_override, _text)) = self.pane_frame_color_override.as_ref() {
                 frame.override_color(*frame_color_override);
             }
@@ -607,6 +620,13 @@ impl Pane for PluginPane {
     fn exclude_from_sync(&self) -> bool {
         self.exclude_from_sync
     }
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+        self.set_should_render(true);
+    }
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
     fn handle_right_click(&mut self, to: &Position, client_id: ClientId) {
         self.send_plugin_instructions
             .send(PluginInstruction::Update(vec![(
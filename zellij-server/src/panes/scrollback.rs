@@ -0,0 +1,484 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::PathBuf;
+
+use unicode_width::UnicodeWidthChar;
+use zellij_utils::{
+    consts::{scrollback_folder_for_session, SCROLLBACK_TO_DISK},
+    envs, rmp_serde, serde,
+    uuid::Uuid,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::panes::grid::{Row, SemanticPromptMarks};
+use crate::panes::terminal_character::{CharacterStyles, RcCharacterStyles, TerminalCharacter};
+
+// the most recent rows are kept as they are, they are the ones most likely to be pulled back
+// into the viewport (eg. when the pane is resized or when wrapped lines are transferred)
+pub const UNCOMPRESSED_SCROLLBACK_ROWS: usize = 100;
+const LENGTH_PREFIX_SIZE: usize = 4; // of each row spilled to disk
+
+/// The lines above the viewport of a grid, all but the most recent of which are kept
+/// compressed and decompressed lazily whenever they are needed again (eg. when scrolled into
+/// the viewport, searched or selected)
+///
+/// With `scrollback_to_disk`, the oldest rows are spilled to a file rather than being discarded
+/// once the scroll buffer is full, and are read back from it in the same way
+#[derive(Default)]
+pub struct Scrollback {
+    spilled_rows: Option<SpilledRows>, // None if this scrollback does not spill to disk
+    rows: VecDeque<ScrollbackRow>,
+}
+
+enum ScrollbackRow {
+    Uncompressed(Row),
+    Compressed(CompressedRow),
+}
+
+impl ScrollbackRow {
+    fn as_row(&self) -> Cow<'_, Row> {
+        match self {
+            ScrollbackRow::Uncompressed(row) => Cow::Borrowed(row),
+            ScrollbackRow::Compressed(compressed_row) => Cow::Owned(compressed_row.decompress()),
+        }
+    }
+    fn into_row(self) -> Row {
+        match self {
+            ScrollbackRow::Uncompressed(row) => row,
+            ScrollbackRow::Compressed(compressed_row) => compressed_row.decompress(),
+        }
+    }
+    fn width(&self) -> usize {
+        match self {
+            ScrollbackRow::Uncompressed(row) => row.width(),
+            ScrollbackRow::Compressed(compressed_row) => compressed_row.width,
+        }
+    }
+    fn semantic_prompt_marks(&self) -> SemanticPromptMarks {
+        match self {
+            ScrollbackRow::Uncompressed(row) => row.semantic_prompt_marks,
+            ScrollbackRow::Compressed(compressed_row) => compressed_row.semantic_prompt_marks,
+        }
+    }
+}
+
+impl Scrollback {
+    pub fn new() -> Self {
+        let scrollback_to_disk = SCROLLBACK_TO_DISK.get().copied().unwrap_or(false);
+        match envs::get_session_name() {
+            Ok(session_name) if scrollback_to_disk => {
+                Scrollback::spilling_to(scrollback_folder_for_session(&session_name))
+            },
+            _ => Scrollback::default(),
+        }
+    }
+    pub fn spilling_to(folder: PathBuf) -> Self {
+        Scrollback {
+            spilled_rows: Some(SpilledRows::new(folder.join(Uuid::new_v4().to_string()))),
+            rows: VecDeque::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.spilled_row_count() + self.rows.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        if let Some(spilled_rows) = self.spilled_rows.as_mut() {
+            spilled_rows.clear();
+        }
+    }
+    pub fn push_back(&mut self, row: Row) {
+        self.rows.push_back(ScrollbackRow::Uncompressed(row));
+        if let Some(index) = self
+            .rows
+            .len()
+            .checked_sub(UNCOMPRESSED_SCROLLBACK_ROWS + 1)
+        {
+            if let ScrollbackRow::Uncompressed(row) = &self.rows[index] {
+                self.rows[index] = ScrollbackRow::Compressed(CompressedRow::new(row));
+            }
+        }
+    }
+    pub fn pop_back(&mut self) -> Option<Row> {
+        match self.rows.pop_back() {
+            Some(row) => Some(row.into_row()),
+            None => self
+                .spilled_rows
+                .as_mut()
+                .and_then(|spilled_rows| spilled_rows.pop_back()),
+        }
+    }
+    /// Removes the oldest row kept in memory, returning its width
+    pub fn drop_front(&mut self) -> Option<usize> {
+        self.rows.pop_front().map(|row| row.width())
+    }
+    /// Moves the oldest row kept in memory to disk, returns false if this scrollback does not
+    /// spill to disk or if the row could not be written
+    pub fn spill_front(&mut self) -> bool {
+        let spilled_rows = match self.spilled_rows.as_mut() {
+            Some(spilled_rows) => spilled_rows,
+            None => return false,
+        };
+        let compressed_row = match self.rows.pop_front() {
+            Some(ScrollbackRow::Compressed(compressed_row)) => compressed_row,
+            Some(ScrollbackRow::Uncompressed(row)) => CompressedRow::new(&row),
+            None => return false,
+        };
+        match spilled_rows.push_back(&compressed_row) {
+            Ok(()) => true,
+            Err(e) => {
+                log::error!("Failed to spill scrollback to disk: {}", e);
+                self.rows
+                    .push_front(ScrollbackRow::Compressed(compressed_row));
+                false
+            },
+        }
+    }
+    pub fn get(&self, index: usize) -> Option<Cow<'_, Row>> {
+        let spilled_row_count = self.spilled_row_count();
+        if index < spilled_row_count {
+            self.spilled_rows
+                .as_ref()
+                .and_then(|spilled_rows| spilled_rows.get(index))
+                .map(Cow::Owned)
+        } else {
+            self.rows
+                .get(index - spilled_row_count)
+                .map(|row| row.as_row())
+        }
+    }
+    pub fn back_mut(&mut self) -> Option<&mut Row> {
+        if self.rows.is_empty() {
+            let row = self.spilled_rows.as_mut()?.pop_back()?;
+            self.rows.push_back(ScrollbackRow::Uncompressed(row));
+        }
+        let row = self.rows.back_mut()?;
+        if let ScrollbackRow::Compressed(compressed_row) = row {
+            *row = ScrollbackRow::Uncompressed(compressed_row.decompress());
+        }
+        match row {
+            ScrollbackRow::Uncompressed(row) => Some(row),
+            ScrollbackRow::Compressed(_) => None,
+        }
+    }
+    pub fn iter(&self) -> ScrollbackIter<'_> {
+        ScrollbackIter {
+            scrollback: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+    /// The widths of the rows, without decompressing them or reading them from disk
+    pub fn widths(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.spilled_rows
+            .iter()
+            .flat_map(|spilled_rows| spilled_rows.rows.iter().map(|row| row.width as usize))
+            .chain(self.rows.iter().map(|row| row.width()))
+    }
+    /// The semantic prompt marks of the row at this index, without decompressing it or reading
+    /// it from disk
+    pub fn semantic_prompt_marks(&self, index: usize) -> Option<SemanticPromptMarks> {
+        let spilled_row_count = self.spilled_row_count();
+        if index < spilled_row_count {
+            self.spilled_rows
+                .as_ref()
+                .and_then(|spilled_rows| spilled_rows.rows.get(index))
+                .map(|row| row.semantic_prompt_marks)
+        } else {
+            self.rows
+                .get(index - spilled_row_count)
+                .map(|row| row.semantic_prompt_marks())
+        }
+    }
+    pub fn spilled_row_count(&self) -> usize {
+        self.spilled_rows
+            .as_ref()
+            .map(|spilled_rows| spilled_rows.rows.len())
+            .unwrap_or(0)
+    }
+    pub fn rows_in_memory(&self) -> usize {
+        self.rows.len()
+    }
+    pub fn compressed_row_count(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| matches!(row, ScrollbackRow::Compressed(_)))
+            .count()
+    }
+}
+
+pub struct ScrollbackIter<'a> {
+    scrollback: &'a Scrollback,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Iterator for ScrollbackIter<'a> {
+    type Item = Cow<'a, Row>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        self.scrollback.get(self.front - 1)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // skip rows without reading them
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back.saturating_sub(self.front);
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for ScrollbackIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.scrollback.get(self.back)
+    }
+}
+
+impl<'a> ExactSizeIterator for ScrollbackIter<'a> {}
+
+impl<'a> IntoIterator for &'a Scrollback {
+    type Item = Cow<'a, Row>;
+    type IntoIter = ScrollbackIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A row packed as its text and the runs of styles of its characters, rather than a style for
+/// each character
+pub struct CompressedRow {
+    text: Box<str>,
+    styles: Box<[(u32, RcCharacterStyles)]>, // (run length, styles)
+    singlewidth_characters: Box<[u32]>,      // indices of characters added as single width anyway
+    width: usize,
+    is_canonical: bool,
+    semantic_prompt_marks: SemanticPromptMarks,
+}
+
+impl CompressedRow {
+    pub fn new(row: &Row) -> Self {
+        let mut text = String::with_capacity(row.columns.len());
+        let mut styles: Vec<(u32, RcCharacterStyles)> = vec![];
+        let mut singlewidth_characters = vec![];
+        let mut width = 0;
+        for (i, terminal_character) in row.columns.iter().enumerate() {
+            text.push(terminal_character.character);
+            width += terminal_character.width();
+            if terminal_character.character.width().unwrap_or(0) != terminal_character.width() {
+                singlewidth_characters.push(i as u32);
+            }
+            match styles.last_mut() {
+                Some((run_length, run_styles)) if *run_styles == terminal_character.styles => {
+                    *run_length += 1;
+                },
+                _ => styles.push((1, terminal_character.styles.clone())),
+            }
+        }
+        CompressedRow {
+            text: text.into_boxed_str(),
+            styles: styles.into_boxed_slice(),
+            singlewidth_characters: singlewidth_characters.into_boxed_slice(),
+            width,
+            is_canonical: row.is_canonical,
+            semantic_prompt_marks: row.semantic_prompt_marks,
+        }
+    }
+    pub fn decompress(&self) -> Row {
+        let styles = self
+            .styles
+            .iter()
+            .flat_map(|(run_length, styles)| std::iter::repeat(styles).take(*run_length as usize));
+        let mut singlewidth_characters = self.singlewidth_characters.iter().peekable();
+        let columns = self
+            .text
+            .chars()
+            .zip(styles)
+            .enumerate()
+            .map(|(i, (character, styles))| {
+                if singlewidth_characters
+                    .next_if(|index| **index as usize == i)
+                    .is_some()
+                {
+                    TerminalCharacter::new_singlewidth_styled(character, styles.clone())
+                } else {
+                    TerminalCharacter::new_styled(character, styles.clone())
+                }
+            })
+            .collect();
+        let row = Row::from_columns(columns).with_semantic_prompt_marks(self.semantic_prompt_marks);
+        if self.is_canonical {
+            row.canonical()
+        } else {
+            row
+        }
+    }
+}
+
+/// Rows spilled to a file, oldest first
+///
+/// Each row is appended to the file as it is spilled, and the file is truncated as rows are read
+/// back from its end, so that it only ever holds the rows that are still in the scrollback
+///
+/// Rows are written as MessagePack, each prefixed by its length (as a little endian u32) so that
+/// the file can be read sequentially without the offsets we keep in memory
+struct SpilledRows {
+    path: PathBuf,
+    file: Option<File>, // created when the first row is spilled
+    rows: Vec<SpilledRow>,
+    end: u64,
+}
+
+struct SpilledRow {
+    offset: u64,
+    width: u32,
+    semantic_prompt_marks: SemanticPromptMarks,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+struct SerializedRow {
+    text: String,
+    styles: Vec<(u32, CharacterStyles)>,
+    singlewidth_characters: Vec<u32>,
+    is_canonical: bool,
+}
+
+impl SpilledRows {
+    pub fn new(path: PathBuf) -> Self {
+        SpilledRows {
+            path,
+            file: None,
+            rows: vec![],
+            end: 0,
+        }
+    }
+    pub fn push_back(&mut self, row: &CompressedRow) -> io::Result<()> {
+        if self.file.is_none() {
+            if let Some(folder) = self.path.parent() {
+                fs::create_dir_all(folder)?;
+            }
+            self.file = Some(
+                OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .open(&self.path)?,
+            );
+        }
+        let serialized_row = rmp_serde::to_vec(&SerializedRow {
+            text: row.text.to_string(),
+            styles: row
+                .styles
+                .iter()
+                .map(|(run_length, styles)| (*run_length, **styles))
+                .collect(),
+            singlewidth_characters: row.singlewidth_characters.to_vec(),
+            is_canonical: row.is_canonical,
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut record = Vec::with_capacity(LENGTH_PREFIX_SIZE + serialized_row.len());
+        record.extend_from_slice(&(serialized_row.len() as u32).to_le_bytes());
+        record.extend_from_slice(&serialized_row);
+        if let Some(file) = self.file.as_ref() {
+            file.write_all_at(&record, self.end)?;
+        }
+        self.rows.push(SpilledRow {
+            offset: self.end,
+            width: row.width as u32,
+            semantic_prompt_marks: row.semantic_prompt_marks,
+        });
+        self.end += record.len() as u64;
+        Ok(())
+    }
+    pub fn pop_back(&mut self) -> Option<Row> {
+        let row = self.get(self.rows.len().checked_sub(1)?);
+        if let Some(spilled_row) = self.rows.pop() {
+            self.truncate(spilled_row.offset);
+        }
+        row
+    }
+    pub fn get(&self, index: usize) -> Option<Row> {
+        let spilled_row = self.rows.get(index)?;
+        let end = self
+            .rows
+            .get(index + 1)
+            .map(|next_row| next_row.offset)
+            .unwrap_or(self.end);
+        match self.read(spilled_row, end) {
+            Ok(row) => Some(row),
+            Err(e) => {
+                log::error!("Failed to read scrollback from disk: {}", e);
+                // keep the scrollback consistent rather than losing track of the rows after this
+                // one
+                Some(Row::new().canonical())
+            },
+        }
+    }
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.truncate(0);
+    }
+    fn read(&self, spilled_row: &SpilledRow, end: u64) -> io::Result<Row> {
+        let file = self
+            .file
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no scrollback file"))?;
+        let mut record = vec![0; end.saturating_sub(spilled_row.offset) as usize];
+        file.read_exact_at(&mut record, spilled_row.offset)?;
+        let serialized_row = record
+            .get(LENGTH_PREFIX_SIZE..)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated row"))?;
+        let serialized_row: SerializedRow = rmp_serde::from_slice(serialized_row)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let compressed_row = CompressedRow {
+            text: serialized_row.text.into_boxed_str(),
+            styles: serialized_row
+                .styles
+                .into_iter()
+                .map(|(run_length, styles)| (run_length, styles.into()))
+                .collect(),
+            singlewidth_characters: serialized_row.singlewidth_characters.into_boxed_slice(),
+            width: spilled_row.width as usize,
+            is_canonical: serialized_row.is_canonical,
+            semantic_prompt_marks: spilled_row.semantic_prompt_marks,
+        };
+        Ok(compressed_row.decompress())
+    }
+    fn truncate(&mut self, end: u64) {
+        self.end = end;
+        if let Some(file) = self.file.as_ref() {
+            if let Err(e) = file.set_len(end) {
+                log::error!("Failed to truncate scrollback file: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for SpilledRows {
+    fn drop(&mut self) {
+        if self.file.is_some() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/scrollback_tests.rs"]
+mod scrollback_tests;
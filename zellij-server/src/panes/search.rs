@@ -5,12 +5,25 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
+use zellij_utils::regex::{Regex, RegexBuilder};
 
 // If char is neither alphanumeric nor an underscore do we consider it a word-boundary
 fn is_word_boundary(x: &Option<char>) -> bool {
     x.map_or(true, |c| !c.is_ascii_alphanumeric() && c != '_')
 }
 
+// The tail may have not been wrapped yet (when coming from lines_below),
+// so it could be that the end extends across more characters than the row is wide.
+// Therefore we need to reflow the end:
+fn reflow_selection_ends(selections: &mut [Selection], row: &Row) {
+    for s in selections.iter_mut() {
+        while s.end.column() > row.width() {
+            s.end.column.0 -= row.width();
+            s.end.line.0 += 1;
+        }
+    }
+}
+
 #[derive(Debug)]
 enum SearchSource<'a> {
     Main(&'a Row),
@@ -98,6 +111,10 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Interpret the needle as a regular expression
+    pub regex_search: bool,
+    // The needle compiled as a regular expression, None if it is not a valid one
+    regex: Option<Regex>,
 }
 
 impl SearchResult {
@@ -141,7 +158,23 @@ impl SearchResult {
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search || self.whole_word_only || self.case_insensitive || self.regex_search
+    }
+
+    pub(crate) fn set_needle(&mut self, needle: &str) {
+        self.needle = needle.to_string();
+        self.compile_regex();
+    }
+
+    pub(crate) fn compile_regex(&mut self) {
+        self.regex = if self.regex_search {
+            RegexBuilder::new(&self.needle)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .ok()
+        } else {
+            None
+        };
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -181,6 +214,12 @@ impl SearchResult {
         if self.needle.is_empty() || row.columns.is_empty() {
             return res;
         }
+        if self.regex_search {
+            if let Some(regex) = self.regex.as_ref() {
+                res = self.search_row_with_regex(regex, ridx, row, tail);
+            }
+            return res;
+        }
 
         let mut tailit = tail.iter();
         let mut source = SearchSource::Main(row); // Where we currently get the haystack-characters from
@@ -267,15 +306,62 @@ impl SearchResult {
             }
         }
 
-        // The tail may have not been wrapped yet (when coming from lines_below),
-        // so it could be that the end extends across more characters than the row is wide.
-        // Therefore we need to reflow the end:
-        for s in res.iter_mut() {
-            while s.end.column() > row.width() {
-                s.end.column.0 -= row.width();
-                s.end.line.0 += 1;
+        reflow_selection_ends(&mut res, row);
+        res
+    }
+
+    /// Search a row and its tail for matches of the regex that start in the row.
+    /// The row and its tail are matched as one line, so matches can span across them.
+    fn search_row_with_regex(
+        &self,
+        regex: &Regex,
+        ridx: usize,
+        row: &Row,
+        tail: &[&Row],
+    ) -> Vec<Selection> {
+        let mut res = Vec::new();
+        let mut haystack = String::new();
+        // The byte offset of every character in the haystack, along with its line and column
+        let mut positions = Vec::new();
+        for (line, r) in std::iter::once(row).chain(tail.iter().copied()).enumerate() {
+            for (column, c) in r.columns.iter().enumerate() {
+                positions.push((haystack.len(), ridx + line, column));
+                haystack.push(c.character);
+            }
+        }
+        let position_of = |offset: usize| positions.partition_point(|(o, _, _)| *o < offset);
+        let end_of_main_row = row.columns.len();
+
+        let mut search_start = 0;
+        while let Some(hit) = regex.find_at(&haystack, search_start) {
+            let first_char = position_of(hit.start());
+            if first_char >= end_of_main_row {
+                // We are only interested in hits starting in the main row
+                break;
+            }
+            let is_whole_word = !self.whole_word_only
+                || (is_word_boundary(&haystack[..hit.start()].chars().next_back())
+                    && is_word_boundary(&haystack[hit.end()..].chars().next()));
+            if hit.start() == hit.end() || !is_whole_word {
+                // Try again from the next character
+                search_start = positions
+                    .get(first_char + 1)
+                    .map(|(o, _, _)| *o)
+                    .unwrap_or(haystack.len());
+                if search_start == haystack.len() {
+                    break;
+                }
+                continue;
             }
+            let (_, start_line, start_column) = positions[first_char];
+            let (_, end_line, end_column) = positions[position_of(hit.end()) - 1];
+            let mut selection = Selection::default();
+            selection.start(Position::new(start_line as i32, start_column as u16));
+            selection.end(Position::new(end_line as i32, (end_column + 1) as u16));
+            res.push(selection);
+            search_start = hit.end();
         }
+        reflow_selection_ends(&mut res, row);
         res
     }
 
@@ -411,7 +497,7 @@ impl Grid {
     }
 
     pub fn set_search_string(&mut self, needle: &str) {
-        self.search_results.needle = needle.to_string();
+        self.search_results.set_needle(needle);
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
@@ -454,6 +540,7 @@ impl Grid {
 
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.compile_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -467,6 +554,17 @@ impl Grid {
         self.search_results.wrap_search = !self.search_results.wrap_search;
     }
 
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex_search = !self.search_results.regex_search;
+        self.search_results.compile_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.active = None;
+        self.search_viewport();
+    }
+
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
         for line in self.search_results.selections.drain(..) {
@@ -8,7 +8,8 @@ use zellij_utils::position::Position;
 pub struct Selection {
     pub start: Position,
     pub end: Position,
-    active: bool, // used to handle moving the selection up and down
+    active: bool,     // used to handle moving the selection up and down
+    whole_rows: bool, // rows are selected in full, even the parts wider than the viewport
 }
 
 impl Default for Selection {
@@ -17,6 +18,7 @@ impl Default for Selection {
             start: Position::new(0, 0),
             end: Position::new(0, 0),
             active: false,
+            whole_rows: false,
         }
     }
 }
@@ -24,10 +26,21 @@ impl Default for Selection {
 impl Selection {
     pub fn start(&mut self, start: Position) {
         self.active = true;
+        self.whole_rows = false;
         self.start = start;
         self.end = start;
     }
 
+    pub fn select_whole_rows(&mut self, start: Position, end: Position) {
+        self.start(start);
+        self.end(end);
+        self.whole_rows = true;
+    }
+
+    pub fn selects_whole_rows(&self) -> bool {
+        self.whole_rows
+    }
+
     pub fn to(&mut self, to: Position) {
         self.end = to
     }
@@ -74,6 +87,7 @@ impl Selection {
     pub fn reset(&mut self) {
         self.start = Position::new(0, 0);
         self.end = self.start;
+        self.whole_rows = false;
     }
 
     pub fn sorted(&self) -> Self {
@@ -86,6 +100,7 @@ impl Selection {
             start,
             end,
             active: self.active,
+            whole_rows: self.whole_rows,
         }
     }
 
@@ -1,4 +1,5 @@
 use crate::output::SixelImageChunk;
+use crate::panes::kitty_graphics::KittyImageStore;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -152,37 +153,13 @@ impl SixelGrid {
         self.sixel_parser = None;
         if let Some(sixel_deserializer) = self.currently_parsing.as_mut() {
             if let Ok(sixel_image) = sixel_deserializer.create_image() {
-                let image_pixel_size = sixel_image.pixel_size();
-                let image_size_and_coordinates = PixelRect::new(
+                self.currently_parsing = None;
+                self.place_image(
+                    new_image_id,
+                    sixel_image.pixel_size(),
                     x_pixel_coordinates,
                     y_pixel_coordinates,
-                    image_pixel_size.0,
-                    image_pixel_size.1,
                 );
-
-                // here we remove images which this image covers completely to save on system
-                // resources - TODO: also do this with partial covers, eg. if several images
-                // together cover one image
-                for (image_id, pixel_rect) in &self.sixel_image_locations {
-                    if let Some(intersecting_rect) =
-                        pixel_rect.intersecting_rect(&image_size_and_coordinates)
-                    {
-                        if intersecting_rect.x == pixel_rect.x
-                            && intersecting_rect.y == pixel_rect.y
-                            && intersecting_rect.height == pixel_rect.height
-                            && intersecting_rect.width == pixel_rect.width
-                        {
-                            self.image_ids_to_reap.push(*image_id);
-                        }
-                    }
-                }
-                for image_id in &self.image_ids_to_reap {
-                    self.sixel_image_locations.remove(image_id);
-                }
-
-                self.sixel_image_locations
-                    .insert(new_image_id, image_size_and_coordinates);
-                self.currently_parsing = None;
                 Some(sixel_image)
             } else {
                 None
@@ -191,6 +168,51 @@ impl SixelGrid {
             None
         }
     }
+    pub fn place_image(
+        &mut self,
+        new_image_id: usize,
+        image_pixel_size: (usize, usize), // (height, width)
+        x_pixel_coordinates: usize,
+        y_pixel_coordinates: usize,
+    ) {
+        let image_size_and_coordinates = PixelRect::new(
+            x_pixel_coordinates,
+            y_pixel_coordinates,
+            image_pixel_size.0,
+            image_pixel_size.1,
+        );
+
+        // here we remove images which this image covers completely to save on system
+        // resources - TODO: also do this with partial covers, eg. if several images
+        // together cover one image
+        for (image_id, pixel_rect) in &self.sixel_image_locations {
+            if let Some(intersecting_rect) =
+                pixel_rect.intersecting_rect(&image_size_and_coordinates)
+            {
+                if intersecting_rect.x == pixel_rect.x
+                    && intersecting_rect.y == pixel_rect.y
+                    && intersecting_rect.height == pixel_rect.height
+                    && intersecting_rect.width == pixel_rect.width
+                {
+                    self.image_ids_to_reap.push(*image_id);
+                }
+            }
+        }
+        for image_id in &self.image_ids_to_reap {
+            self.sixel_image_locations.remove(image_id);
+        }
+
+        self.sixel_image_locations
+            .insert(new_image_id, image_size_and_coordinates);
+    }
+    pub fn image_location(&self, image_id: usize) -> Option<&PixelRect> {
+        self.sixel_image_locations.get(&image_id)
+    }
+    pub fn remove_image(&mut self, image_id: usize) {
+        if self.sixel_image_locations.remove(&image_id).is_some() {
+            self.image_ids_to_reap.push(image_id);
+        }
+    }
     pub fn image_coordinates(&self) -> impl Iterator<Item = (usize, &PixelRect)> {
         self.sixel_image_locations
             .iter()
@@ -262,7 +284,12 @@ impl SixelGrid {
         }
     }
     pub fn next_image_id(&self) -> usize {
-        self.sixel_image_store.borrow().sixel_images.keys().len()
+        // ids are never reused, so that an image removed from the store (eg. when reaped) is
+        // never confused with a newer one
+        let mut sixel_image_store = self.sixel_image_store.borrow_mut();
+        let next_image_id = sixel_image_store.next_image_id;
+        sixel_image_store.next_image_id += 1;
+        next_image_id
     }
     pub fn new_sixel_image(&mut self, sixel_image_id: usize, sixel_image: SixelImage) {
         self.sixel_image_store
@@ -428,6 +455,8 @@ type SixelImageCache = HashMap<PixelRect, String>;
 #[derive(Debug, Clone, Default)]
 pub struct SixelImageStore {
     sixel_images: HashMap<usize, (SixelImage, SixelImageCache)>,
+    next_image_id: usize,
+    pub kitty_images: KittyImageStore, // transmitted but not necessarily placed yet
 }
 
 impl SixelImageStore {
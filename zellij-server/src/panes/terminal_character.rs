@@ -8,9 +8,12 @@ use unicode_width::UnicodeWidthStr;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::{
     data::{PaletteColor, Style},
+    serde,
     vte::ParamsIter,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::panes::alacritty_functions::parse_sgr_color;
 
 pub const EMPTY_TERMINAL_CHARACTER: TerminalCharacter = TerminalCharacter {
@@ -60,7 +63,8 @@ thread_local! {
         RcCharacterStyles::Rc(Rc::new(DEFAULT_STYLES));
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum AnsiCode {
     On,
     Reset,
@@ -70,7 +74,8 @@ pub enum AnsiCode {
     Underline(Option<AnsiStyledUnderline>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum AnsiStyledUnderline {
     Double,
     Undercurl,
@@ -87,7 +92,8 @@ impl From<PaletteColor> for AnsiCode {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum NamedColor {
     Black,
     Red,
@@ -209,7 +215,8 @@ impl RcCharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub struct CharacterStyles {
     pub foreground: Option<AnsiCode>,
     pub background: Option<AnsiCode>,
@@ -762,7 +769,8 @@ impl Display for CharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum LinkAnchor {
     Start(u16),
     End,
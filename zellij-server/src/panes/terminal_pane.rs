@@ -1,19 +1,21 @@
 use crate::output::{CharacterChunk, SixelImageChunk};
 use crate::panes::sixel::SixelImageStore;
-use crate::panes::LinkHandler;
 use crate::panes::{
     grid::Grid,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
+use crate::panes::{LinkHandler, PaneMonitor};
 use crate::pty::VteBytes;
 use crate::tab::{AdjustedInput, Pane};
 use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::time::{self, Instant};
 use zellij_utils::input::command::RunCommand;
+use zellij_utils::input::kitty_keyboard::{KittyKeyEvent, KittyKeyboardFlags};
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
     data::{
@@ -130,6 +132,9 @@ pub struct TerminalPane {
     invoked_with: Option<Run>,
     #[allow(dead_code)]
     arrow_fonts: bool,
+    has_unseen_notification: bool, // the pane sent a notification and was not focused since
+    pinned: bool,
+    pub monitor: PaneMonitor,
 }
 
 impl Pane for TerminalPane {
@@ -180,6 +185,7 @@ impl Pane for TerminalPane {
         self.set_should_render(true);
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
+            self.grid.handle_apc_byte(byte);
         }
     }
     fn cursor_coordinates(&self) -> Option<(usize, usize)> {
@@ -217,6 +223,21 @@ impl Pane for TerminalPane {
             }
         }
 
+        let raw_input_is_key_release = raw_input_bytes_are_kitty
+            && KittyKeyEvent::from_bytes(&raw_input_bytes)
+                .map(|key_event| key_event.is_release())
+                .unwrap_or(false);
+        if raw_input_is_key_release
+            && (self.is_held.is_some()
+                || !self
+                    .grid
+                    .kitty_keyboard_flags()
+                    .contains(KittyKeyboardFlags::REPORT_EVENT_TYPES))
+        {
+            // key releases are only of interest to applications that asked for them
+            return None;
+        }
+
         if self.is_held.is_some() {
             if key_with_modifier
                 .as_ref()
@@ -245,7 +266,7 @@ impl Pane for TerminalPane {
                 }
             }
         } else {
-            if self.grid.supports_kitty_keyboard_protocol {
+            if self.grid.supports_kitty_keyboard_protocol() {
                 self.adjust_input_to_terminal_with_kitty_keyboard_protocol(
                     key_with_modifier,
                     raw_input_bytes,
@@ -350,6 +371,9 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex_search {
+                    modifiers.push("r")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
@@ -377,6 +401,12 @@ impl Pane for TerminalPane {
                 frame.add_exit_status(exit_status.as_ref().copied());
             }
         }
+        if self.has_unseen_notification {
+            frame.indicate_unseen_notification();
+        }
+        if self.pinned {
+            frame.indicate_pinned();
+        }
         if let Some((frame_color_override, _text)) = self.pane_frame_color_override.as_ref() {
             frame.override_color(*frame_color_override);
         }
@@ -524,6 +554,25 @@ impl Pane for TerminalPane {
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
+    fn scroll_to_previous_prompt(&mut self) {
+        self.grid.scroll_to_previous_prompt();
+        self.set_should_render(true);
+    }
+    fn scroll_to_next_prompt(&mut self) {
+        self.grid.scroll_to_next_prompt();
+        self.set_should_render(true);
+    }
+    fn select_command_output(&mut self) -> bool {
+        let found_command_output = self.grid.select_command_output();
+        self.set_should_render(true);
+        found_command_output
+    }
+    fn last_command_exit_code(&self) -> Option<i32> {
+        self.grid.last_command_exit_code
+    }
+    fn selected_command_exit_code(&self) -> Option<i32> {
+        self.grid.selected_command_exit_code
+    }
 
     fn active_at(&self) -> Instant {
         self.active_at
@@ -543,6 +592,39 @@ impl Pane for TerminalPane {
         self.grid.pending_clipboard_update.take()
     }
 
+    fn drain_cwd_update(&mut self) -> Option<PathBuf> {
+        self.grid.pending_cwd_update.take()
+    }
+
+    fn drain_notifications(&mut self) -> Vec<(Option<String>, String)> {
+        self.grid.pending_notifications.drain(..).collect()
+    }
+
+    fn set_unseen_notification(&mut self, has_unseen_notification: bool) {
+        self.has_unseen_notification = has_unseen_notification;
+        self.set_should_render(true);
+    }
+
+    fn has_unseen_notification(&self) -> bool {
+        self.has_unseen_notification
+    }
+
+    fn drain_bell(&mut self) -> bool {
+        std::mem::replace(&mut self.grid.pending_bell, false)
+    }
+
+    fn monitor(&self) -> Option<&PaneMonitor> {
+        Some(&self.monitor)
+    }
+
+    fn monitor_mut(&mut self) -> Option<&mut PaneMonitor> {
+        Some(&mut self.monitor)
+    }
+
+    fn reported_cwd(&self) -> Option<PathBuf> {
+        self.grid.cwd.clone()
+    }
+
     fn start_selection(&mut self, start: &Position, _client_id: ClientId) {
         self.grid.start_selection(start);
         self.set_should_render(true);
@@ -617,6 +699,15 @@ impl Pane for TerminalPane {
         self.exclude_from_sync
     }
 
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+        self.set_should_render(true);
+    }
+
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
     fn mouse_left_click(&self, position: &Position, is_held: bool) -> Option<String> {
         self.grid.mouse_left_click_signal(position, is_held)
     }
@@ -695,6 +786,10 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
@@ -828,6 +923,9 @@ impl TerminalPane {
             pane_frame_color_override: None,
             invoked_with,
             arrow_fonts,
+            has_unseen_notification: false,
+            pinned: false,
+            monitor: PaneMonitor::default(),
         }
     }
     pub fn get_x(&self) -> usize {
@@ -900,15 +998,24 @@ impl TerminalPane {
         raw_input_bytes: Vec<u8>,
         raw_input_bytes_are_kitty: bool,
     ) -> Option<AdjustedInput> {
+        let flags = self.grid.kitty_keyboard_flags();
         if raw_input_bytes_are_kitty {
-            Some(AdjustedInput::WriteBytesToTerminal(raw_input_bytes))
+            // the host terminal reports keys with all the enhancements Zellij asked for, so we
+            // re-serialize them with only the enhancements this terminal pane asked for
+            match KittyKeyEvent::from_bytes(&raw_input_bytes) {
+                Some(key_event) => key_event
+                    .serialize(flags)
+                    .map(AdjustedInput::WriteBytesToTerminal),
+                None => Some(AdjustedInput::WriteBytesToTerminal(raw_input_bytes)),
+            }
         } else {
             // here what happens is that the host terminal is operating in non "kitty keys" mode, but
             // this terminal pane *is* operating in "kitty keys" mode - so we need to serialize the "non kitty"
             // key to a "kitty key"
             key.as_ref()
-                .and_then(|k| k.serialize_kitty())
-                .map(|s| AdjustedInput::WriteBytesToTerminal(s.as_bytes().to_vec()))
+                .and_then(KittyKeyEvent::from_key_with_modifier)
+                .and_then(|key_event| key_event.serialize(flags))
+                .map(AdjustedInput::WriteBytesToTerminal)
         }
     }
     fn adjust_input_to_terminal_without_kitty_keyboard_protocol(
@@ -992,9 +1099,15 @@ impl TerminalPane {
             // this terminal pane is not - so we need to serialize the kitty key to "non kitty" if
             // possible - if not possible (eg. with multiple modifiers), we'll return a None here
             // and write nothing to the terminal pane
-            key.as_ref()
-                .and_then(|k| k.serialize_non_kitty())
-                .map(|s| AdjustedInput::WriteBytesToTerminal(s.as_bytes().to_vec()))
+            match KittyKeyEvent::from_bytes(&raw_input_bytes) {
+                Some(key_event) => key_event
+                    .serialize(KittyKeyboardFlags::empty())
+                    .map(AdjustedInput::WriteBytesToTerminal),
+                None => key
+                    .as_ref()
+                    .and_then(|k| k.serialize_non_kitty())
+                    .map(|s| AdjustedInput::WriteBytesToTerminal(s.as_bytes().to_vec())),
+            }
         } else {
             Some(AdjustedInput::WriteBytesToTerminal(raw_input_bytes))
         }
@@ -523,6 +523,134 @@ impl TiledPanes {
             },
         }
     }
+    pub fn stack_active_pane_in_direction(
+        &mut self,
+        client_id: ClientId,
+        direction: Direction,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to stack active pane {direction:?} for client {client_id}");
+        let active_pane_id = self
+            .get_active_pane_id(client_id)
+            .with_context(err_context)?;
+        let neighbour_pane_id = {
+            let pane_grid = TiledPaneGrid::new(
+                &mut self.panes,
+                &self.panes_to_hide,
+                *self.display_area.borrow(),
+                *self.viewport.borrow(),
+            );
+            match direction {
+                Direction::Left => pane_grid.next_selectable_pane_id_to_the_left(&active_pane_id),
+                Direction::Right => pane_grid.next_selectable_pane_id_to_the_right(&active_pane_id),
+                Direction::Up => pane_grid.next_selectable_pane_id_above(&active_pane_id),
+                Direction::Down => pane_grid.next_selectable_pane_id_below(&active_pane_id),
+            }
+        };
+        match neighbour_pane_id {
+            Some(neighbour_pane_id) => self
+                .stack_pane_onto(active_pane_id, neighbour_pane_id)
+                .with_context(err_context),
+            None => Ok(()),
+        }
+    }
+    pub fn stack_all_panes(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to stack all panes for client {client_id}");
+        let active_pane_id = self
+            .get_active_pane_id(client_id)
+            .with_context(err_context)?;
+        let mut pane_ids_to_stack: Vec<(PaneId, PaneGeom)> = self
+            .panes
+            .iter()
+            .filter(|(pane_id, pane)| {
+                **pane_id != active_pane_id
+                    && pane.selectable()
+                    && !self.panes_to_hide.contains(pane_id)
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+            .collect();
+        pane_ids_to_stack.sort_by(|(_a_id, a), (_b_id, b)| (a.y, a.x).cmp(&(b.y, b.x)));
+        for (pane_id, _pane_geom) in pane_ids_to_stack {
+            if let Err(e) = self.stack_pane_onto(pane_id, active_pane_id) {
+                // panes that cannot be stacked (eg. because their space cannot be filled by
+                // their neighbours) are left in place
+                log::error!("Failed to stack pane {:?}: {:?}", pane_id, e);
+            }
+        }
+        self.expand_pane_in_stack(active_pane_id);
+        self.focus_pane(active_pane_id, client_id);
+        Ok(())
+    }
+    pub fn unstack_pane(&mut self, pane_id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to unstack pane {pane_id:?}");
+        let pane_is_stacked = self
+            .panes
+            .get(&pane_id)
+            .map(|p| p.current_geom().is_stacked)
+            .with_context(err_context)?;
+        if !pane_is_stacked {
+            return Ok(());
+        }
+        let new_geom = StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide)
+            .remove_pane_from_stack(&pane_id)
+            .with_context(err_context)?;
+        self.panes
+            .get_mut(&pane_id)
+            .with_context(err_context)?
+            .set_geom(new_geom);
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(())
+    }
+    fn stack_pane_onto(&mut self, pane_id: PaneId, destination_pane_id: PaneId) -> Result<()> {
+        let err_context =
+            || format!("failed to stack pane {pane_id:?} onto pane {destination_pane_id:?}");
+        let pane_geom = self
+            .panes
+            .get(&pane_id)
+            .map(|p| p.current_geom())
+            .with_context(err_context)?;
+        let destination_geom = self
+            .panes
+            .get(&destination_pane_id)
+            .map(|p| p.current_geom())
+            .with_context(err_context)?;
+        let panes_are_in_the_same_stack = pane_geom.is_stacked
+            && destination_geom.is_stacked
+            && pane_geom.x == destination_geom.x
+            && pane_geom.cols == destination_geom.cols;
+        if panes_are_in_the_same_stack {
+            return Ok(());
+        }
+        let filled_space_over_pane = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        )
+        .fill_space_over_pane(pane_id);
+        if !filled_space_over_pane {
+            return Err(anyhow!("No room to remove pane from its current location"))
+                .with_context(err_context);
+        }
+        let mut pane = self.panes.remove(&pane_id).with_context(err_context)?;
+        match StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide)
+            .make_room_for_new_pane_in_stack_of(&destination_pane_id)
+        {
+            Ok(new_geom) => {
+                pane.set_geom(new_geom);
+                self.panes.insert(pane_id, pane);
+            },
+            Err(e) => {
+                // we already made room over the pane, so we have to find it a new place
+                let should_relayout = false;
+                self.add_pane(pane_id, pane, should_relayout);
+                self.set_pane_frames(self.draw_pane_frames);
+                return Err(e).with_context(err_context);
+            },
+        }
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(())
+    }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         if self.panes_to_hide.contains(&pane_id) {
             // this means there is a fullscreen pane that is not the current pane, let's unset it
@@ -608,6 +736,18 @@ impl TiledPanes {
     pub fn focused_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
         self.active_panes.get(&client_id).copied()
     }
+    pub fn any_active_pane_id(&self) -> Option<PaneId> {
+        // clients that left the tab keep their focus in it
+        self.first_active_pane_id()
+            .or_else(|| self.active_panes.values().next().copied())
+    }
+    pub fn set_action_target(&mut self, pane_id: PaneId, client_id: ClientId) {
+        self.active_panes
+            .insert_without_focus_events(client_id, pane_id);
+    }
+    pub fn clear_action_target(&mut self, client_id: ClientId) {
+        self.active_panes.remove_without_focus_events(&client_id);
+    }
     #[allow(clippy::borrowed_box)]
     pub fn get_pane(&self, pane_id: PaneId) -> Option<&Box<dyn Pane>> {
         self.panes.get(&pane_id)
@@ -1,4 +1,7 @@
-use crate::{panes::PaneId, tab::Pane};
+use crate::{
+    panes::PaneId,
+    tab::{Pane, MIN_TERMINAL_HEIGHT},
+};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
@@ -433,38 +436,136 @@ impl<'a> StackedPanes<'a> {
         ))
     }
     pub fn make_room_for_new_pane(&mut self) -> Result<PaneGeom> {
-        let err_context = || format!("Failed to add pane to stack");
         let all_stacks = self.get_all_stacks()?;
         for stack in all_stacks {
             if let Some((id_of_flexible_pane_in_stack, _flexible_pane_in_stack)) = stack
                 .iter()
                 .find(|(_p_id, p)| !p.rows.is_fixed() && p.rows.as_usize() > 1)
             {
-                self.make_lowest_pane_in_stack_flexible(id_of_flexible_pane_in_stack)?;
-                let all_stacked_pane_positions =
-                    self.positions_in_stack(id_of_flexible_pane_in_stack)?;
-                let position_of_flexible_pane =
-                    self.position_of_flexible_pane(&all_stacked_pane_positions)?;
-                let (flexible_pane_id, mut flexible_pane_geom) = *all_stacked_pane_positions
-                    .iter()
-                    .nth(position_of_flexible_pane)
-                    .with_context(err_context)?;
-                let mut position_for_new_pane = flexible_pane_geom.clone();
-                position_for_new_pane
-                    .rows
-                    .set_inner(position_for_new_pane.rows.as_usize() - 1);
-                position_for_new_pane.y = position_for_new_pane.y + 1;
-                flexible_pane_geom.rows = Dimension::fixed(1);
-                self.panes
-                    .borrow_mut()
-                    .get_mut(&flexible_pane_id)
-                    .with_context(err_context)?
-                    .set_geom(flexible_pane_geom);
-                return Ok(position_for_new_pane);
+                return self.make_room_in_stack(id_of_flexible_pane_in_stack);
             }
         }
         Err(anyhow!("Not enough room for another pane!"))
     }
+    pub fn make_room_for_new_pane_in_stack_of(&mut self, id: &PaneId) -> Result<PaneGeom> {
+        // if the pane is not stacked, it becomes a stack of its own which the new pane then joins
+        let err_context = || format!("Failed to add pane to stack of pane {:?}", id);
+        let all_stacked_pane_positions = {
+            let mut panes = self.panes.borrow_mut();
+            let pane = panes.get_mut(id).with_context(err_context)?;
+            let mut pane_geom = pane.position_and_size();
+            if !pane_geom.is_stacked {
+                if pane_geom.rows.is_fixed() {
+                    return Err(anyhow!("Cannot stack panes with a fixed height"));
+                }
+                pane_geom.is_stacked = true;
+                pane.set_geom(pane_geom);
+            }
+            drop(panes);
+            self.positions_in_stack(id)?
+        };
+        let position_of_flexible_pane =
+            self.position_of_flexible_pane(&all_stacked_pane_positions)?;
+        let (_flexible_pane_id, flexible_pane_geom) = all_stacked_pane_positions
+            .iter()
+            .nth(position_of_flexible_pane)
+            .with_context(err_context)?;
+        if flexible_pane_geom.rows.as_usize() <= MIN_TERMINAL_HEIGHT {
+            if all_stacked_pane_positions.len() == 1 {
+                // we made this pane a stack of its own, no need to leave it this way
+                let mut panes = self.panes.borrow_mut();
+                let pane = panes.get_mut(id).with_context(err_context)?;
+                let mut pane_geom = pane.position_and_size();
+                pane_geom.is_stacked = false;
+                pane.set_geom(pane_geom);
+            }
+            return Err(anyhow!("Not enough room for another pane in stack"));
+        }
+        self.make_room_in_stack(id)
+    }
+    pub fn remove_pane_from_stack(&mut self, id: &PaneId) -> Result<PaneGeom> {
+        // the stack gives up the bottom half of its space to the pane, whose new geometry is
+        // returned
+        let err_context = || format!("Failed to remove pane {:?} from stack", id);
+        let all_stacked_pane_positions = self.positions_in_stack(id).with_context(err_context)?;
+        let stack_geom = self
+            .position_and_size_of_stack(id)
+            .with_context(err_context)?;
+        let stack_percent = stack_geom.rows.as_percent().with_context(err_context)?;
+        let id_remaining_in_stack = all_stacked_pane_positions
+            .iter()
+            .map(|(pid, _p)| *pid)
+            .find(|pid| pid != id);
+        let id_remaining_in_stack = match id_remaining_in_stack {
+            Some(id_remaining_in_stack) => id_remaining_in_stack,
+            None => {
+                // this pane is the only one in its stack
+                let mut pane_geom = stack_geom;
+                pane_geom.is_stacked = false;
+                return Ok(pane_geom);
+            },
+        };
+        let panes_remaining_in_stack = all_stacked_pane_positions.len() - 1;
+        let rows_for_pane = stack_geom.rows.as_usize() / 2;
+        let rows_for_stack = stack_geom.rows.as_usize() - rows_for_pane;
+        if rows_for_pane < MIN_TERMINAL_HEIGHT
+            || rows_for_stack < MIN_TERMINAL_HEIGHT + panes_remaining_in_stack - 1
+        {
+            return Err(anyhow!("Not enough room to remove pane from stack"));
+        }
+        self.fill_space_over_pane_in_stack(id)
+            .with_context(err_context)?;
+        self.panes.borrow_mut().remove(id);
+
+        let mut new_stack_geom = stack_geom;
+        new_stack_geom.rows = Dimension::percent(stack_percent / 2.0);
+        new_stack_geom.rows.set_inner(rows_for_stack);
+        self.resize_panes_in_stack(&id_remaining_in_stack, new_stack_geom)
+            .with_context(err_context)?;
+        let flexible_pane_id = self
+            .flexible_pane_id_in_stack(&id_remaining_in_stack)
+            .with_context(err_context)?;
+        let mut panes = self.panes.borrow_mut();
+        let flexible_pane = panes.get_mut(&flexible_pane_id).with_context(err_context)?;
+        let mut flexible_pane_geom = flexible_pane.position_and_size();
+        let flexible_pane_rows = flexible_pane_geom.rows.as_usize();
+        flexible_pane_geom.rows = Dimension::percent(stack_percent / 2.0);
+        flexible_pane_geom.rows.set_inner(flexible_pane_rows);
+        if panes_remaining_in_stack == 1 {
+            flexible_pane_geom.is_stacked = false;
+        }
+        flexible_pane.set_geom(flexible_pane_geom);
+
+        let mut pane_geom = stack_geom;
+        pane_geom.y = stack_geom.y + rows_for_stack;
+        pane_geom.rows = Dimension::percent(stack_percent / 2.0);
+        pane_geom.rows.set_inner(rows_for_pane);
+        pane_geom.is_stacked = false;
+        Ok(pane_geom)
+    }
+    fn make_room_in_stack(&mut self, id_in_stack: &PaneId) -> Result<PaneGeom> {
+        let err_context = || format!("Failed to add pane to stack");
+        self.make_lowest_pane_in_stack_flexible(id_in_stack)?;
+        let all_stacked_pane_positions = self.positions_in_stack(id_in_stack)?;
+        let position_of_flexible_pane =
+            self.position_of_flexible_pane(&all_stacked_pane_positions)?;
+        let (flexible_pane_id, mut flexible_pane_geom) = *all_stacked_pane_positions
+            .iter()
+            .nth(position_of_flexible_pane)
+            .with_context(err_context)?;
+        let mut position_for_new_pane = flexible_pane_geom.clone();
+        position_for_new_pane
+            .rows
+            .set_inner(position_for_new_pane.rows.as_usize() - 1);
+        position_for_new_pane.y = position_for_new_pane.y + 1;
+        flexible_pane_geom.rows = Dimension::fixed(1);
+        self.panes
+            .borrow_mut()
+            .get_mut(&flexible_pane_id)
+            .with_context(err_context)?
+            .set_geom(flexible_pane_geom);
+        Ok(position_for_new_pane)
+    }
     fn get_all_stacks(&self) -> Result<Vec<Vec<(PaneId, PaneGeom)>>> {
         let err_context = || "Failed to get positions in stack";
         let panes = self.panes.borrow();
@@ -1,9 +1,11 @@
 use super::super::Grid;
 use crate::panes::grid::SixelImageStore;
+use crate::panes::kitty_graphics::KittyImageStore;
 use crate::panes::link_handler::LinkHandler;
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 use zellij_utils::{
     data::{Palette, Style},
@@ -3862,3 +3864,957 @@ fn text_ui_component_with_coordinates() {
     }
     assert_snapshot!(format!("{:?}", grid));
 }
+
+#[test]
+fn semantic_prompt_marks_are_recorded_on_canonical_rows() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    // the command wraps to a second line, the marks should be placed on the first one
+    let content = "\u{1b}]133;A\u{1b}\\$ \u{1b}]133;B\u{1b}\\a-command-longer-than-the-line\n\r\u{1b}]133;C\u{1b}\\output\n\r\u{1b}]133;D;1\u{1b}\\";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let prompt_row_marks = grid.viewport[0].semantic_prompt_marks;
+    assert!(prompt_row_marks.prompt_start);
+    assert!(prompt_row_marks.command_start);
+    assert!(grid.viewport[1].semantic_prompt_marks.is_empty());
+    assert!(grid.viewport[2].semantic_prompt_marks.output_start);
+    assert!(grid.viewport[3].semantic_prompt_marks.command_end);
+    assert_eq!(grid.viewport[3].semantic_prompt_marks.exit_code, Some(1));
+}
+
+#[test]
+fn scroll_to_previous_and_next_prompt() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let mut content = String::new();
+    for i in 0..3 {
+        content.push_str(&format!(
+            "\u{1b}]133;A\u{1b}\\$ \u{1b}]133;B\u{1b}\\command {}\n\r\u{1b}]133;C\u{1b}\\",
+            i
+        ));
+        for j in 0..15 {
+            content.push_str(&format!("output {} of command {}\n\r", j, i));
+        }
+        content.push_str("\u{1b}]133;D;0\u{1b}\\");
+    }
+    content.push_str("\u{1b}]133;A\u{1b}\\$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.scroll_to_previous_prompt();
+    assert_snapshot!("scroll_to_previous_prompt_once", format!("{:?}", grid));
+    grid.scroll_to_previous_prompt();
+    assert_snapshot!("scroll_to_previous_prompt_twice", format!("{:?}", grid));
+    grid.scroll_to_next_prompt();
+    assert_snapshot!("scroll_to_next_prompt", format!("{:?}", grid));
+}
+
+#[test]
+fn scroll_to_next_prompt_over_wrapped_lines() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let mut content = String::new();
+    for i in 0..3 {
+        content.push_str(&format!(
+            "\u{1b}]133;A\u{1b}\\$ \u{1b}]133;B\u{1b}\\command {}\n\r\u{1b}]133;C\u{1b}\\",
+            i
+        ));
+        for j in 0..6 {
+            // each of these wraps into 3 rows
+            content.push_str(&format!(
+                "{:-<120}\n\r",
+                format!("output {} of command {}", j, i)
+            ));
+        }
+        content.push_str("\u{1b}]133;D;0\u{1b}\\");
+    }
+    content.push_str("\u{1b}]133;A\u{1b}\\$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.scroll_to_previous_prompt();
+    grid.scroll_to_previous_prompt();
+    grid.scroll_to_previous_prompt();
+    assert!(grid.viewport[0].semantic_prompt_marks.prompt_start);
+    // leaves the rest of a wrapped line below the viewport as a single row
+    grid.move_viewport_down(1);
+    grid.scroll_to_next_prompt();
+    assert!(
+        grid.viewport[0].semantic_prompt_marks.prompt_start,
+        "scrolled exactly to the next prompt"
+    );
+    assert_eq!(grid.dump_screen(false).lines().next(), Some("$ command 1"));
+}
+
+#[test]
+fn select_last_command_output() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let mut content = String::new();
+    for i in 0..3 {
+        content.push_str(&format!(
+            "\u{1b}]133;A\u{1b}\\$ \u{1b}]133;B\u{1b}\\command {}\n\r\u{1b}]133;C\u{1b}\\",
+            i
+        ));
+        for j in 0..12 {
+            content.push_str(&format!("output {} of command {}\n\r", j, i));
+        }
+        content.push_str(&format!("\u{1b}]133;D;{}\u{1b}\\", i));
+    }
+    content.push_str("\u{1b}]133;A\u{1b}\\$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.last_command_exit_code, Some(2));
+    let expected_output = |i| {
+        (0..12)
+            .map(|j| format!("output {} of command {}", j, i))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    assert!(grid.select_command_output());
+    assert_eq!(grid.get_selected_text(), Some(expected_output(2)));
+
+    // when scrolled, the output of the command at the top of the viewport is selected
+    grid.scroll_to_previous_prompt();
+    grid.scroll_to_previous_prompt();
+    assert!(grid.select_command_output());
+    assert_eq!(grid.get_selected_text(), Some(expected_output(1)));
+    assert_eq!(grid.selected_command_exit_code, Some(1));
+    assert_eq!(
+        grid.last_command_exit_code,
+        Some(2),
+        "the exit code of the most recent command is kept"
+    );
+}
+
+#[test]
+fn select_first_line_of_scrollback() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    for i in 0..15 {
+        for byte in format!("line {}\n\r", i).as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    let first_line = -(grid.lines_above.len() as i32);
+    grid.start_selection(&Position::new(first_line, 0));
+    grid.end_selection(&Position::new(first_line + 1, 6));
+    assert_eq!(
+        grid.get_selected_text(),
+        Some("line 0\nline 1".to_owned()),
+        "the first line of the scrollback is selected"
+    );
+}
+
+#[test]
+fn osc_7_reports_current_working_directory() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}]7;file://some-host/home/user/my%20project;v2\u{7}";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let expected_cwd = Some(PathBuf::from("/home/user/my project;v2"));
+    assert_eq!(grid.cwd, expected_cwd);
+    assert_eq!(grid.pending_cwd_update.take(), expected_cwd);
+
+    // urls with other schemes are ignored
+    let content = "\u{1b}]7;kitty-shell-cwd://some-host/tmp\u{1b}\\";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.cwd, expected_cwd);
+    assert_eq!(grid.pending_cwd_update, None);
+}
+
+#[test]
+fn osc_9_and_osc_777_notifications() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}]9;Build finished; 0 errors\u{7}\
+                   \u{1b}]9;4;1;50\u{7}\
+                   \u{1b}]777;notify;Tests;All tests passed\u{1b}\\\
+                   \u{1b}]777;notify;Done\u{7}";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.pending_notifications,
+        vec![
+            (None, "Build finished; 0 errors".to_owned()),
+            (Some("Tests".to_owned()), "All tests passed".to_owned()),
+            (Some("Done".to_owned()), String::new()),
+        ]
+    );
+}
+
+fn kitty_graphics_grid(
+    rows: usize,
+    columns: usize,
+    character_cell_size: Option<SizeInPixels>,
+    sixel_image_store: Rc<RefCell<SixelImageStore>>,
+) -> Grid {
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(character_cell_size)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    )
+}
+
+fn feed_kitty_graphics_grid(grid: &mut Grid, vte_parser: &mut vte::Parser, content: &str) {
+    for byte in content.as_bytes() {
+        vte_parser.advance(grid, *byte);
+        grid.handle_apc_byte(*byte);
+    }
+}
+
+#[test]
+pub fn kitty_graphics_image_is_transmitted_and_placed() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    // a 2x2 red RGBA image scaled to 4 columns and 2 rows
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "before\n\r\u{1b}_Ga=T,f=32,s=2,v=2,c=4,r=2,i=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\after",
+    );
+    assert_snapshot!(format!("{:?}", grid));
+    assert_eq!(sixel_image_store.borrow().image_count(), 1);
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec!["\u{1b}_Gi=1;OK\u{1b}\\".as_bytes().to_vec()]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_png_image_transmitted_in_chunks() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=100,I=7,q=1,m=1;iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAYAAABytg0k\u{1b}\\\
+         \u{1b}_Gm=0;AAAAEklEQVR4nGP4z8DwHwSZGKAAADTvA/8QRCjSAAAAAElFTkSuQmCC\u{1b}\\",
+    );
+    assert_eq!(
+        sixel_image_store.borrow().image_count(),
+        0,
+        "image is only transmitted"
+    );
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,I=7,c=2,r=3,C=1\u{1b}\\",
+    );
+    assert_snapshot!(format!("{:?}", grid));
+    assert_eq!(sixel_image_store.borrow().image_count(), 1);
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec!["\u{1b}_Gi=2147483648,I=7;OK\u{1b}\\".as_bytes().to_vec()],
+        "only the placement is answered, the transmission was quiet"
+    );
+}
+
+#[test]
+pub fn kitty_graphics_images_are_deleted() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=T,f=32,s=2,v=2,c=2,r=1,i=1,q=2;/wAA//8AAP//AAD//wAA/w==\u{1b}\\\
+         \u{1b}[5;1H\u{1b}_Ga=p,i=1,c=2,r=1,q=2\u{1b}\\",
+    );
+    assert_eq!(sixel_image_store.borrow().image_count(), 2);
+    // delete the placement under the cursor of the first image, keeping its data
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}[1;1H\u{1b}_Ga=d,d=c\u{1b}\\",
+    );
+    let _ = grid.read_changes(0, 0); // we do this because this is where the images are reaped
+    assert_snapshot!(format!("{:?}", grid));
+    assert_eq!(sixel_image_store.borrow().image_count(), 1);
+    // delete all placements of the image and free its data
+    feed_kitty_graphics_grid(&mut grid, &mut vte_parser, "\u{1b}_Ga=d,d=I,i=1\u{1b}\\");
+    let _ = grid.read_changes(0, 0);
+    assert_eq!(sixel_image_store.borrow().image_count(), 0);
+    feed_kitty_graphics_grid(&mut grid, &mut vte_parser, "\u{1b}_Ga=p,i=1\u{1b}\\");
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;OK\u{1b}\\".as_bytes().to_vec(),
+            "\u{1b}_Gi=1;ENOENT:image not found\u{1b}\\"
+                .as_bytes()
+                .to_vec()
+        ]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_images_scroll_with_the_grid() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}[3;5H\u{1b}_Ga=T,f=32,s=2,v=2,c=3,r=2;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    for line_number in 0..8 {
+        feed_kitty_graphics_grid(
+            &mut grid,
+            &mut vte_parser,
+            &format!("\n\rline {}", line_number),
+        );
+    }
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+pub fn kitty_graphics_query_is_answered() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\u{1b}\\",
+    );
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec!["\u{1b}_Gi=31;OK\u{1b}\\".as_bytes().to_vec()]
+    );
+    assert_eq!(sixel_image_store.borrow().image_count(), 0);
+
+    let mut grid_without_pixel_size = kitty_graphics_grid(10, 20, None, sixel_image_store);
+    feed_kitty_graphics_grid(
+        &mut grid_without_pixel_size,
+        &mut vte_parser,
+        "\u{1b}_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\u{1b}\\",
+    );
+    assert_eq!(
+        grid_without_pixel_size.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=31;ENOTSUP:the pixel size of the terminal is unknown\u{1b}\\"
+                .as_bytes()
+                .to_vec()
+        ]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_files_are_only_read_from_the_temporary_directory() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store);
+    let file_outside_of_temp_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let missing_file = std::env::temp_dir().join("tty-graphics-protocol-missing-file");
+    let mut temporary_file = tempfile::Builder::new()
+        .prefix("tty-graphics-protocol")
+        .tempfile()
+        .unwrap();
+    std::io::Write::write_all(&mut temporary_file, &[255, 0, 0]).unwrap();
+    let temporary_file_path = temporary_file.into_temp_path().keep().unwrap();
+    for (image_id, transmission_medium, path) in [
+        (1, 'f', &file_outside_of_temp_dir),
+        (2, 't', &file_outside_of_temp_dir),
+        (3, 't', &missing_file),
+        (4, 't', &temporary_file_path),
+    ] {
+        feed_kitty_graphics_grid(
+            &mut grid,
+            &mut vte_parser,
+            &format!(
+                "\u{1b}_Ga=t,f=24,s=1,v=1,t={},i={};{}\u{1b}\\",
+                transmission_medium,
+                image_id,
+                base64::encode(path.to_string_lossy().as_bytes())
+            ),
+        );
+    }
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;EINVAL:unsupported transmission medium\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=2;EBADF:failed to read image file\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=3;EBADF:failed to read image file\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=4;OK\u{1b}\\".as_bytes().to_vec(),
+        ]
+    );
+    assert!(file_outside_of_temp_dir.exists());
+    assert!(
+        !temporary_file_path.exists(),
+        "temporary files meant for us are deleted"
+    );
+}
+
+#[test]
+pub fn kitty_graphics_oldest_images_are_evicted_over_the_storage_quota() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    // room for two 2x2 RGBA images in the whole session
+    sixel_image_store.borrow_mut().kitty_images = KittyImageStore::new(2 * 2 * 4 * 2);
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    let mut other_grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store);
+    for image_id in 1..=2 {
+        feed_kitty_graphics_grid(
+            &mut grid,
+            &mut vte_parser,
+            &format!(
+                "\u{1b}_Ga=t,f=32,s=2,v=2,i={},q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+                image_id
+            ),
+        );
+    }
+    // an image transmitted in another pane counts towards the same quota
+    feed_kitty_graphics_grid(
+        &mut other_grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=3,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    for image_id in 1..=2 {
+        feed_kitty_graphics_grid(
+            &mut grid,
+            &mut vte_parser,
+            &format!("\u{1b}_Ga=p,i={},C=1\u{1b}\\", image_id),
+        );
+    }
+    feed_kitty_graphics_grid(
+        &mut other_grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=3,C=1\u{1b}\\",
+    );
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;ENOENT:image not found\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=2;OK\u{1b}\\".as_bytes().to_vec(),
+        ]
+    );
+    assert_eq!(
+        other_grid.pending_messages_to_pty,
+        vec!["\u{1b}_Gi=3;OK\u{1b}\\".as_bytes().to_vec()]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_images_are_freed_with_their_grid() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    sixel_image_store.borrow_mut().kitty_images = KittyImageStore::new(2 * 2 * 4);
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=1,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    drop(grid);
+    let mut other_grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store);
+    feed_kitty_graphics_grid(
+        &mut other_grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=2,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(
+        &mut other_grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=3,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(&mut other_grid, &mut vte_parser, "\u{1b}_Ga=p,i=2\u{1b}\\");
+    feed_kitty_graphics_grid(&mut other_grid, &mut vte_parser, "\u{1b}_Ga=p,i=3\u{1b}\\");
+    // the quota only has room for one image, the one of the closed grid must not take it up
+    assert_eq!(
+        other_grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=2;ENOENT:image not found\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=3;OK\u{1b}\\".as_bytes().to_vec(),
+        ]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_overflowing_dimensions_are_rejected() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    // 2^32 x 2^32 pixels, which wraps around to 0 when multiplied
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=T,f=24,s=4294967296,v=4294967296,i=1;AAAA\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=2,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    // columns and rows that overflow once scaled to pixels, or once multiplied with each other
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=2,c=2305843009213693952\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=2,r=1152921504606846976\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=2,c=4294967296,r=4294967296\u{1b}\\",
+    );
+    assert_eq!(sixel_image_store.borrow().image_count(), 0);
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;EINVAL:invalid image dimensions\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=2;EINVAL:invalid placement dimensions\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=2;EINVAL:invalid placement dimensions\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=2;EINVAL:invalid placement dimensions\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+        ]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_invalid_png_images_are_rejected() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    // a PNG signature followed by a truncated header chunk, then data that is not a PNG at all
+    for payload in ["iVBORw0KGgoAAAANSUhEUgAAAAE=", "AAAA"] {
+        feed_kitty_graphics_grid(
+            &mut grid,
+            &mut vte_parser,
+            &format!("\u{1b}_Ga=T,f=100,i=1;{}\u{1b}\\", payload),
+        );
+    }
+    assert_eq!(sixel_image_store.borrow().image_count(), 0);
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;EBADPNG:failed to decode PNG image\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=1;EBADPNG:failed to decode PNG image\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+        ]
+    );
+}
+
+#[test]
+pub fn kitty_graphics_offsets_outside_of_a_cell_are_rejected() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let character_cell_size = Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    });
+    let mut grid = kitty_graphics_grid(10, 20, character_cell_size, sixel_image_store.clone());
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=t,f=32,s=2,v=2,i=1,q=1;/wAA//8AAP//AAD//wAA/w==\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(&mut grid, &mut vte_parser, "\u{1b}_Ga=p,i=1,X=8\u{1b}\\");
+    feed_kitty_graphics_grid(&mut grid, &mut vte_parser, "\u{1b}_Ga=p,i=1,Y=21\u{1b}\\");
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=1,X=18446744073709551615,Y=18446744073709551615\u{1b}\\",
+    );
+    feed_kitty_graphics_grid(
+        &mut grid,
+        &mut vte_parser,
+        "\u{1b}_Ga=p,i=1,X=7,Y=20\u{1b}\\",
+    );
+    assert_eq!(sixel_image_store.borrow().image_count(), 1);
+    assert_eq!(grid.cursor.y, 1, "cursor moved below the offset image");
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec![
+            "\u{1b}_Gi=1;EINVAL:the offset is larger than a character cell\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=1;EINVAL:the offset is larger than a character cell\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=1;EINVAL:the offset is larger than a character cell\u{1b}\\"
+                .as_bytes()
+                .to_vec(),
+            "\u{1b}_Gi=1;OK\u{1b}\\".as_bytes().to_vec(),
+        ]
+    );
+}
+
+fn kitty_keyboard_grid(explicitly_disable_kitty_keyboard_protocol: bool) -> Grid {
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(SixelImageStore::default())),
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    )
+}
+
+fn kitty_keyboard_query(grid: &mut Grid, vte_parser: &mut vte::Parser, content: &str) -> String {
+    for byte in content.as_bytes().iter().chain("\u{1b}[?u".as_bytes()) {
+        vte_parser.advance(grid, *byte);
+    }
+    String::from_utf8(grid.pending_messages_to_pty.pop().unwrap()).unwrap()
+}
+
+#[test]
+pub fn kitty_keyboard_flags_are_pushed_set_and_popped() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = kitty_keyboard_grid(false);
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, ""),
+        "\u{1b}[?0u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>1u"),
+        "\u{1b}[?1u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>11u"),
+        "\u{1b}[?11u",
+        "push"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[=16;2u"),
+        "\u{1b}[?27u",
+        "set the given flags"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[=8;3u"),
+        "\u{1b}[?19u",
+        "reset the given flags"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[=5u"),
+        "\u{1b}[?5u",
+        "assign all flags"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[<u"),
+        "\u{1b}[?1u",
+        "pop"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>3u\u{1b}[>7u\u{1b}[<3u"),
+        "\u{1b}[?0u",
+        "pop several"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[<u"),
+        "\u{1b}[?0u",
+        "pop an empty stack"
+    );
+}
+
+#[test]
+pub fn kitty_keyboard_flags_of_the_main_and_alternate_screens_are_separate() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = kitty_keyboard_grid(false);
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>1u"),
+        "\u{1b}[?1u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[?1049h"),
+        "\u{1b}[?0u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>31u"),
+        "\u{1b}[?31u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[?1049l"),
+        "\u{1b}[?1u"
+    );
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}c"),
+        "\u{1b}[?0u",
+        "reset"
+    );
+}
+
+#[test]
+pub fn kitty_keyboard_flags_are_ignored_when_explicitly_disabled() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = kitty_keyboard_grid(true);
+    assert_eq!(
+        kitty_keyboard_query(&mut grid, &mut vte_parser, "\u{1b}[>31u\u{1b}[=3u"),
+        "\u{1b}[?0u"
+    );
+}
+
+#[test]
+pub fn compressed_scrollback_is_reflowed_when_resizing() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content: String = (0..300)
+        .map(|i| format!("\u{1b}[3{}mline {:03} of output\u{1b}[m\n\r", i % 8, i))
+        .collect();
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert!(grid.lines_above.compressed_row_count() > 0);
+    let lines_above_before_resize: Vec<String> = grid
+        .lines_above
+        .iter()
+        .map(|row| format!("{:?}", row))
+        .collect();
+    grid.change_size(10, 8);
+    grid.change_size(10, 20);
+    let lines_above_after_resize: Vec<String> = grid
+        .lines_above
+        .iter()
+        .map(|row| format!("{:?}", row))
+        .collect();
+    assert_eq!(lines_above_before_resize, lines_above_after_resize);
+}
@@ -0,0 +1,178 @@
+use super::*;
+use crate::panes::terminal_character::{AnsiCode, DEFAULT_STYLES};
+use std::collections::VecDeque;
+use tempfile::tempdir;
+
+fn styled_row(text: &str, styles: RcCharacterStyles) -> Row {
+    let columns: VecDeque<TerminalCharacter> = text
+        .chars()
+        .map(|character| TerminalCharacter::new_styled(character, styles.clone()))
+        .collect();
+    Row::from_columns(columns)
+}
+
+fn row_text(row: &Row) -> String {
+    row.columns.iter().map(|c| c.character).collect()
+}
+
+#[test]
+fn compressed_row_is_decompressed_with_its_characters_and_styles() {
+    let red: RcCharacterStyles = DEFAULT_STYLES
+        .foreground(Some(AnsiCode::ColorIndex(1)))
+        .into();
+    let bold: RcCharacterStyles = DEFAULT_STYLES.bold(Some(AnsiCode::On)).into();
+    let mut columns = styled_row("hello ", red.clone()).columns;
+    columns.extend(styled_row("世界", bold.clone()).columns);
+    columns.push_back(TerminalCharacter::new_singlewidth_styled('✔', bold.clone()));
+    columns.extend(styled_row(" world", RcCharacterStyles::default()).columns);
+    let row = Row::from_columns(columns)
+        .canonical()
+        .with_semantic_prompt_marks(SemanticPromptMarks {
+            prompt_start: true,
+            ..Default::default()
+        });
+
+    let decompressed = CompressedRow::new(&row).decompress();
+
+    assert_eq!(row_text(&decompressed), "hello 世界✔ world");
+    assert!(
+        decompressed.columns == row.columns,
+        "characters, widths and styles are preserved"
+    );
+    assert_eq!(decompressed.width(), row.width());
+    assert!(decompressed.is_canonical);
+    assert!(decompressed.semantic_prompt_marks.prompt_start);
+}
+
+#[test]
+fn only_rows_beyond_the_most_recent_ones_are_compressed() {
+    let mut scrollback = Scrollback::new();
+    for i in 0..UNCOMPRESSED_SCROLLBACK_ROWS + 50 {
+        scrollback.push_back(styled_row(&format!("line {}", i), Default::default()).canonical());
+    }
+    assert_eq!(scrollback.len(), UNCOMPRESSED_SCROLLBACK_ROWS + 50);
+    assert_eq!(scrollback.compressed_row_count(), 50);
+    let texts: Vec<String> = scrollback.iter().map(|row| row_text(&row)).collect();
+    let expected: Vec<String> = (0..UNCOMPRESSED_SCROLLBACK_ROWS + 50)
+        .map(|i| format!("line {}", i))
+        .collect();
+    assert_eq!(texts, expected);
+}
+
+#[test]
+fn rows_keep_their_widths_and_order_when_dropped_and_popped() {
+    let mut scrollback = Scrollback::new();
+    for i in 0..UNCOMPRESSED_SCROLLBACK_ROWS * 2 {
+        scrollback.push_back(styled_row(&"x".repeat(i + 1), Default::default()));
+    }
+    assert_eq!(scrollback.drop_front(), Some(1));
+    assert_eq!(scrollback.widths().next(), Some(2));
+    while scrollback.len() > 1 {
+        scrollback.pop_back();
+    }
+    let last_row = scrollback.back_mut().unwrap();
+    assert_eq!(last_row.width(), 2);
+    assert_eq!(scrollback.compressed_row_count(), 0);
+}
+
+#[test]
+fn rows_spilled_to_disk_are_read_back_with_their_styles() {
+    let folder = tempdir().unwrap();
+    let red: RcCharacterStyles = DEFAULT_STYLES
+        .foreground(Some(AnsiCode::ColorIndex(1)))
+        .into();
+    let mut scrollback = Scrollback::spilling_to(folder.path().to_path_buf());
+    for i in 0..10 {
+        scrollback.push_back(styled_row(&format!("line {}", i), red.clone()).canonical());
+    }
+    for _ in 0..5 {
+        assert!(scrollback.spill_front());
+    }
+    assert_eq!(scrollback.len(), 10);
+    assert_eq!(scrollback.spilled_row_count(), 5);
+    assert_eq!(scrollback.rows_in_memory(), 5);
+    assert_eq!(std::fs::read_dir(folder.path()).unwrap().count(), 1);
+
+    let texts: Vec<String> = scrollback.iter().map(|row| row_text(&row)).collect();
+    let expected: Vec<String> = (0..10).map(|i| format!("line {}", i)).collect();
+    assert_eq!(texts, expected);
+    let spilled_row = scrollback.get(2).unwrap();
+    assert!(spilled_row.is_canonical);
+    assert!(spilled_row.columns == styled_row("line 2", red.clone()).columns);
+    assert_eq!(
+        scrollback.iter().nth(7).map(|row| row_text(&row)),
+        Some(String::from("line 7"))
+    );
+    assert_eq!(scrollback.widths().sum::<usize>(), 60);
+
+    drop(scrollback);
+    assert_eq!(std::fs::read_dir(folder.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn rows_popped_back_from_disk_are_removed_from_it() {
+    let folder = tempdir().unwrap();
+    let mut scrollback = Scrollback::spilling_to(folder.path().to_path_buf());
+    for i in 0..4 {
+        scrollback.push_back(styled_row(&format!("line {}", i), Default::default()));
+        assert!(scrollback.spill_front());
+    }
+    let file_size = |folder: &std::path::Path| {
+        std::fs::read_dir(folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum::<u64>()
+    };
+    let size_with_four_rows = file_size(folder.path());
+    assert_eq!(
+        scrollback.pop_back().map(|row| row_text(&row)),
+        Some(String::from("line 3"))
+    );
+    assert!(file_size(folder.path()) < size_with_four_rows);
+    assert_eq!(
+        scrollback.back_mut().map(|row| row_text(row)),
+        Some(String::from("line 2"))
+    );
+    assert_eq!(scrollback.spilled_row_count(), 2);
+    assert_eq!(scrollback.rows_in_memory(), 1);
+    scrollback.clear();
+    assert!(scrollback.is_empty());
+    assert_eq!(file_size(folder.path()), 0);
+}
+
+#[test]
+fn rows_are_not_spilled_unless_spilling_to_disk() {
+    let mut scrollback = Scrollback::new();
+    scrollback.push_back(styled_row("line", Default::default()));
+    assert!(!scrollback.spill_front());
+    assert_eq!(scrollback.rows_in_memory(), 1);
+}
+
+#[test]
+fn spilled_rows_are_length_prefixed_in_the_file() {
+    let folder = tempdir().unwrap();
+    let mut scrollback = Scrollback::spilling_to(folder.path().to_path_buf());
+    for i in 0..3 {
+        scrollback.push_back(styled_row(&format!("line {}", i), Default::default()));
+        assert!(scrollback.spill_front());
+    }
+    // the file can be read without knowing the offsets of its rows (eg. after a crash)
+    let file_path = std::fs::read_dir(folder.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let contents = std::fs::read(file_path).unwrap();
+    let mut remaining = &contents[..];
+    let mut texts = vec![];
+    while !remaining.is_empty() {
+        let (length, rest) = remaining.split_at(LENGTH_PREFIX_SIZE);
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+        let (serialized_row, rest) = rest.split_at(length);
+        let serialized_row: SerializedRow = rmp_serde::from_slice(serialized_row).unwrap();
+        texts.push(serialized_row.text);
+        remaining = rest;
+    }
+    assert_eq!(texts, vec!["line 0", "line 1", "line 2"]);
+}
@@ -421,3 +421,87 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn searching_with_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("t[aeiou]rt[aeiou]r");
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_tortor_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.search_up();
+    assert_snapshot!(
+        "grid_copy_search_cursor_at_bottom",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.search_up();
+    assert_snapshot!(
+        "grid_copy_search_cursor_at_second",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_across_line_wrap() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term(r"aliquam\s+sem\s+fringilla");
+    terminal_pane.toggle_search_regex();
+    terminal_pane.grid.change_size(30, 60);
+    assert_snapshot!(
+        "grid_copy_multiline_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.grid.change_size(40, 4);
+    assert_snapshot!(
+        "grid_copy_multiline_highlighted_narrow",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.search_up();
+    assert_snapshot!(
+        "grid_copy_multiline_selected_narrow",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_whole_word_case_insensitive() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("qu?am");
+    terminal_pane.toggle_search_regex();
+    terminal_pane.toggle_search_whole_words();
+    assert_snapshot!(
+        "grid_copy_quam_whole_word_only",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.toggle_search_case_sensitivity();
+    assert_snapshot!(
+        "grid_copy_quam_whole_word_case_insensitive",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.toggle_search_whole_words();
+    assert_snapshot!(
+        "grid_copy_quam_insensitive_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_is_toggled_on_the_current_search_term() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tort.r");
+    let no_literal_hits = format!("{:?}", terminal_pane.grid);
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_tortor_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+    terminal_pane.toggle_search_regex();
+    assert_eq!(format!("{:?}", terminal_pane.grid), no_literal_hits);
+
+    // an incomplete regex has no hits rather than failing
+    terminal_pane.update_search_term("[");
+    terminal_pane.toggle_search_regex();
+    assert!(terminal_pane.grid.search_results.selections.is_empty());
+}
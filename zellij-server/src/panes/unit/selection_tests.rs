@@ -43,6 +43,7 @@ fn contains() {
         start: Position::new(10, 5),
         end: Position::new(40, 20),
         active: false,
+        whole_rows: false,
     };
 
     let test_cases = vec![
@@ -93,6 +94,7 @@ fn sorted() {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
         active: false,
+        whole_rows: false,
     };
     let sorted_selection = selection.sorted();
     assert_eq!(selection.start, sorted_selection.start);
@@ -102,6 +104,7 @@ fn sorted() {
         start: Position::new(10, 2),
         end: Position::new(1, 1),
         active: false,
+        whole_rows: false,
     };
     let sorted_selection = selection.sorted();
     assert_eq!(selection.end, sorted_selection.start);
@@ -114,6 +117,7 @@ fn line_indices() {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
         active: false,
+        whole_rows: false,
     };
 
     assert_eq!(selection.line_indices(), (1..=10))
@@ -127,6 +131,7 @@ fn move_up_inactive() {
         start,
         end,
         active: false,
+        whole_rows: false,
     };
 
     inactive_selection.move_up(2);
@@ -145,6 +150,7 @@ fn move_up_active() {
         start,
         end,
         active: true,
+        whole_rows: false,
     };
 
     inactive_selection.move_up(2);
@@ -160,6 +166,7 @@ fn move_down_inactive() {
         start,
         end,
         active: false,
+        whole_rows: false,
     };
 
     inactive_selection.move_down(2);
@@ -178,6 +185,7 @@ fn move_down_active() {
         start,
         end,
         active: true,
+        whole_rows: false,
     };
 
     inactive_selection.move_down(2);
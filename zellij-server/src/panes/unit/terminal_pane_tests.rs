@@ -1,7 +1,7 @@
 use super::super::TerminalPane;
 use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
-use crate::tab::Pane;
+use crate::tab::{AdjustedInput, Pane};
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -764,3 +764,77 @@ pub fn frameless_pane_position_is_on_frame() {
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 130)));
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 131)));
 }
+
+#[test]
+pub fn kitty_key_events_are_adjusted_to_the_enhancements_requested_by_the_pane() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_colors = Rc::new(RefCell::new(Palette::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        terminal_emulator_colors,
+        terminal_emulator_color_codes,
+        None,
+        None,
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    ); // 0 is the pane index
+    let adjust_input = |terminal_pane: &mut TerminalPane, raw_input_bytes: &str| match terminal_pane
+        .adjust_input_to_terminal(&None, raw_input_bytes.as_bytes().to_vec(), true)
+    {
+        Some(AdjustedInput::WriteBytesToTerminal(bytes)) => Some(String::from_utf8(bytes).unwrap()),
+        _ => None,
+    };
+    // the host terminal reports these with all the enhancements
+    let press = "\u{1b}[97:65;2;65u";
+    let release = "\u{1b}[97:65;2:3u";
+    let ctrl_release = "\u{1b}[97;5:3u";
+
+    assert_eq!(
+        adjust_input(&mut terminal_pane, press),
+        Some("A".to_owned())
+    );
+    assert_eq!(adjust_input(&mut terminal_pane, release), None);
+
+    terminal_pane.handle_pty_bytes("\u{1b}[>3u".as_bytes().to_vec());
+    assert_eq!(
+        adjust_input(&mut terminal_pane, press),
+        Some("A".to_owned())
+    );
+    assert_eq!(
+        adjust_input(&mut terminal_pane, ctrl_release),
+        Some("\u{1b}[97;5:3u".to_owned())
+    );
+
+    terminal_pane.handle_pty_bytes("\u{1b}[>31u".as_bytes().to_vec());
+    assert_eq!(
+        adjust_input(&mut terminal_pane, press),
+        Some(press.to_owned())
+    );
+    assert_eq!(
+        adjust_input(&mut terminal_pane, release),
+        Some(release.to_owned())
+    );
+
+    terminal_pane.handle_pty_bytes("\u{1b}[<2u".as_bytes().to_vec());
+    assert_eq!(adjust_input(&mut terminal_pane, ctrl_release), None);
+}
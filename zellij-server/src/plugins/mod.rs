@@ -105,7 +105,7 @@ pub enum PluginInstruction {
         Option<PathBuf>,
     ),
     DumpLayout(SessionLayoutMetadata, ClientId),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    ListClientsMetadata(SessionLayoutMetadata, bool, ClientId), // bool - output as json
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
     CliPipe {
@@ -491,10 +491,15 @@ pub(crate) fn plugin_thread_main(
                     client_id,
                 )));
             },
-            PluginInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PluginInstruction::ListClientsMetadata(
+                mut session_layout_metadata,
+                json,
+                client_id,
+            ) => {
                 populate_session_layout_metadata(&mut session_layout_metadata, &wasm_bridge);
                 drop(bus.senders.send_to_pty(PtyInstruction::ListClientsMetadata(
                     session_layout_metadata,
+                    json,
                     client_id,
                 )));
             },
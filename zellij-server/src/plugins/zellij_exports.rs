@@ -19,7 +19,7 @@ use wasmer::{imports, AsStoreMut, Function, FunctionEnv, FunctionEnvMut, Imports
 use wasmer_wasi::WasiEnv;
 use zellij_utils::data::{
     CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, LayoutInfo, MessageToPlugin,
-    PermissionStatus, PermissionType, PluginPermission,
+    PaneId as ZellijUtilsPaneId, PermissionStatus, PermissionType, PluginPermission,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::{
@@ -39,7 +39,7 @@ use zellij_utils::{
     input::{
         actions::Action,
         command::{RunCommand, RunCommandAction, TerminalAction},
-        layout::{Layout, RunPluginOrAlias},
+        layout::{Layout, LayoutParseOptions, RunPluginOrAlias},
         plugins::PluginType,
     },
     plugin_api::{
@@ -157,6 +157,9 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                     PluginCommand::NewTabsWithLayoutInfo(layout_info) => {
                         new_tabs_with_layout_info(env, layout_info)?
                     },
+                    PluginCommand::NewTabsWithLayoutAndVars(raw_layout, layout_vars) => {
+                        new_tabs_with_layout_and_vars(env, &raw_layout, layout_vars)?
+                    },
                     PluginCommand::NewTab => new_tab(env),
                     PluginCommand::GoToNextTab => go_to_next_tab(env),
                     PluginCommand::GoToPreviousTab => go_to_previous_tab(env),
@@ -207,6 +210,15 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                     PluginCommand::ClosePluginPane(plugin_pane_id) => {
                         close_plugin_pane(env, plugin_pane_id)
                     },
+                    PluginCommand::MovePaneToTab(pane_id, tab_position, tab_name, should_float) => {
+                        move_pane_to_tab(env, pane_id, tab_position, tab_name, should_float)
+                    },
+                    PluginCommand::ChangeFloatingPanesCoordinates(pane_ids_and_coordinates) => {
+                        change_floating_panes_coordinates(env, pane_ids_and_coordinates)
+                    },
+                    PluginCommand::StackPane(direction) => stack_pane(env, direction),
+                    PluginCommand::UnstackPane => unstack_pane(env),
+                    PluginCommand::StackAllPanes => stack_all_panes(env),
                     PluginCommand::FocusTerminalPane(terminal_pane_id, should_float_if_hidden) => {
                         focus_terminal_pane(env, terminal_pane_id, should_float_if_hidden)
                     },
@@ -580,6 +592,7 @@ fn open_command_pane(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
         .cwd
         .map(|cwd| env.plugin_env.plugin_cwd.join(cwd));
     let args = command_to_run.args;
+    let env_variables = command_to_run.env;
     let direction = None;
     let hold_on_close = true;
     let hold_on_start = false;
@@ -591,6 +604,7 @@ fn open_command_pane(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
         direction,
         hold_on_close,
         hold_on_start,
+        env: env_variables,
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -607,6 +621,7 @@ fn open_command_pane_floating(
         .cwd
         .map(|cwd| env.plugin_env.plugin_cwd.join(cwd));
     let args = command_to_run.args;
+    let env_variables = command_to_run.env;
     let direction = None;
     let hold_on_close = true;
     let hold_on_start = false;
@@ -618,6 +633,7 @@ fn open_command_pane_floating(
         direction,
         hold_on_close,
         hold_on_start,
+        env: env_variables,
     };
     let action = Action::NewFloatingPane(Some(run_command_action), name, floating_pane_coordinates);
     apply_action!(action, error_msg, env);
@@ -630,6 +646,7 @@ fn open_command_pane_in_place(env: &ForeignFunctionEnv, command_to_run: CommandT
         .cwd
         .map(|cwd| env.plugin_env.plugin_cwd.join(cwd));
     let args = command_to_run.args;
+    let env_variables = command_to_run.env;
     let direction = None;
     let hold_on_close = true;
     let hold_on_start = false;
@@ -641,6 +658,7 @@ fn open_command_pane_in_place(env: &ForeignFunctionEnv, command_to_run: CommandT
         direction,
         hold_on_close,
         hold_on_start,
+        env: env_variables,
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -855,11 +873,35 @@ fn switch_to_mode(env: &ForeignFunctionEnv, input_mode: InputMode) {
 
 fn new_tabs_with_layout(env: &ForeignFunctionEnv, raw_layout: &str) -> Result<()> {
     // TODO: cwd
-    let layout = Layout::from_str(
+    let layout = Layout::from_kdl_with_options(
         &raw_layout,
         format!("Layout from plugin: {}", env.plugin_env.name()),
         None,
+        LayoutParseOptions {
+            layout_dir: env.plugin_env.layout_dir.clone(),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
+    apply_layout(env, layout);
+    Ok(())
+}
+
+fn new_tabs_with_layout_and_vars(
+    env: &ForeignFunctionEnv,
+    raw_layout: &str,
+    layout_vars: BTreeMap<String, String>,
+) -> Result<()> {
+    // TODO: cwd
+    let layout = Layout::from_kdl_with_options(
+        raw_layout,
+        format!("Layout from plugin: {}", env.plugin_env.name()),
         None,
+        LayoutParseOptions {
+            layout_vars,
+            layout_dir: env.plugin_env.layout_dir.clone(),
+            ..Default::default()
+        },
     )
     .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
     apply_layout(env, layout);
@@ -1292,6 +1334,57 @@ fn close_terminal_pane(env: &ForeignFunctionEnv, terminal_pane_id: u32) {
     apply_action!(action, error_msg, env);
 }
 
+fn move_pane_to_tab(
+    env: &ForeignFunctionEnv,
+    pane_id: ZellijUtilsPaneId,
+    tab_position: Option<u32>,
+    tab_name: Option<String>,
+    should_float: bool,
+) {
+    let error_msg = || format!("failed to move pane in plugin {}", env.plugin_env.name());
+    let action = Action::MovePaneToTab(pane_id, tab_position, tab_name, should_float);
+    apply_action!(action, error_msg, env);
+}
+
+fn change_floating_panes_coordinates(
+    env: &ForeignFunctionEnv,
+    pane_ids_and_coordinates: Vec<(ZellijUtilsPaneId, FloatingPaneCoordinates)>,
+) {
+    let error_msg = || {
+        format!(
+            "failed to change floating pane coordinates in plugin {}",
+            env.plugin_env.name()
+        )
+    };
+    for (pane_id, coordinates) in pane_ids_and_coordinates {
+        let action = Action::ChangeFloatingPaneCoordinates(pane_id, coordinates);
+        apply_action!(action, error_msg, env);
+    }
+}
+
+fn stack_pane(env: &ForeignFunctionEnv, direction: Direction) {
+    let error_msg = || format!("failed to stack pane in plugin {}", env.plugin_env.name());
+    let action = Action::StackPane(direction);
+    apply_action!(action, error_msg, env);
+}
+
+fn unstack_pane(env: &ForeignFunctionEnv) {
+    let error_msg = || format!("failed to unstack pane in plugin {}", env.plugin_env.name());
+    let action = Action::UnstackPane;
+    apply_action!(action, error_msg, env);
+}
+
+fn stack_all_panes(env: &ForeignFunctionEnv) {
+    let error_msg = || {
+        format!(
+            "failed to stack all panes in plugin {}",
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::StackAllPanes;
+    apply_action!(action, error_msg, env);
+}
+
 fn close_plugin_pane(env: &ForeignFunctionEnv, plugin_pane_id: u32) {
     let error_msg = || {
         format!(
@@ -1542,6 +1635,7 @@ fn check_command_permission(
         | PluginCommand::SwitchToMode(..)
         | PluginCommand::NewTabsWithLayout(..)
         | PluginCommand::NewTabsWithLayoutInfo(..)
+        | PluginCommand::NewTabsWithLayoutAndVars(..)
         | PluginCommand::NewTab
         | PluginCommand::GoToNextTab
         | PluginCommand::GoToPreviousTab
@@ -1578,6 +1672,11 @@ fn check_command_permission(
         | PluginCommand::GoToTab(..)
         | PluginCommand::CloseTerminalPane(..)
         | PluginCommand::ClosePluginPane(..)
+        | PluginCommand::MovePaneToTab(..)
+        | PluginCommand::ChangeFloatingPanesCoordinates(..)
+        | PluginCommand::StackPane(..)
+        | PluginCommand::UnstackPane
+        | PluginCommand::StackAllPanes
         | PluginCommand::FocusTerminalPane(..)
         | PluginCommand::FocusPluginPane(..)
         | PluginCommand::RenameTerminalPane(..)
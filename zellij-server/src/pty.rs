@@ -45,7 +45,8 @@ pub enum PtyInstruction {
         ClientTabIndexOrPaneId,
     ), // bool (if Some) is
     // should_float, String is an optional pane name
-    OpenInPlaceEditor(PathBuf, Option<usize>, ClientId), // Option<usize> is the optional line number
+    OpenInPlaceEditor(PathBuf, Option<usize>, PaneId, ClientId), // Option<usize> is the optional line
+    // number, PaneId is the pane to replace
     SpawnTerminalVertically(Option<TerminalAction>, Option<String>, ClientId), // String is an
     // optional pane
     // name
@@ -92,7 +93,8 @@ pub enum PtyInstruction {
         Option<PathBuf>, // if Some, will not fill cwd but just forward the message
         Option<FloatingPaneCoordinates>,
     ),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    ListClientsMetadata(SessionLayoutMetadata, bool, ClientId), // bool - output as json
+    ReportTerminalCwd(u32, PathBuf), // terminal_id, cwd reported through OSC 7
     Exit,
 }
 
@@ -116,6 +118,7 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::LogLayoutToHd(..) => PtyContext::LogLayoutToHd,
             PtyInstruction::FillPluginCwd(..) => PtyContext::FillPluginCwd,
             PtyInstruction::ListClientsMetadata(..) => PtyContext::ListClientsMetadata,
+            PtyInstruction::ReportTerminalCwd(..) => PtyContext::ReportTerminalCwd,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -125,6 +128,7 @@ pub(crate) struct Pty {
     pub active_panes: HashMap<ClientId, PaneId>,
     pub bus: Bus<PtyInstruction>,
     pub id_to_child_pid: HashMap<u32, RawFd>, // terminal_id => child raw fd
+    id_to_reported_cwd: HashMap<u32, PathBuf>, // terminal_id => cwd reported through OSC 7
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
     default_editor: Option<PathBuf>,
@@ -300,7 +304,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
-            PtyInstruction::OpenInPlaceEditor(temp_file, line_number, client_id) => {
+            PtyInstruction::OpenInPlaceEditor(
+                temp_file,
+                line_number,
+                replaced_pane_id,
+                client_id,
+            ) => {
                 let err_context =
                     || format!("failed to open in-place editor for client {}", client_id);
 
@@ -313,7 +322,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                             .senders
                             .send_to_screen(ScreenInstruction::OpenInPlaceEditor(
                                 PaneId::Terminal(pid),
-                                client_id,
+                                replaced_pane_id,
                             ))
                             .with_context(err_context)?;
                     },
@@ -634,18 +643,23 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
-            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, json, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                let clients_metadata = if json {
+                    session_layout_metadata.list_clients_metadata_as_json()
+                } else {
+                    Ok(session_layout_metadata.list_clients_metadata())
+                };
+                let instruction = match clients_metadata {
+                    Ok(clients_metadata) => {
+                        ServerInstruction::Log(vec![clients_metadata], client_id)
+                    },
+                    Err(e) => ServerInstruction::LogError(vec![e.to_string()], client_id),
+                };
                 pty.bus
                     .senders
-                    .send_to_server(ServerInstruction::Log(
-                        vec![format!(
-                            "{}",
-                            session_layout_metadata.list_clients_metadata()
-                        )],
-                        client_id,
-                    ))
+                    .send_to_server(instruction)
                     .with_context(err_context)
                     .non_fatal();
             },
@@ -707,6 +721,9 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     floating_pane_coordinates,
                 )?;
             },
+            PtyInstruction::ReportTerminalCwd(terminal_id, cwd) => {
+                pty.report_terminal_cwd(terminal_id, cwd);
+            },
             PtyInstruction::Exit => break,
         }
     }
@@ -723,6 +740,7 @@ impl Pty {
             active_panes: HashMap::new(),
             bus,
             id_to_child_pid: HashMap::new(),
+            id_to_reported_cwd: HashMap::new(),
             debug_to_file,
             task_handles: HashMap::new(),
             default_editor,
@@ -762,41 +780,55 @@ impl Pty {
                     cwd, // note: this might also be filled by the calling function, eg. spawn_terminal
                     hold_on_close: false,
                     hold_on_start: false,
+                    ..Default::default()
                 })
             },
         }
     }
+    fn reported_cwd(&self, terminal_id: &u32) -> Option<PathBuf> {
+        // the shell might report a directory that does not exist locally (eg. over ssh)
+        self.id_to_reported_cwd
+            .get(terminal_id)
+            .filter(|cwd| cwd.is_dir())
+            .cloned()
+    }
+    fn get_terminal_cwd(&self, terminal_id: &u32) -> Option<PathBuf> {
+        self.reported_cwd(terminal_id).or_else(|| {
+            self.id_to_child_pid.get(terminal_id).and_then(|&id| {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .and_then(|input| input.get_cwd(Pid::from_raw(id)))
+            })
+        })
+    }
+    fn get_focused_cwd(&self, client_id: ClientId) -> Option<PathBuf> {
+        self.active_panes
+            .get(&client_id)
+            .and_then(|pane| match pane {
+                PaneId::Plugin(..) => None,
+                PaneId::Terminal(id) => self.get_terminal_cwd(id),
+            })
+    }
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
-                run_command.cwd = self
-                    .active_panes
-                    .get(&client_id)
-                    .and_then(|pane| match pane {
-                        PaneId::Plugin(..) => None,
-                        PaneId::Terminal(id) => self.id_to_child_pid.get(id),
-                    })
-                    .and_then(|&id| {
-                        self.bus
-                            .os_input
-                            .as_ref()
-                            .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                    });
+                run_command.cwd = self.get_focused_cwd(client_id);
             };
         };
     }
     fn fill_cwd_from_pane_id(&self, terminal_action: &mut TerminalAction, pane_id: &u32) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
-                run_command.cwd = self.id_to_child_pid.get(pane_id).and_then(|&id| {
-                    self.bus
-                        .os_input
-                        .as_ref()
-                        .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                });
+                run_command.cwd = self.get_terminal_cwd(pane_id);
             };
         };
     }
+    pub fn report_terminal_cwd(&mut self, terminal_id: u32, cwd: PathBuf) {
+        if self.id_to_child_pid.contains_key(&terminal_id) {
+            self.id_to_reported_cwd.insert(terminal_id, cwd);
+        }
+    }
     pub fn spawn_terminal(
         &mut self,
         terminal_action: Option<TerminalAction>,
@@ -1208,6 +1240,7 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.id_to_reported_cwd.remove(&id);
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -1343,9 +1376,12 @@ impl Pty {
 
         for terminal_id in terminal_ids {
             let process_id = self.id_to_child_pid.get(&terminal_id);
-            let cwd = process_id
-                .as_ref()
-                .and_then(|pid| pids_to_cwds.get(&Pid::from_raw(**pid)));
+            let cwd = self.reported_cwd(&terminal_id).or_else(|| {
+                process_id
+                    .as_ref()
+                    .and_then(|pid| pids_to_cwds.get(&Pid::from_raw(**pid)))
+                    .cloned()
+            });
             let cmd = process_id
                 .as_ref()
                 .and_then(|pid| ppids_to_cmds.get(&format!("{}", pid)));
@@ -1353,7 +1389,7 @@ impl Pty {
                 terminal_ids_to_commands.insert(terminal_id, cmd.clone());
             }
             if let Some(cwd) = cwd {
-                terminal_ids_to_cwds.insert(terminal_id, cwd.clone());
+                terminal_ids_to_cwds.insert(terminal_id, cwd);
             }
         }
         session_layout_metadata.update_default_shell(get_default_shell());
@@ -1377,20 +1413,7 @@ impl Pty {
         // of the pipeline between threads and end up needing to forward this
         _floating_pane_coordinates: Option<FloatingPaneCoordinates>,
     ) -> Result<()> {
-        let get_focused_cwd = || {
-            self.active_panes
-                .get(&client_id)
-                .and_then(|pane| match pane {
-                    PaneId::Plugin(..) => None,
-                    PaneId::Terminal(id) => self.id_to_child_pid.get(id),
-                })
-                .and_then(|&id| {
-                    self.bus
-                        .os_input
-                        .as_ref()
-                        .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                })
-        };
+        let get_focused_cwd = || self.get_focused_cwd(client_id);
 
         let cwd = cwd.or_else(get_focused_cwd);
 
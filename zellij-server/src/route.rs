@@ -22,7 +22,8 @@ use zellij_utils::{
         layout::Layout,
     },
     ipc::{
-        ClientAttributes, ClientToServerMsg, ExitReason, IpcReceiverWithContext, ServerToClientMsg,
+        ClientAttributes, ClientToServerMsg, ClientType, ExitReason, IpcReceiverWithContext,
+        ServerToClientMsg,
     },
 };
 
@@ -182,13 +183,17 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
                 .with_context(err_context)?;
         },
-        Action::DumpLayout => {
+        Action::DumpLayout(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
             };
             senders
-                .send_to_screen(ScreenInstruction::DumpLayout(default_shell, client_id))
+                .send_to_screen(ScreenInstruction::DumpLayout(
+                    default_shell,
+                    json,
+                    client_id,
+                ))
                 .with_context(err_context)?;
         },
         Action::EditScrollback => {
@@ -226,6 +231,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollToTop(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollToPreviousPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToPreviousPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToNextPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToNextPrompt(client_id))
+                .with_context(err_context)?;
+        },
         Action::PageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::PageScrollUp(client_id))
@@ -426,6 +441,36 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::TogglePaneEmbedOrFloating(client_id))
                 .with_context(err_context)?;
         },
+        Action::TogglePanePinned => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePanePinned(client_id))
+                .with_context(err_context)?;
+        },
+        Action::UndoLayoutChange => {
+            senders
+                .send_to_screen(ScreenInstruction::UndoLayoutChange(client_id))
+                .with_context(err_context)?;
+        },
+        Action::RedoLayoutChange => {
+            senders
+                .send_to_screen(ScreenInstruction::RedoLayoutChange(client_id))
+                .with_context(err_context)?;
+        },
+        Action::StackPane(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::StackPane(client_id, direction))
+                .with_context(err_context)?;
+        },
+        Action::UnstackPane => {
+            senders
+                .send_to_screen(ScreenInstruction::UnstackPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::StackAllPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::StackAllPanes(client_id))
+                .with_context(err_context)?;
+        },
         Action::ToggleFloatingPanes => {
             senders
                 .send_to_screen(ScreenInstruction::ToggleFloatingPanes(
@@ -620,6 +665,31 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::Copy(client_id))
                 .with_context(err_context)?;
         },
+        Action::CopyCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetTiling(algorithm) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetTiling(algorithm, client_id))
+                .with_context(err_context)?;
+        },
+        Action::PromoteToMaster => {
+            senders
+                .send_to_screen(ScreenInstruction::PromoteToMaster(client_id))
+                .with_context(err_context)?;
+        },
+        Action::IncreaseMasterCount => {
+            senders
+                .send_to_screen(ScreenInstruction::ChangeMasterCount(1, client_id))
+                .with_context(err_context)?;
+        },
+        Action::DecreaseMasterCount => {
+            senders
+                .send_to_screen(ScreenInstruction::ChangeMasterCount(-1, client_id))
+                .with_context(err_context)?;
+        },
         Action::Confirm => {
             senders
                 .send_to_screen(ScreenInstruction::ConfirmPrompt(client_id))
@@ -630,6 +700,29 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DenyPrompt(client_id))
                 .with_context(err_context)?;
         },
+        Action::ApplyToTarget(target_pane_id, tab_position, action) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetActionTarget(
+                    target_pane_id.map(|p| p.into()),
+                    tab_position,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+            should_break = route_action(
+                *action,
+                client_id,
+                target_pane_id.map(|p| p.into()).or(pane_id),
+                senders.clone(),
+                capabilities,
+                client_attributes,
+                default_shell,
+                default_layout,
+                seen_cli_pipes,
+            )?;
+            senders
+                .send_to_screen(ScreenInstruction::ClearActionTarget(client_id))
+                .with_context(err_context)?;
+        },
         #[allow(clippy::single_match)]
         Action::SkipConfirm(action) => match *action {
             Action::Quit => {
@@ -662,6 +755,7 @@ pub(crate) fn route_action(
                 },
                 SearchOption::WholeWord => ScreenInstruction::SearchToggleWholeWord(client_id),
                 SearchOption::Wrap => ScreenInstruction::SearchToggleWrap(client_id),
+                SearchOption::Regex => ScreenInstruction::SearchToggleRegex(client_id),
             };
             senders
                 .send_to_screen(instruction)
@@ -678,9 +772,9 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::NextSwapLayout(client_id))
                 .with_context(err_context)?;
         },
-        Action::QueryTabNames => {
+        Action::QueryTabNames(json) => {
             senders
-                .send_to_screen(ScreenInstruction::QueryTabNames(client_id))
+                .send_to_screen(ScreenInstruction::QueryTabNames(json, client_id))
                 .with_context(err_context)?;
         },
         Action::NewTiledPluginPane(run_plugin, name, skip_cache, cwd) => {
@@ -825,6 +919,36 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::MovePaneToTab(pane_id, tab_position, tab_name, should_float) => {
+            senders
+                .send_to_screen(ScreenInstruction::MovePaneToTab(
+                    pane_id.into(),
+                    tab_position,
+                    tab_name,
+                    should_float,
+                    default_layout.clone(),
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ChangeFloatingPaneCoordinates(pane_id, coordinates) => {
+            senders
+                .send_to_screen(ScreenInstruction::ChangeFloatingPanesCoordinates(vec![(
+                    pane_id.into(),
+                    coordinates,
+                )]))
+                .with_context(err_context)?;
+        },
+        Action::MergeTab(source_tab_position, target_tab_position) => {
+            senders
+                .send_to_screen(ScreenInstruction::MergeTabs(
+                    source_tab_position,
+                    target_tab_position,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::BreakPaneRight => {
             senders
                 .send_to_screen(ScreenInstruction::BreakPaneRight(client_id))
@@ -933,7 +1057,41 @@ pub(crate) fn route_action(
                 log::error!("Message must have a name");
             }
         },
-        Action::ListClients => {
+        Action::ListPanes(tab_index, tab_name, json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListPanes(
+                    tab_index, tab_name, json, client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListTabs(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListTabs(json, client_id))
+                .with_context(err_context)?;
+        },
+        Action::WaitFor {
+            pipe_id,
+            pane_id: pane_to_wait_for,
+            condition,
+            ..
+        } => {
+            // the cli client is unblocked through its pipe once the condition is met
+            senders
+                .send_to_server(ServerInstruction::AssociatePipeWithClient {
+                    pipe_id: pipe_id.clone(),
+                    client_id,
+                })
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::WaitForPane(
+                    pane_to_wait_for.into(),
+                    condition,
+                    pipe_id,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListClients(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
@@ -941,6 +1099,7 @@ pub(crate) fn route_action(
             senders
                 .send_to_screen(ScreenInstruction::ListClientsMetadata(
                     default_shell,
+                    json,
                     client_id,
                 ))
                 .with_context(err_context)?;
@@ -965,6 +1124,37 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+// actions that only change what a read-only client sees, without changing the session itself
+// scrolling is not one of them: all clients share the viewport of a pane, so a read-only client
+// scrolling would scroll the pane for everyone else as well - the same goes for moving between tabs
+// and panes in mirrored sessions, where all clients share their focus
+fn is_allowed_for_read_only_clients(action: &Action, session_is_mirrored: bool) -> bool {
+    match action {
+        Action::SkipConfirm(action) | Action::ApplyToTarget(_, _, action) => {
+            is_allowed_for_read_only_clients(action, session_is_mirrored)
+        },
+        Action::GoToTab(_)
+        | Action::GoToNextTab
+        | Action::GoToPreviousTab
+        | Action::GoToTabName(_, false)
+        | Action::MoveFocus(_)
+        | Action::MoveFocusOrTab(_)
+        | Action::FocusNextPane
+        | Action::FocusPreviousPane => !session_is_mirrored,
+        Action::Quit
+        | Action::Detach
+        | Action::NoOp
+        | Action::SwitchToMode(_)
+        | Action::ToggleMouseMode
+        | Action::ListClients(_)
+        | Action::ListPanes(..)
+        | Action::ListTabs(_)
+        | Action::QueryTabNames(_)
+        | Action::WaitFor { .. } => true,
+        _ => false,
+    }
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -976,6 +1166,9 @@ pub(crate) fn route_thread_main(
     let mut retry_queue = VecDeque::new();
     let err_context = || format!("failed to handle instruction for client {client_id}");
     let mut seen_cli_pipes = HashSet::new();
+    // the screen keeps track of read-only clients for listing them, this is only used to refuse
+    // their actions before they reach it
+    let mut client_is_read_only = false;
     'route_loop: loop {
         match receiver.recv() {
             Some((instruction, err_ctx)) => {
@@ -989,6 +1182,25 @@ pub(crate) fn route_thread_main(
                     let mut should_break = false;
                     match instruction {
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
+                            let session_is_mirrored = rlocked_sessions
+                                .as_ref()
+                                .and_then(|session| session.config_options.mirror_session)
+                                .unwrap_or(false);
+                            if client_is_read_only
+                                && !is_allowed_for_read_only_clients(&action, session_is_mirrored)
+                            {
+                                log::warn!(
+                                    "Ignoring action {:?} from read-only client {}",
+                                    action,
+                                    client_id
+                                );
+                                // the client might be waiting for this action to be handled
+                                let _ = os_input.send_to_client(
+                                    client_id,
+                                    ServerToClientMsg::UnblockInputThread,
+                                );
+                                return Ok(false);
+                            }
                             let client_id = maybe_client_id.unwrap_or(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 if let Action::SwitchToMode(input_mode) = action {
@@ -1017,6 +1229,10 @@ pub(crate) fn route_thread_main(
                                 }
                             }
                         },
+                        ClientToServerMsg::TerminalResize(_) if client_is_read_only => {
+                            // read-only clients do not get to resize the session, they are shown
+                            // it at the size of the other clients
+                        },
                         ClientToServerMsg::TerminalResize(new_size) => {
                             session_state
                                 .write()
@@ -1104,12 +1320,15 @@ pub(crate) fn route_thread_main(
                             opts,
                             tab_position_to_focus,
                             pane_id_to_focus,
+                            client_type,
                         ) => {
+                            client_is_read_only = client_type == ClientType::Reader;
                             let attach_client_instruction = ServerInstruction::AttachClient(
                                 client_attributes,
                                 opts,
                                 tab_position_to_focus,
                                 pane_id_to_focus,
+                                client_type,
                                 client_id,
                             );
                             to_server
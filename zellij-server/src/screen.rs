@@ -9,7 +9,8 @@ use std::time::Duration;
 
 use log::{debug, warn};
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionInfo,
+    Direction, KeyWithModifier, PaneInfo, PaneManifest, PluginPermission, Resize, ResizeStrategy,
+    SessionInfo,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -21,9 +22,12 @@ use zellij_utils::{
     input::command::TerminalAction,
     input::layout::{
         FloatingPaneLayout, Layout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        TiledPaneLayout, TilingAlgorithm,
     },
     position::Position,
+    regex::Regex,
+    serde::{self, Serialize},
+    vte,
 };
 
 use crate::background_jobs::BackgroundJob;
@@ -38,7 +42,7 @@ use crate::{
     panes::PaneId,
     plugins::{PluginId, PluginInstruction, PluginRenderAsset},
     pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
-    tab::Tab,
+    tab::{Pane, Tab},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
@@ -52,7 +56,7 @@ use zellij_utils::{
         PluginCapabilities, Style, TabInfo,
     },
     errors::{ContextType, ScreenContext},
-    input::{get_mode_info, options::Options},
+    input::{actions::WaitCondition, get_mode_info, options::Options},
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
 };
 
@@ -99,7 +103,12 @@ macro_rules! active_tab_and_connected_client_id {
                 $closure(active_tab, $client_id);
             },
             Err(_) => {
-                if let Some(client_id) = $screen.get_first_client_id() {
+                if $screen.unresolved_action_targets.contains(&$client_id) {
+                    log::error!(
+                        "No pane or tab found for the action of client {}",
+                        $client_id
+                    );
+                } else if let Some(client_id) = $screen.get_first_client_id() {
                     match $screen.get_active_tab_mut(client_id) {
                         Ok(active_tab) => {
                             $closure(active_tab, client_id);
@@ -119,7 +128,12 @@ macro_rules! active_tab_and_connected_client_id {
                 $closure(active_tab, $client_id).non_fatal();
             },
             Err(_) => {
-                if let Some(client_id) = $screen.get_first_client_id() {
+                if $screen.unresolved_action_targets.contains(&$client_id) {
+                    log::error!(
+                        "No pane or tab found for the action of client {}",
+                        $client_id
+                    );
+                } else if let Some(client_id) = $screen.get_first_client_id() {
                     match $screen.get_active_tab_mut(client_id) {
                         Ok(active_tab) => {
                             $closure(active_tab, client_id)?;
@@ -153,8 +167,14 @@ pub enum ScreenInstruction {
         Option<FloatingPaneCoordinates>,
         ClientTabIndexOrPaneId,
     ),
-    OpenInPlaceEditor(PaneId, ClientId),
+    OpenInPlaceEditor(PaneId, PaneId), // the editor pane and the pane it replaces
     TogglePaneEmbedOrFloating(ClientId),
+    TogglePanePinned(ClientId),
+    UndoLayoutChange(ClientId),
+    RedoLayoutChange(ClientId),
+    StackPane(ClientId, Direction),
+    UnstackPane(ClientId),
+    StackAllPanes(ClientId),
     ToggleFloatingPanes(ClientId, Option<TerminalAction>),
     HorizontalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     VerticalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
@@ -179,7 +199,7 @@ pub enum ScreenInstruction {
     Exit,
     ClearScreen(ClientId),
     DumpScreen(String, ClientId, bool),
-    DumpLayout(Option<PathBuf>, ClientId), // PathBuf is the default configured
+    DumpLayout(Option<PathBuf>, bool, ClientId), // PathBuf is the default configured
     // shell
     DumpLayoutToPlugin(PluginId),
     EditScrollback(ClientId),
@@ -189,6 +209,8 @@ pub enum ScreenInstruction {
     ScrollDownAt(Position, ClientId),
     ScrollToBottom(ClientId),
     ScrollToTop(ClientId),
+    ScrollToPreviousPrompt(ClientId),
+    ScrollToNextPrompt(ClientId),
     PageScrollUp(ClientId),
     PageScrollDown(ClientId),
     HalfPageScrollUp(ClientId),
@@ -260,8 +282,10 @@ pub enum ScreenInstruction {
     MouseHoldRight(Position, ClientId),
     MouseHoldMiddle(Position, ClientId),
     Copy(ClientId),
+    CopyCommandOutput(ClientId),
     AddClient(
         ClientId,
+        bool,                // is read only
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
     ),
@@ -276,11 +300,15 @@ pub enum ScreenInstruction {
     SearchToggleCaseSensitivity(ClientId),
     SearchToggleWholeWord(ClientId),
     SearchToggleWrap(ClientId),
+    SearchToggleRegex(ClientId),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
-    QueryTabNames(ClientId),
+    SetTiling(Option<TilingAlgorithm>, ClientId),
+    PromoteToMaster(ClientId),
+    ChangeMasterCount(isize, ClientId),
+    QueryTabNames(bool, ClientId), // bool - output as json
     NewTiledPluginPane(
         RunPluginOrAlias,
         Option<String>,
@@ -344,12 +372,28 @@ pub enum ScreenInstruction {
         PluginPermission,
     ),
     BreakPane(Box<Layout>, Option<TerminalAction>, ClientId),
+    MovePaneToTab(
+        PaneId,
+        Option<u32>,    // tab position, starting at 1
+        Option<String>, // tab name
+        bool,           // should float
+        Box<Layout>,
+        Option<TerminalAction>,
+        ClientId,
+    ),
+    ChangeFloatingPanesCoordinates(Vec<(PaneId, FloatingPaneCoordinates)>),
+    MergeTabs(
+        Option<u32>, // source tab position, starting at 1 (None for the active tab)
+        u32,         // target tab position, starting at 1
+        ClientId,
+    ),
     BreakPaneRight(ClientId),
     BreakPaneLeft(ClientId),
     UpdateSessionInfos(
         BTreeMap<String, SessionInfo>, // String is the session name
         BTreeMap<String, Duration>,    // resurrectable sessions - <name, created>
     ),
+    CheckPaneSilence,
     ReplacePane(
         PaneId,
         HoldForCommand,
@@ -359,7 +403,19 @@ pub enum ScreenInstruction {
     ),
     DumpLayoutToHd,
     RenameSession(String, ClientId), // String -> new name
-    ListClientsMetadata(Option<PathBuf>, ClientId), // Option<PathBuf> - default shell
+    /// List the connected clients with the default shell, as JSON if the bool is true
+    ListClientsMetadata(Option<PathBuf>, bool, ClientId),
+    /// List the panes of the tab at the given position (starting at 1) or with the given name, or
+    /// of all tabs if neither is set, as JSON if the bool is true
+    ListPanes(Option<u32>, Option<String>, bool, ClientId),
+    /// List the tabs along with their position, whether they are active and their pane count, as
+    /// JSON if the bool is true
+    ListTabs(bool, ClientId),
+    /// Target the given pane and/or the tab at the given position (starting at 1) with the
+    /// client's next actions
+    SetActionTarget(Option<PaneId>, Option<u32>, ClientId),
+    ClearActionTarget(ClientId),
+    WaitForPane(PaneId, WaitCondition, String, ClientId), // String is the pipe id
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -373,6 +429,12 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::TogglePaneEmbedOrFloating(..) => {
                 ScreenContext::TogglePaneEmbedOrFloating
             },
+            ScreenInstruction::TogglePanePinned(..) => ScreenContext::TogglePanePinned,
+            ScreenInstruction::UndoLayoutChange(..) => ScreenContext::UndoLayoutChange,
+            ScreenInstruction::RedoLayoutChange(..) => ScreenContext::RedoLayoutChange,
+            ScreenInstruction::StackPane(..) => ScreenContext::StackPane,
+            ScreenInstruction::UnstackPane(..) => ScreenContext::UnstackPane,
+            ScreenInstruction::StackAllPanes(..) => ScreenContext::StackAllPanes,
             ScreenInstruction::ToggleFloatingPanes(..) => ScreenContext::ToggleFloatingPanes,
             ScreenInstruction::HorizontalSplit(..) => ScreenContext::HorizontalSplit,
             ScreenInstruction::VerticalSplit(..) => ScreenContext::VerticalSplit,
@@ -430,6 +492,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
             ScreenInstruction::ScrollToTop(..) => ScreenContext::ScrollToTop,
+            ScreenInstruction::ScrollToPreviousPrompt(..) => ScreenContext::ScrollToPreviousPrompt,
+            ScreenInstruction::ScrollToNextPrompt(..) => ScreenContext::ScrollToNextPrompt,
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
@@ -484,6 +548,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MouseHoldRight(..) => ScreenContext::MouseHoldRight,
             ScreenInstruction::MouseHoldMiddle(..) => ScreenContext::MouseHoldMiddle,
             ScreenInstruction::Copy(..) => ScreenContext::Copy,
+            ScreenInstruction::CopyCommandOutput(..) => ScreenContext::CopyCommandOutput,
             ScreenInstruction::ToggleTab(..) => ScreenContext::ToggleTab,
             ScreenInstruction::AddClient(..) => ScreenContext::AddClient,
             ScreenInstruction::RemoveClient(..) => ScreenContext::RemoveClient,
@@ -498,6 +563,7 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::SearchToggleCaseSensitivity
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
@@ -507,6 +573,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::PreviousSwapLayout(..) => ScreenContext::PreviousSwapLayout,
             ScreenInstruction::NextSwapLayout(..) => ScreenContext::NextSwapLayout,
+            ScreenInstruction::SetTiling(..) => ScreenContext::SetTiling,
+            ScreenInstruction::PromoteToMaster(..) => ScreenContext::PromoteToMaster,
+            ScreenInstruction::ChangeMasterCount(..) => ScreenContext::ChangeMasterCount,
             ScreenInstruction::QueryTabNames(..) => ScreenContext::QueryTabNames,
             ScreenInstruction::NewTiledPluginPane(..) => ScreenContext::NewTiledPluginPane,
             ScreenInstruction::NewFloatingPluginPane(..) => ScreenContext::NewFloatingPluginPane,
@@ -536,14 +605,25 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::RequestPluginPermissions
             },
             ScreenInstruction::BreakPane(..) => ScreenContext::BreakPane,
+            ScreenInstruction::MovePaneToTab(..) => ScreenContext::MovePaneToTab,
+            ScreenInstruction::ChangeFloatingPanesCoordinates(..) => {
+                ScreenContext::ChangeFloatingPanesCoordinates
+            },
+            ScreenInstruction::MergeTabs(..) => ScreenContext::MergeTabs,
             ScreenInstruction::BreakPaneRight(..) => ScreenContext::BreakPaneRight,
             ScreenInstruction::BreakPaneLeft(..) => ScreenContext::BreakPaneLeft,
             ScreenInstruction::UpdateSessionInfos(..) => ScreenContext::UpdateSessionInfos,
+            ScreenInstruction::CheckPaneSilence => ScreenContext::CheckPaneSilence,
             ScreenInstruction::ReplacePane(..) => ScreenContext::ReplacePane,
             ScreenInstruction::NewInPlacePluginPane(..) => ScreenContext::NewInPlacePluginPane,
             ScreenInstruction::DumpLayoutToHd => ScreenContext::DumpLayoutToHd,
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
             ScreenInstruction::ListClientsMetadata(..) => ScreenContext::ListClientsMetadata,
+            ScreenInstruction::ListPanes(..) => ScreenContext::ListPanes,
+            ScreenInstruction::ListTabs(..) => ScreenContext::ListTabs,
+            ScreenInstruction::SetActionTarget(..) => ScreenContext::SetActionTarget,
+            ScreenInstruction::ClearActionTarget(..) => ScreenContext::ClearActionTarget,
+            ScreenInstruction::WaitForPane(..) => ScreenContext::WaitForPane,
         }
     }
 }
@@ -578,6 +658,109 @@ impl CopyOptions {
     }
 }
 
+// a pane as listed by the `list-panes` cli action
+#[derive(Serialize)]
+#[serde(crate = "self::serde")]
+struct ListedPane {
+    /// Starts at 1, like the tab index used to filter the panes
+    tab_index: usize,
+    #[serde(flatten)]
+    pane_info: PaneInfo,
+}
+
+// a tab as listed by the `list-tabs` cli action
+#[derive(Serialize)]
+#[serde(crate = "self::serde")]
+struct ListedTab {
+    /// Starts at 1, like the tab index used to filter the listed panes
+    tab_index: usize,
+    pane_count: usize,
+    #[serde(flatten)]
+    tab_info: TabInfo,
+}
+
+// the longest unfinished line kept while waiting for the output of a pane to match
+const MAX_WAITED_LINE_LENGTH: usize = 64 * 1024;
+
+enum WaitingFor {
+    Exit,
+    Match(Regex, Box<vte::Parser>, PrintedText),
+}
+
+// the text printed to a pane, without its escape sequences
+#[derive(Default)]
+struct PrintedText {
+    text: String,
+}
+
+impl vte::Perform for PrintedText {
+    fn print(&mut self, c: char) {
+        self.text.push(c);
+    }
+    fn execute(&mut self, byte: u8) {
+        if byte == b'\n' {
+            self.text.push('\n');
+        }
+    }
+}
+
+// a cli client blocked by the `wait-for` cli action
+struct PaneWaiter {
+    pipe_id: String,
+    pane_id: PaneId,
+    waiting_for: WaitingFor,
+    client_id: ClientId,
+}
+
+impl PaneWaiter {
+    // the output for the cli client once the condition is met, or an error if it can no longer be
+    // met - only the bytes printed to the pane since the previous check are matched, line by line,
+    // so that the pane's scrollback is not searched over and over again
+    fn check(
+        &mut self,
+        pane: Option<&mut Box<dyn Pane>>,
+        new_bytes: &[u8],
+    ) -> Option<Result<String>> {
+        let pane = match pane {
+            Some(pane) => pane,
+            None => return Some(Err(anyhow!("Pane {:?} was closed", self.pane_id))),
+        };
+        match &mut self.waiting_for {
+            WaitingFor::Exit if pane.exited() => Some(
+                pane.exit_status()
+                    .map(|exit_status| exit_status.to_string())
+                    .ok_or_else(|| anyhow!("Pane {:?} exited without a status", self.pane_id)),
+            ),
+            WaitingFor::Exit => None,
+            WaitingFor::Match(regex, vte_parser, printed_text) => {
+                for byte in new_bytes {
+                    vte_parser.advance(printed_text, *byte);
+                }
+                let found = printed_text
+                    .text
+                    .split('\n')
+                    .find_map(|line| regex.find(line))
+                    .map(|found| found.as_str().to_owned());
+                if found.is_none() {
+                    // the unfinished line is kept, since it might only match once it's complete
+                    let text = &mut printed_text.text;
+                    if let Some(last_line_start) = text.rfind('\n') {
+                        text.drain(..=last_line_start);
+                    }
+                    if text.len() > MAX_WAITED_LINE_LENGTH {
+                        let mut cut = text.len() - MAX_WAITED_LINE_LENGTH;
+                        while !text.is_char_boundary(cut) {
+                            cut += 1;
+                        }
+                        text.drain(..cut);
+                    }
+                }
+                found.map(Ok)
+            },
+        }
+    }
+}
+
 /// A [`Screen`] holds multiple [`Tab`]s, each one holding multiple [`panes`](crate::client::panes).
 /// It only directly controls which tab is active, delegating the rest to the individual `Tab`.
 pub(crate) struct Screen {
@@ -597,6 +780,15 @@ pub(crate) struct Screen {
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
+    /// Clients that observe the session without being able to change it
+    read_only_clients: HashSet<ClientId>,
+    /// Clients whose actions currently apply to a pane or tab they do not focus, along with the
+    /// index of the tab they were on before
+    action_targets: HashMap<ClientId, Option<usize>>,
+    /// Clients whose requested pane or tab could not be found, so that their actions are dropped
+    /// rather than applied to the focused pane of another client
+    unresolved_action_targets: HashSet<ClientId>,
+    pane_waiters: Vec<PaneWaiter>,
     /// The indices of this [`Screen`]'s active [`Tab`]s.
     active_tab_indices: BTreeMap<ClientId, usize>,
     tab_history: BTreeMap<ClientId, Vec<usize>>,
@@ -662,6 +854,10 @@ impl Screen {
             sixel_image_store: Rc::new(RefCell::new(SixelImageStore::default())),
             style: client_attributes.style,
             connected_clients: Rc::new(RefCell::new(HashSet::new())),
+            read_only_clients: HashSet::new(),
+            action_targets: HashMap::new(),
+            unresolved_action_targets: HashSet::new(),
+            pane_waiters: vec![],
             active_tab_indices: BTreeMap::new(),
             tabs: BTreeMap::new(),
             overlay: OverlayWindow::default(),
@@ -1099,16 +1295,22 @@ impl Screen {
             self.styled_underlines,
         );
         let mut tabs_to_close = vec![];
+        let mut alerts_changed = false;
         for (tab_index, tab) in &mut self.tabs {
             if tab.has_selectable_tiled_panes() {
                 tab.render(&mut output).context(err_context)?;
             } else if !tab.is_pending() {
                 tabs_to_close.push(*tab_index);
             }
+            alerts_changed |= tab.take_alerts_changed();
         }
         for tab_index in tabs_to_close {
             self.close_tab_at_index(tab_index).context(err_context)?;
         }
+        if alerts_changed {
+            // panes raised alerts or had them seen, update the tab indications
+            self.log_and_report_session_state().context(err_context)?;
+        }
         if output.is_dirty() {
             let serialized_output = output.serialize().context(err_context)?;
             let _ = self
@@ -1376,12 +1578,14 @@ impl Screen {
             self.tab_history.remove(&client_id);
         }
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.read_only_clients.remove(&client_id);
+        self.pane_waiters
+            .retain(|waiter| waiter.client_id != client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
     }
 
-    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
-        let mut plugin_updates = vec![];
+    fn get_tab_infos(&self) -> Vec<TabInfo> {
         let mut tab_infos_for_screen_state = BTreeMap::new();
         for tab in self.tabs.values() {
             let all_focused_clients: Vec<ClientId> = self
@@ -1403,9 +1607,17 @@ impl Screen {
                 other_focused_clients: all_focused_clients,
                 active_swap_layout_name,
                 is_swap_layout_dirty,
+                has_unseen_notification: tab.has_unseen_notification(),
+                has_bell: tab.has_bell(),
+                has_activity: tab.has_activity(),
+                has_silence: tab.has_silence(),
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
+        tab_infos_for_screen_state.into_values().collect()
+    }
+    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
+        let mut plugin_updates = vec![];
         for (client_id, active_tab_index) in self.active_tab_indices.iter() {
             let mut plugin_tab_updates = vec![];
             for tab in self.tabs.values() {
@@ -1433,6 +1645,10 @@ impl Screen {
                     other_focused_clients,
                     active_swap_layout_name,
                     is_swap_layout_dirty,
+                    has_unseen_notification: tab.has_unseen_notification(),
+                    has_bell: tab.has_bell(),
+                    has_activity: tab.has_activity(),
+                    has_silence: tab.has_silence(),
                 };
                 plugin_tab_updates.push(tab_info_for_plugins);
             }
@@ -1442,13 +1658,17 @@ impl Screen {
             .senders
             .send_to_plugin(PluginInstruction::Update(plugin_updates))
             .context("failed to update tabs")?;
-        Ok(tab_infos_for_screen_state.values().cloned().collect())
+        Ok(self.get_tab_infos())
     }
-    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+    fn get_pane_manifest(&self) -> PaneManifest {
         let mut pane_manifest = PaneManifest::default();
         for tab in self.tabs.values() {
             pane_manifest.panes.insert(tab.position, tab.pane_infos());
         }
+        pane_manifest
+    }
+    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+        let pane_manifest = self.get_pane_manifest();
         self.bus
             .senders
             .send_to_plugin(PluginInstruction::Update(vec![(
@@ -1462,6 +1682,16 @@ impl Screen {
     }
     fn log_and_report_session_state(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
+        // panes might have exited or closed since the last report
+        let waited_for_pane_ids: HashSet<PaneId> = self
+            .pane_waiters
+            .iter()
+            .map(|waiter| waiter.pane_id)
+            .collect();
+        for pane_id in waited_for_pane_ids {
+            self.update_pane_waiters(pane_id, &[])
+                .with_context(err_context)?;
+        }
         // generate own session info
         let pane_manifest = self.generate_and_report_pane_state()?;
         let tab_infos = self.generate_and_report_tab_state()?;
@@ -1495,6 +1725,245 @@ impl Screen {
             .with_context(err_context)?;
         Ok(())
     }
+    fn session_info_as_json(&self) -> Result<String> {
+        let session_info = SessionInfo {
+            name: self.session_name.clone(),
+            tabs: self.get_tab_infos(),
+            panes: self.get_pane_manifest(),
+            connected_clients: self.active_tab_indices.keys().len(),
+            is_current_session: true,
+            available_layouts: vec![],
+        };
+        serde_json::to_string(&session_info).context("failed to serialize session info")
+    }
+    fn list_tabs(&self, json: bool) -> Result<String> {
+        let tabs = self.get_tab_infos().into_iter().map(|tab_info| ListedTab {
+            tab_index: tab_info.position + 1,
+            pane_count: self
+                .tabs
+                .values()
+                .find(|tab| tab.position == tab_info.position)
+                .map(|tab| tab.pane_infos().len())
+                .unwrap_or(0),
+            tab_info,
+        });
+        if json {
+            let tabs: Vec<ListedTab> = tabs.collect();
+            return serde_json::to_string(&tabs).context("failed to serialize tab infos");
+        }
+        let mut lines = vec![String::from("TAB_INDEX ACTIVE PANES NAME")];
+        for ListedTab {
+            tab_index,
+            pane_count,
+            tab_info,
+        } in tabs
+        {
+            // 9 - TAB_INDEX, 6 - ACTIVE, 5 - PANES
+            lines.push(format!(
+                "{:<9} {:<6} {:<5} {}",
+                tab_index, tab_info.active, pane_count, tab_info.name
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+    fn list_panes(
+        &self,
+        tab_index: Option<u32>,
+        tab_name: Option<String>,
+        json: bool,
+    ) -> Result<String> {
+        let tabs: Vec<&Tab> = self
+            .tabs
+            .values()
+            .filter(|tab| match (tab_index, tab_name.as_ref()) {
+                (Some(tab_index), _) => tab.position + 1 == tab_index as usize,
+                (None, Some(tab_name)) => &tab.name == tab_name,
+                (None, None) => true,
+            })
+            .collect();
+        if tabs.is_empty() && (tab_index.is_some() || tab_name.is_some()) {
+            return Err(anyhow!("No tab matches the given index or name"));
+        }
+        let panes = tabs.iter().flat_map(|tab| {
+            tab.pane_infos().into_iter().map(|pane_info| ListedPane {
+                tab_index: tab.position + 1,
+                pane_info,
+            })
+        });
+        if json {
+            let panes: Vec<ListedPane> = panes.collect();
+            return serde_json::to_string(&panes).context("failed to serialize pane infos");
+        }
+        let mut lines = vec![String::from(
+            "PANE_ID        TAB_INDEX FOCUSED FLOATING X    Y    COLS ROWS TITLE | COMMAND",
+        )];
+        for ListedPane {
+            tab_index,
+            pane_info,
+        } in panes
+        {
+            let pane_id = if pane_info.is_plugin {
+                format!("plugin_{}", pane_info.id)
+            } else {
+                format!("terminal_{}", pane_info.id)
+            };
+            let command = pane_info
+                .terminal_command
+                .as_ref()
+                .or(pane_info.plugin_url.as_ref())
+                .map(|c| c.as_str())
+                .unwrap_or("N/A");
+            // 14 - PANE_ID, 9 - TAB_INDEX, 7 - FOCUSED, 8 - FLOATING, 4 - X, Y, COLS and ROWS
+            lines.push(format!(
+                "{:<14} {:<9} {:<7} {:<8} {:<4} {:<4} {:<4} {:<4} {} | {}",
+                pane_id,
+                tab_index,
+                pane_info.is_focused,
+                pane_info.is_floating,
+                pane_info.pane_x,
+                pane_info.pane_y,
+                pane_info.pane_columns,
+                pane_info.pane_rows,
+                pane_info.title,
+                command,
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+    fn set_action_target(
+        &mut self,
+        pane_id: Option<PaneId>,
+        tab_position: Option<u32>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let tab_index = match (pane_id, tab_position) {
+            (Some(pane_id), tab_position) => {
+                let tab = self
+                    .tabs
+                    .values()
+                    .find(|tab| tab.has_pane_with_pid(&pane_id))
+                    .ok_or_else(|| anyhow!("No pane with id {:?}", pane_id))?;
+                if let Some(tab_position) = tab_position {
+                    if tab.position + 1 != tab_position as usize {
+                        return Err(anyhow!("Pane {:?} is not in tab {}", pane_id, tab_position));
+                    }
+                }
+                tab.index
+            },
+            (None, Some(tab_position)) => self
+                .tabs
+                .values()
+                .find(|tab| tab.position + 1 == tab_position as usize)
+                .map(|tab| tab.index)
+                .ok_or_else(|| anyhow!("No tab at position {}", tab_position))?,
+            (None, None) => return Ok(()),
+        };
+        self.tabs
+            .get_mut(&tab_index)
+            .with_context(|| format!("failed to find tab with index {tab_index}"))?
+            .set_action_target(pane_id, client_id)?;
+        let previous_tab_index = self.active_tab_indices.insert(client_id, tab_index);
+        self.action_targets.insert(client_id, previous_tab_index);
+        Ok(())
+    }
+    fn clear_action_target(&mut self, client_id: ClientId) {
+        self.unresolved_action_targets.remove(&client_id);
+        if let Some(previous_tab_index) = self.action_targets.remove(&client_id) {
+            for tab in self.tabs.values_mut() {
+                tab.clear_action_target(client_id);
+            }
+            match previous_tab_index {
+                Some(previous_tab_index) => {
+                    self.active_tab_indices
+                        .insert(client_id, previous_tab_index);
+                },
+                None => {
+                    self.active_tab_indices.remove(&client_id);
+                },
+            }
+        }
+    }
+    fn wait_for_pane(
+        &mut self,
+        pane_id: PaneId,
+        condition: WaitCondition,
+        pipe_id: String,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let waiting_for = match condition {
+            WaitCondition::Exit => WaitingFor::Exit,
+            WaitCondition::Match(pattern) => WaitingFor::Match(
+                Regex::new(&pattern).map_err(|e| anyhow!("Invalid regular expression: {}", e))?,
+                Box::new(vte::Parser::new()),
+                PrintedText::default(),
+            ),
+        };
+        if !self
+            .tabs
+            .values()
+            .any(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            return Err(anyhow!("No pane with id {:?}", pane_id));
+        }
+        self.pane_waiters.push(PaneWaiter {
+            pipe_id,
+            pane_id,
+            waiting_for,
+            client_id,
+        });
+        // output printed before the client started waiting does not count
+        self.update_pane_waiters(pane_id, &[])
+    }
+    fn update_pane_waiters(&mut self, pane_id: PaneId, new_bytes: &[u8]) -> Result<()> {
+        if !self
+            .pane_waiters
+            .iter()
+            .any(|waiter| waiter.pane_id == pane_id)
+        {
+            return Ok(());
+        }
+        let mut pane = self
+            .tabs
+            .values_mut()
+            .find_map(|tab| tab.get_pane_with_id_mut(pane_id));
+        let mut outcomes = vec![];
+        self.pane_waiters.retain_mut(|waiter| {
+            if waiter.pane_id != pane_id {
+                return true;
+            }
+            match waiter.check(pane.as_deref_mut(), new_bytes) {
+                Some(outcome) => {
+                    outcomes.push((waiter.pipe_id.clone(), waiter.client_id, outcome));
+                    false
+                },
+                None => true,
+            }
+        });
+        for (pipe_id, client_id, outcome) in outcomes {
+            match outcome {
+                Ok(output) => {
+                    self.bus
+                        .senders
+                        .send_to_server(ServerInstruction::CliPipeOutput(
+                            pipe_id.clone(),
+                            output,
+                        ))?;
+                    self.bus
+                        .senders
+                        .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_id))?;
+                },
+                Err(e) => {
+                    self.bus
+                        .senders
+                        .send_to_server(ServerInstruction::LogError(
+                            vec![e.to_string()],
+                            client_id,
+                        ))?;
+                },
+            }
+        }
+        Ok(())
+    }
     fn dump_layout_to_hd(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
         let session_layout_metadata = self.get_layout_metadata(self.default_shell.clone());
@@ -1505,6 +1974,24 @@ impl Screen {
 
         Ok(())
     }
+    pub fn check_pane_silence(&mut self) -> Result<()> {
+        if !self.tabs.values().any(|tab| tab.monitors_silence()) {
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::StopMonitoringPaneSilence)
+                .context("failed to stop monitoring pane silence")?;
+            return Ok(());
+        }
+        let mut pane_became_silent = false;
+        for tab in self.tabs.values_mut() {
+            pane_became_silent |= tab.check_pane_silence();
+        }
+        if pane_became_silent {
+            self.log_and_report_session_state()
+                .context("failed to report pane silence")?;
+        }
+        Ok(())
+    }
     pub fn update_session_infos(
         &mut self,
         new_session_infos: BTreeMap<String, SessionInfo>,
@@ -2031,6 +2518,265 @@ impl Screen {
         }
         Ok(())
     }
+    pub fn move_pane_to_tab(
+        &mut self,
+        pane_id: PaneId,
+        tab_position: Option<u32>,
+        tab_name: Option<String>,
+        should_float: bool,
+        default_shell: Option<TerminalAction>,
+        default_layout: Box<Layout>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to move pane {:?} to tab", pane_id);
+        let source_tab_index = match self
+            .tabs
+            .iter()
+            .find(|(_tab_index, tab)| tab.has_pane_with_pid(&pane_id))
+            .map(|(tab_index, _tab)| *tab_index)
+        {
+            Some(source_tab_index) => source_tab_index,
+            None => {
+                log::error!("Could not find pane with id: {:?}", pane_id);
+                return Ok(());
+            },
+        };
+        let target_tab_index = self
+            .tabs
+            .values()
+            .find(|tab| match (&tab_name, tab_position) {
+                (Some(tab_name), _) => &tab.name == tab_name,
+                (None, Some(tab_position)) => tab.position + 1 == tab_position as usize,
+                (None, None) => false,
+            })
+            .map(|tab| tab.index);
+        if target_tab_index == Some(source_tab_index) {
+            log::error!("Pane {:?} is already in the requested tab", pane_id);
+            return Ok(());
+        }
+        let pane = match self
+            .tabs
+            .get_mut(&source_tab_index)
+            .with_context(err_context)?
+            .extract_pane(pane_id, None)
+        {
+            Some(pane) => pane,
+            None => {
+                log::error!("Failed to extract pane with id: {:?}", pane_id);
+                return Ok(());
+            },
+        };
+        match target_tab_index {
+            Some(target_tab_index) => {
+                let tab = self
+                    .tabs
+                    .get_mut(&target_tab_index)
+                    .with_context(err_context)?;
+                if should_float {
+                    tab.show_floating_panes();
+                    tab.add_floating_pane(pane, pane_id, None, None)?;
+                } else {
+                    tab.add_tiled_pane(pane, pane_id, None)?;
+                }
+            },
+            None => {
+                // the source tab will be closed on the next render if this was its last pane
+                let pane_run_instruction = pane.invoked_with().clone();
+                let tab_index = self.get_new_tab_index();
+                let swap_layouts = (
+                    default_layout.swap_tiled_layouts.clone(),
+                    default_layout.swap_floating_layouts.clone(),
+                );
+                self.new_tab(tab_index, swap_layouts, tab_name, client_id)?;
+                let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+                let (mut tiled_panes_layout, mut floating_panes_layout) = default_layout.new_tab();
+                if should_float {
+                    tab.show_floating_panes();
+                    tab.add_floating_pane(pane, pane_id, None, Some(client_id))?;
+                    if let Some(already_running_layout) = floating_panes_layout
+                        .iter_mut()
+                        .find(|i| i.run == pane_run_instruction)
+                    {
+                        already_running_layout.already_running = true;
+                    }
+                } else {
+                    tab.add_tiled_pane(pane, pane_id, Some(client_id))?;
+                    tiled_panes_layout.ignore_run_instruction(pane_run_instruction.clone());
+                }
+                self.bus.senders.send_to_plugin(PluginInstruction::NewTab(
+                    None,
+                    default_shell,
+                    Some(tiled_panes_layout),
+                    floating_panes_layout,
+                    tab_index,
+                    client_id,
+                ))?;
+                return Ok(());
+            },
+        }
+        self.log_and_report_session_state()
+            .and_then(|_| self.render(None))
+            .with_context(err_context)
+    }
+    pub fn change_floating_panes_coordinates(
+        &mut self,
+        pane_ids_and_coordinates: Vec<(PaneId, FloatingPaneCoordinates)>,
+    ) -> Result<()> {
+        let err_context = || "failed to change floating panes coordinates".to_string();
+        for (pane_id, coordinates) in pane_ids_and_coordinates {
+            match self
+                .tabs
+                .values_mut()
+                .find(|tab| tab.has_pane_with_pid(&pane_id))
+            {
+                Some(tab) => {
+                    tab.change_floating_pane_coordinates(&pane_id, coordinates)
+                        .with_context(err_context)
+                        .non_fatal();
+                },
+                None => {
+                    log::error!("Could not find pane with id: {:?}", pane_id);
+                },
+            }
+        }
+        self.render(None).with_context(err_context)
+    }
+    pub fn merge_tabs(
+        &mut self,
+        source_tab_position: Option<u32>,
+        target_tab_position: u32,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to merge tab {:?} into tab {}",
+                source_tab_position, target_tab_position
+            )
+        };
+        let source_tab_index = match source_tab_position {
+            Some(source_tab_position) => self
+                .tabs
+                .values()
+                .find(|tab| tab.position + 1 == source_tab_position as usize)
+                .map(|tab| tab.index),
+            None => self.active_tab_indices.get(&client_id).copied(),
+        };
+        let target_tab_index = self
+            .tabs
+            .values()
+            .find(|tab| tab.position + 1 == target_tab_position as usize)
+            .map(|tab| tab.index);
+        let (source_tab_index, target_tab_index) = match (source_tab_index, target_tab_index) {
+            (Some(source_tab_index), Some(target_tab_index))
+                if source_tab_index != target_tab_index =>
+            {
+                (source_tab_index, target_tab_index)
+            },
+            _ => {
+                log::error!(
+                    "Cannot merge tab {:?} into tab {}",
+                    source_tab_position,
+                    target_tab_position
+                );
+                return Ok(());
+            },
+        };
+        let source_has_tiled_panes = self
+            .tabs
+            .get(&source_tab_index)
+            .with_context(err_context)?
+            .get_selectable_tiled_panes_count()
+            > 0;
+        if source_has_tiled_panes
+            && !self
+                .tabs
+                .get_mut(&target_tab_index)
+                .with_context(err_context)?
+                .has_room_for_new_tiled_pane()
+        {
+            log::error!(
+                "Cannot merge tab {:?} into tab {}: no room for more panes",
+                source_tab_position,
+                target_tab_position
+            );
+            return Ok(());
+        }
+        let (tiled_panes, floating_panes, suppressed_panes, floating_panes_are_visible) = {
+            let source_tab = self
+                .tabs
+                .get_mut(&source_tab_index)
+                .with_context(err_context)?;
+            let floating_panes_are_visible = source_tab.are_floating_panes_visible();
+            // non-selectable panes (eg. the tab-bar and status-bar) stay behind and are closed
+            // with the source tab, the target tab has its own
+            let tiled_pane_ids = source_tab.get_selectable_tiled_pane_ids();
+            let floating_pane_ids = source_tab.get_floating_pane_ids();
+            let tiled_panes: Vec<_> = tiled_pane_ids
+                .into_iter()
+                .filter_map(|pane_id| {
+                    source_tab
+                        .extract_pane(pane_id, None)
+                        .map(|pane| (pane_id, pane))
+                })
+                .collect();
+            let floating_panes: Vec<_> = floating_pane_ids
+                .into_iter()
+                .filter_map(|pane_id| {
+                    source_tab
+                        .extract_pane(pane_id, None)
+                        .map(|pane| (pane_id, pane))
+                })
+                .collect();
+            let suppressed_panes = source_tab.extract_suppressed_panes();
+            (
+                tiled_panes,
+                floating_panes,
+                suppressed_panes,
+                floating_panes_are_visible,
+            )
+        };
+        let target_tab = self
+            .tabs
+            .get_mut(&target_tab_index)
+            .with_context(err_context)?;
+        for (pane_id, pane) in tiled_panes {
+            if target_tab.has_room_for_new_tiled_pane() {
+                target_tab
+                    .add_tiled_pane(pane, pane_id, None)
+                    .with_context(err_context)?;
+            } else {
+                // the target tab filled up while merging, rather than losing the remaining
+                // panes (and leaving their ptys running) we float them
+                target_tab
+                    .add_floating_pane(pane, pane_id, None, None)
+                    .with_context(err_context)?;
+                target_tab.show_floating_panes();
+            }
+        }
+        let has_floating_panes = !floating_panes.is_empty();
+        for (pane_id, pane) in floating_panes {
+            target_tab
+                .add_floating_pane(pane, pane_id, None, None)
+                .with_context(err_context)?;
+        }
+        target_tab.add_suppressed_panes(suppressed_panes);
+        if has_floating_panes && floating_panes_are_visible {
+            target_tab.show_floating_panes();
+        }
+        let clients_in_source_tab: Vec<ClientId> = self
+            .active_tab_indices
+            .iter()
+            .filter(|(_client_id, tab_index)| **tab_index == source_tab_index)
+            .map(|(client_id, _tab_index)| *client_id)
+            .collect();
+        self.move_clients_between_tabs(source_tab_index, target_tab_index, true, None)
+            .with_context(err_context)?;
+        for client_id in clients_in_source_tab {
+            self.update_client_tab_focus(client_id, target_tab_index);
+        }
+        self.close_tab_at_index(source_tab_index)
+            .with_context(err_context)
+    }
     pub fn break_pane_to_new_tab(
         &mut self,
         direction: Direction,
@@ -2161,6 +2907,7 @@ impl Screen {
         if let Some(default_shell) = default_shell {
             session_layout_metadata.update_default_shell(default_shell);
         }
+        session_layout_metadata.update_read_only_clients(self.read_only_clients.clone());
         let first_client_id = self.get_first_client_id();
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
@@ -2215,6 +2962,7 @@ impl Screen {
                         pane_id,
                         p.position_and_size(),
                         p.borderless(),
+                        false, // tiled panes are never pinned
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
@@ -2225,6 +2973,7 @@ impl Screen {
                         },
                         focused_clients,
                     )
+                    .with_monitor(p.monitor())
                 })
                 .collect();
             let floating_panes: Vec<PaneLayoutMetadata> = tab
@@ -2253,6 +3002,7 @@ impl Screen {
                         pane_id,
                         p.position_and_size(),
                         false, // floating panes are never borderless
+                        p.is_pinned(),
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
@@ -2263,12 +3013,14 @@ impl Screen {
                         },
                         focused_clients,
                     )
+                    .with_monitor(p.monitor())
                 })
                 .collect();
             session_layout_metadata.add_tab(
                 tab.name.clone(),
                 tab_is_focused,
                 hide_floating_panes,
+                tab.dynamic_tiling(),
                 tiled_panes,
                 floating_panes,
             );
@@ -2377,6 +3129,7 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                screen.update_pane_waiters(PaneId::Terminal(pid), &vte_bytes)?;
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
@@ -2467,9 +3220,18 @@ pub(crate) fn screen_thread_main(
 
                 screen.render(None)?;
             },
-            ScreenInstruction::OpenInPlaceEditor(pid, client_id) => {
-                active_tab!(screen, client_id, |tab: &mut Tab| tab
-                    .replace_active_pane_with_editor_pane(pid, client_id), ?);
+            ScreenInstruction::OpenInPlaceEditor(pid, replaced_pane_id) => {
+                match screen
+                    .tabs
+                    .values_mut()
+                    .find(|tab| tab.has_pane_with_pid(&replaced_pane_id))
+                {
+                    Some(tab) => tab.replace_pane_with_editor_pane(pid, replaced_pane_id)?,
+                    None => log::error!(
+                        "Pane {:?} to open the scrollback editor in was not found",
+                        replaced_pane_id
+                    ),
+                }
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
 
@@ -2477,7 +3239,55 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::TogglePaneEmbedOrFloating(client_id) => {
                 active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
-                    .toggle_pane_embed_or_floating(client_id), ?);
+                    .with_layout_history(|tab| tab.toggle_pane_embed_or_floating(client_id)), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::TogglePanePinned(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .toggle_pane_pinned(client_id), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::UndoLayoutChange(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .undo_layout_change(client_id), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::RedoLayoutChange(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .redo_layout_change(client_id), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::StackPane(client_id, direction) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .with_layout_history(|tab| tab.stack_active_pane(client_id, direction)), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::UnstackPane(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .with_layout_history(|tab| tab.unstack_active_pane(client_id)), ?);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::StackAllPanes(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .with_layout_history(|tab| tab.stack_all_panes(client_id)), ?);
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
 
@@ -2485,7 +3295,7 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::ToggleFloatingPanes(client_id, default_shell) => {
                 active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
-                    .toggle_floating_panes(Some(client_id), default_shell), ?);
+                    .with_layout_history(|tab| tab.toggle_floating_panes(Some(client_id), default_shell)), ?);
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
 
@@ -2579,7 +3389,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.resize(client_id, strategy),
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.resize(client_id, strategy)),
                     ?
                 );
                 screen.unblock_input()?;
@@ -2697,7 +3508,14 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::DumpLayout(default_shell, client_id) => {
+            ScreenInstruction::DumpLayout(_default_shell, true, client_id) => {
+                let session_info = screen.session_info_as_json()?;
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(vec![session_info], client_id))?;
+            },
+            ScreenInstruction::DumpLayout(default_shell, false, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
                 screen
@@ -2709,7 +3527,7 @@ pub(crate) fn screen_thread_main(
                     ))
                     .with_context(err_context)?;
             },
-            ScreenInstruction::ListClientsMetadata(default_shell, client_id) => {
+            ScreenInstruction::ListClientsMetadata(default_shell, json, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
                 screen
@@ -2717,10 +3535,64 @@ pub(crate) fn screen_thread_main(
                     .senders
                     .send_to_plugin(PluginInstruction::ListClientsMetadata(
                         session_layout_metadata,
+                        json,
                         client_id,
                     ))
                     .with_context(err_context)?;
             },
+            ScreenInstruction::ListPanes(tab_index, tab_name, json, client_id) => {
+                match screen.list_panes(tab_index, tab_name, json) {
+                    Ok(panes) => {
+                        screen
+                            .bus
+                            .senders
+                            .send_to_server(ServerInstruction::Log(vec![panes], client_id))?;
+                    },
+                    Err(e) => {
+                        screen
+                            .bus
+                            .senders
+                            .send_to_server(ServerInstruction::LogError(
+                                vec![e.to_string()],
+                                client_id,
+                            ))?;
+                    },
+                }
+            },
+            ScreenInstruction::ListTabs(json, client_id) => {
+                let tabs = screen.list_tabs(json)?;
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(vec![tabs], client_id))?;
+            },
+            ScreenInstruction::SetActionTarget(pane_id, tab_position, client_id) => {
+                if let Err(e) = screen.set_action_target(pane_id, tab_position, client_id) {
+                    screen.unresolved_action_targets.insert(client_id);
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::LogError(
+                            vec![e.to_string()],
+                            client_id,
+                        ))?;
+                }
+            },
+            ScreenInstruction::ClearActionTarget(client_id) => {
+                screen.clear_action_target(client_id);
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::WaitForPane(pane_id, condition, pipe_id, client_id) => {
+                if let Err(e) = screen.wait_for_pane(pane_id, condition, pipe_id, client_id) {
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::LogError(
+                            vec![e.to_string()],
+                            client_id,
+                        ))?;
+                }
+            },
             ScreenInstruction::DumpLayoutToPlugin(plugin_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata =
@@ -2758,7 +3630,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2768,7 +3641,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_backwards(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane_backwards(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2778,7 +3652,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_down(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane_down(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2788,7 +3663,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_up(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane_up(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2798,7 +3674,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_right(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane_right(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2808,7 +3685,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_left(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.move_active_pane_left(client_id))
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2863,6 +3741,26 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ScrollToPreviousPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_previous_prompt(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ScrollToNextPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_next_prompt(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::PageScrollUp(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2917,7 +3815,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.close_focused_pane(client_id)), ?
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
@@ -2950,7 +3849,7 @@ pub(crate) fn screen_thread_main(
                     None => {
                         for tab in screen.tabs.values_mut() {
                             if tab.get_all_pane_ids().contains(&id) {
-                                tab.close_pane(id, false, None);
+                                tab.with_layout_history(|tab| tab.close_pane(id, false, None));
                                 break;
                             }
                         }
@@ -3300,6 +4199,15 @@ pub(crate) fn screen_thread_main(
                     .copy_selection(client_id), ?);
                 screen.render(None)?;
             },
+            ScreenInstruction::CopyCommandOutput(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.copy_command_output(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::Exit => {
                 break;
             },
@@ -3308,8 +4216,16 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render(None)?;
             },
-            ScreenInstruction::AddClient(client_id, tab_position_to_focus, pane_id_to_focus) => {
+            ScreenInstruction::AddClient(
+                client_id,
+                is_read_only,
+                tab_position_to_focus,
+                pane_id_to_focus,
+            ) => {
                 screen.add_client(client_id)?;
+                if is_read_only {
+                    screen.read_only_clients.insert(client_id);
+                }
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
                     if is_plugin {
                         PaneId::Plugin(pane_id)
@@ -3410,6 +4326,15 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SearchToggleRegex(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -3438,7 +4363,8 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.previous_swap_layout(Some(client_id)),
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.previous_swap_layout(Some(client_id))),
                     ?
                 );
                 screen.render(None)?;
@@ -3449,19 +4375,58 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.next_swap_layout(Some(client_id), true),
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .with_layout_history(|tab| tab.next_swap_layout(Some(client_id), true)),
                     ?
                 );
                 screen.render(None)?;
                 screen.log_and_report_session_state()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::QueryTabNames(client_id) => {
-                let tab_names = screen
-                    .get_tabs_mut()
-                    .values()
-                    .map(|tab| tab.name.clone())
-                    .collect::<Vec<String>>();
+            ScreenInstruction::SetTiling(algorithm, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.set_tiling(algorithm, client_id),
+                    ?
+                );
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::PromoteToMaster(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.promote_focused_pane_to_master(client_id),
+                    ?
+                );
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ChangeMasterCount(change_by, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.change_master_count(change_by, client_id),
+                    ?
+                );
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::QueryTabNames(json, client_id) => {
+                let tab_names = if json {
+                    vec![serde_json::to_string(&screen.get_tab_infos())
+                        .context("failed to serialize tab infos")?]
+                } else {
+                    screen
+                        .get_tabs_mut()
+                        .values()
+                        .map(|tab| tab.name.clone())
+                        .collect::<Vec<String>>()
+                };
                 screen
                     .bus
                     .senders
@@ -3909,6 +4874,34 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::BreakPane(default_layout, default_shell, client_id) => {
                 screen.break_pane(default_shell, default_layout, client_id)?;
             },
+            ScreenInstruction::MovePaneToTab(
+                pane_id,
+                tab_position,
+                tab_name,
+                should_float,
+                default_layout,
+                default_shell,
+                client_id,
+            ) => {
+                screen.move_pane_to_tab(
+                    pane_id,
+                    tab_position,
+                    tab_name,
+                    should_float,
+                    default_shell,
+                    default_layout,
+                    client_id,
+                )?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ChangeFloatingPanesCoordinates(pane_ids_and_coordinates) => {
+                screen.change_floating_panes_coordinates(pane_ids_and_coordinates)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::MergeTabs(source_tab_position, target_tab_position, client_id) => {
+                screen.merge_tabs(source_tab_position, target_tab_position, client_id)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::BreakPaneRight(client_id) => {
                 screen.break_pane_to_new_tab(Direction::Right, client_id)?;
             },
@@ -3918,6 +4911,9 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::UpdateSessionInfos(new_session_infos, resurrectable_sessions) => {
                 screen.update_session_infos(new_session_infos, resurrectable_sessions)?;
             },
+            ScreenInstruction::CheckPaneSilence => {
+                screen.check_pane_silence()?;
+            },
             ScreenInstruction::ReplacePane(
                 new_pane_id,
                 hold_for_command,
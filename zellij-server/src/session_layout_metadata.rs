@@ -1,12 +1,14 @@
-use crate::panes::PaneId;
+use crate::panes::{PaneId, PaneMonitor};
 use crate::ClientId;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use zellij_utils::common_path::common_path_all;
 use zellij_utils::pane_size::PaneGeom;
+use zellij_utils::serde::{self, Serialize};
 use zellij_utils::{
+    errors::prelude::*,
     input::command::RunCommand,
-    input::layout::{Layout, Run, RunPlugin, RunPluginOrAlias},
+    input::layout::{DynamicTiling, Layout, Run, RunPlugin, RunPluginOrAlias},
     session_serialization::{
         extract_command_and_args, extract_edit_and_line_number, extract_plugin_and_config,
         GlobalLayoutManifest, PaneLayoutManifest, TabLayoutManifest,
@@ -20,6 +22,7 @@ pub struct SessionLayoutMetadata {
     pub default_shell: Option<PathBuf>,
     pub default_editor: Option<PathBuf>,
     tabs: Vec<TabLayoutMetadata>,
+    read_only_clients: HashSet<ClientId>,
 }
 
 impl SessionLayoutMetadata {
@@ -29,6 +32,9 @@ impl SessionLayoutMetadata {
             ..Default::default()
         }
     }
+    pub fn update_read_only_clients(&mut self, read_only_clients: HashSet<ClientId>) {
+        self.read_only_clients = read_only_clients;
+    }
     pub fn update_default_shell(&mut self, default_shell: PathBuf) {
         if self.default_shell.is_none() {
             self.default_shell = Some(default_shell);
@@ -58,7 +64,7 @@ impl SessionLayoutMetadata {
             }
         }
     }
-    pub fn list_clients_metadata(&self) -> String {
+    fn clients_metadata(&self) -> BTreeMap<ClientId, ClientMetadata> {
         let mut clients_metadata: BTreeMap<ClientId, ClientMetadata> = BTreeMap::new();
         for tab in &self.tabs {
             let panes = if tab.hide_floating_panes {
@@ -73,13 +79,19 @@ impl SessionLayoutMetadata {
                         ClientMetadata {
                             pane_id: pane.id.clone(),
                             command: pane.run.clone(),
+                            is_read_only: self.read_only_clients.contains(focused_client),
                         },
                     );
                 }
             }
         }
-
-        ClientMetadata::render_many(clients_metadata, &self.default_editor)
+        clients_metadata
+    }
+    pub fn list_clients_metadata(&self) -> String {
+        ClientMetadata::render_many(self.clients_metadata(), &self.default_editor)
+    }
+    pub fn list_clients_metadata_as_json(&self) -> Result<String> {
+        ClientMetadata::render_many_as_json(self.clients_metadata(), &self.default_editor)
     }
     fn is_default_shell(
         default_shell: Option<&PathBuf>,
@@ -101,6 +113,7 @@ impl SessionLayoutMetadata {
         name: String,
         is_focused: bool,
         hide_floating_panes: bool,
+        dynamic_tiling: Option<DynamicTiling>,
         tiled_panes: Vec<PaneLayoutMetadata>,
         floating_panes: Vec<PaneLayoutMetadata>,
     ) {
@@ -108,6 +121,7 @@ impl SessionLayoutMetadata {
             name: Some(name),
             is_focused,
             hide_floating_panes,
+            dynamic_tiling,
             tiled_panes,
             floating_panes,
         })
@@ -160,6 +174,12 @@ impl SessionLayoutMetadata {
                         } else {
                             let mut run_command = RunCommand::new(PathBuf::from(command_name));
                             run_command.args = args;
+                            // the environment of the running command isn't visible to us, so we
+                            // keep the one the pane was originally started with
+                            if let Some(Run::Command(original_command)) = &pane_layout_metadata.run
+                            {
+                                run_command.env = original_command.env.clone();
+                            }
                             pane_layout_metadata.run = Some(Run::Command(run_command));
                         }
                     }
@@ -253,6 +273,7 @@ impl Into<TabLayoutManifest> for TabLayoutMetadata {
             floating_panes: self.floating_panes.into_iter().map(|t| t.into()).collect(),
             is_focused: self.is_focused,
             hide_floating_panes: self.hide_floating_panes,
+            dynamic_tiling: self.dynamic_tiling,
         }
     }
 }
@@ -264,6 +285,9 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             run: self.run,
             cwd: self.cwd,
             is_borderless: self.is_borderless,
+            is_pinned: self.is_pinned,
+            monitor_activity: self.monitor_activity,
+            monitor_silence: self.monitor_silence,
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
@@ -278,6 +302,7 @@ pub struct TabLayoutMetadata {
     floating_panes: Vec<PaneLayoutMetadata>,
     is_focused: bool,
     hide_floating_panes: bool,
+    dynamic_tiling: Option<DynamicTiling>,
 }
 
 #[derive(Debug, Clone)]
@@ -287,6 +312,9 @@ pub struct PaneLayoutMetadata {
     run: Option<Run>,
     cwd: Option<PathBuf>,
     is_borderless: bool,
+    is_pinned: bool,
+    monitor_activity: bool,
+    monitor_silence: Option<u64>, // seconds
     title: Option<String>,
     is_focused: bool,
     pane_contents: Option<String>,
@@ -298,6 +326,7 @@ impl PaneLayoutMetadata {
         id: PaneId,
         geom: PaneGeom,
         is_borderless: bool,
+        is_pinned: bool,
         run: Option<Run>,
         title: Option<String>,
         is_focused: bool,
@@ -310,17 +339,28 @@ impl PaneLayoutMetadata {
             run,
             cwd: None,
             is_borderless,
+            is_pinned,
+            monitor_activity: false,
+            monitor_silence: None,
             title,
             is_focused,
             pane_contents,
             focused_clients,
         }
     }
+    pub fn with_monitor(mut self, monitor: Option<&PaneMonitor>) -> Self {
+        if let Some(monitor) = monitor {
+            self.monitor_activity = monitor.monitor_activity;
+            self.monitor_silence = monitor.monitor_silence.map(|d| d.as_secs());
+        }
+        self
+    }
 }
 
 struct ClientMetadata {
     pane_id: PaneId,
     command: Option<Run>,
+    is_read_only: bool,
 }
 impl ClientMetadata {
     pub fn stringify_pane_id(&self) -> String {
@@ -355,14 +395,17 @@ impl ClientMetadata {
         default_editor: &Option<PathBuf>,
     ) -> String {
         let mut lines = vec![];
-        lines.push(String::from("CLIENT_ID ZELLIJ_PANE_ID RUNNING_COMMAND"));
+        lines.push(String::from(
+            "CLIENT_ID ZELLIJ_PANE_ID READ_ONLY RUNNING_COMMAND",
+        ));
 
         for (client_id, client_metadata) in clients_metadata.iter() {
-            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 15 - RUNNING_COMMAND
+            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 9 - READ_ONLY, 15 - RUNNING_COMMAND
             lines.push(format!(
-                "{} {} {}",
+                "{} {} {} {}",
                 format!("{0: <9}", client_id),
                 format!("{0: <14}", client_metadata.stringify_pane_id()),
+                format!("{0: <9}", client_metadata.is_read_only),
                 format!(
                     "{0: <15}",
                     client_metadata.stringify_command(default_editor)
@@ -371,4 +414,31 @@ impl ClientMetadata {
         }
         lines.join("\n")
     }
+    pub fn render_many_as_json(
+        clients_metadata: BTreeMap<ClientId, ClientMetadata>,
+        default_editor: &Option<PathBuf>,
+    ) -> Result<String> {
+        let clients: Vec<SerializedClientMetadata> = clients_metadata
+            .iter()
+            .map(|(client_id, client_metadata)| SerializedClientMetadata {
+                client_id: *client_id,
+                pane_id: client_metadata.stringify_pane_id(),
+                is_read_only: client_metadata.is_read_only,
+                running_command: client_metadata
+                    .command
+                    .as_ref()
+                    .map(|_| client_metadata.stringify_command(default_editor)),
+            })
+            .collect();
+        serde_json::to_string(&clients).context("failed to serialize clients metadata")
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "self::serde")]
+struct SerializedClientMetadata {
+    client_id: ClientId,
+    pane_id: String,
+    is_read_only: bool,
+    running_command: Option<String>,
 }
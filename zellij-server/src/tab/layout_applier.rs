@@ -16,6 +16,7 @@ use crate::{
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
+use std::time::Duration;
 use zellij_utils::{
     data::{Palette, Style},
     input::layout::{FloatingPaneLayout, Run, RunPluginOrAlias, TiledPaneLayout},
@@ -124,9 +125,12 @@ impl<'a> LayoutApplier<'a> {
     pub fn apply_tiled_panes_layout_to_existing_panes(
         &mut self,
         layout: &TiledPaneLayout,
+        pane_order: &[PaneId],
         refocus_pane: bool,
         client_id: Option<ClientId>,
     ) -> Result<()> {
+        // pane_order, if not empty, dictates which panes are placed in the empty (without a run
+        // instruction) slots of the layout, in order
         let free_space = self.total_space_for_tiled_panes();
         let tiled_panes_count = self.tiled_panes.visible_panes_count();
         let positions_in_layout =
@@ -146,13 +150,23 @@ impl<'a> LayoutApplier<'a> {
             ExistingTabState::new(self.tiled_panes.drain(), currently_focused_pane_id);
         let mut pane_focuser = PaneFocuser::new(refocus_pane);
         let mut positions_left = vec![];
+        let mut pane_order = pane_order.iter();
         for (layout, position_and_size) in positions_in_layout {
+            let ordered_pane = if layout.run.is_none() {
+                pane_order
+                    .by_ref()
+                    .find_map(|pane_id| existing_tab_state.remove_pane(pane_id))
+            } else {
+                None
+            };
             // first try to find panes with contents matching the layout exactly
-            match existing_tab_state.find_and_extract_exact_match_pane(
-                &layout.run,
-                &position_and_size,
-                true,
-            ) {
+            match ordered_pane.or_else(|| {
+                existing_tab_state.find_and_extract_exact_match_pane(
+                    &layout.run,
+                    &position_and_size,
+                    true,
+                )
+            }) {
                 Some(mut pane) => {
                     self.apply_layout_properties_to_pane(
                         &mut pane,
@@ -335,6 +349,9 @@ impl<'a> LayoutApplier<'a> {
                         self.styled_underlines,
                         self.explicitly_disable_kitty_keyboard_protocol,
                     );
+                    new_pane.monitor.monitor_activity = layout.monitor_activity.unwrap_or(false);
+                    new_pane.monitor.monitor_silence =
+                        layout.monitor_silence.map(Duration::from_secs);
                     if let Some(pane_initial_contents) = &layout.pane_initial_contents {
                         new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
                         new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -418,6 +435,7 @@ impl<'a> LayoutApplier<'a> {
                 }
                 new_pane.set_borderless(false);
                 new_pane.set_content_offset(Offset::frame(1));
+                new_pane.set_pinned(floating_pane_layout.pinned.unwrap_or(false));
                 resize_pty!(
                     new_pane,
                     self.os_api,
@@ -454,12 +472,18 @@ impl<'a> LayoutApplier<'a> {
                     self.styled_underlines,
                     self.explicitly_disable_kitty_keyboard_protocol,
                 );
+                new_pane.monitor.monitor_activity =
+                    floating_pane_layout.monitor_activity.unwrap_or(false);
+                new_pane.monitor.monitor_silence = floating_pane_layout
+                    .monitor_silence
+                    .map(Duration::from_secs);
                 if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
                     new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
                     new_pane.handle_pty_bytes("\n\r".as_bytes().into());
                 }
                 new_pane.set_borderless(false);
                 new_pane.set_content_offset(Offset::frame(1));
+                new_pane.set_pinned(floating_pane_layout.pinned.unwrap_or(false));
                 if let Some(held_command) = hold_for_command {
                     new_pane.hold(None, true, held_command.clone());
                 }
@@ -675,6 +699,9 @@ impl<'a> LayoutApplier<'a> {
         if let Some(pane_title) = floating_pane_layout.and_then(|f| f.name.clone()) {
             pane.set_title(pane_title);
         }
+        if let Some(pinned) = floating_pane_layout.and_then(|f| f.pinned) {
+            pane.set_pinned(pinned);
+        }
         pane.set_content_offset(Offset::frame(1));
     }
     fn total_space_for_tiled_panes(&self) -> PaneGeom {
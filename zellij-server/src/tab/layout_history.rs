@@ -0,0 +1,124 @@
+use crate::panes::PaneId;
+use std::collections::{BTreeMap, VecDeque};
+use zellij_utils::{
+    input::{command::RunCommand, layout::Run},
+    pane_size::PaneGeom,
+};
+
+use crate::tab::Pane;
+
+// how many layout changes can be undone in a single tab
+const MAX_LAYOUT_HISTORY: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaneSnapshot {
+    pub geom: PaneGeom,
+    pub run_command: Option<RunCommand>, // used to re-spawn command panes that were closed
+}
+
+impl PaneSnapshot {
+    pub fn new(pane: &Box<dyn Pane>) -> Self {
+        let run_command = match pane.invoked_with() {
+            Some(Run::Command(run_command)) => Some(run_command.clone()),
+            _ => None,
+        };
+        PaneSnapshot {
+            geom: pane.position_and_size(),
+            run_command,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutSnapshot {
+    pub tiled_panes: BTreeMap<PaneId, PaneSnapshot>,
+    pub floating_panes: BTreeMap<PaneId, PaneSnapshot>,
+    pub floating_panes_visible: bool,
+}
+
+impl LayoutSnapshot {
+    pub fn can_be_applied_over(&self, current: &LayoutSnapshot) -> bool {
+        // tiled panes must cover the whole tab, so their geometry can only be restored if they
+        // are the exact same panes as the current ones
+        let restores_tiled_panes = self.tiled_panes.len() == current.tiled_panes.len()
+            && self
+                .tiled_panes
+                .keys()
+                .all(|pane_id| current.tiled_panes.contains_key(pane_id))
+            && self.tiled_panes != current.tiled_panes;
+        let restores_floating_panes = self.floating_panes.iter().any(|(pane_id, pane)| {
+            current
+                .floating_panes
+                .get(pane_id)
+                .map(|current_pane| current_pane.geom != pane.geom)
+                .unwrap_or(false)
+        });
+        let restores_floating_panes_visibility = self.floating_panes_visible
+            != current.floating_panes_visible
+            && (!self.floating_panes_visible || !current.floating_panes.is_empty());
+        restores_tiled_panes
+            || restores_floating_panes
+            || restores_floating_panes_visibility
+            || self.has_closed_command_panes(current)
+    }
+    fn has_closed_command_panes(&self, current: &LayoutSnapshot) -> bool {
+        self.tiled_panes
+            .iter()
+            .chain(self.floating_panes.iter())
+            .any(|(pane_id, pane)| {
+                pane.run_command.is_some()
+                    && !current.tiled_panes.contains_key(pane_id)
+                    && !current.floating_panes.contains_key(pane_id)
+            })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LayoutHistory {
+    undo_stack: VecDeque<LayoutSnapshot>,
+    redo_stack: Vec<LayoutSnapshot>,
+}
+
+impl LayoutHistory {
+    pub fn record(&mut self, snapshot: LayoutSnapshot) {
+        if self.undo_stack.back() != Some(&snapshot) {
+            self.undo_stack.push_back(snapshot);
+            if self.undo_stack.len() > MAX_LAYOUT_HISTORY {
+                self.undo_stack.pop_front();
+            }
+        }
+        self.redo_stack.clear();
+    }
+    pub fn undo(&mut self, current: LayoutSnapshot) -> Option<LayoutSnapshot> {
+        // panes might have been opened or closed since a snapshot was taken, entries that can no
+        // longer change anything are dropped rather than being undone as a no-op
+        while let Some(previous) = self.undo_stack.pop_back() {
+            if previous.can_be_applied_over(&current) {
+                self.redo_stack.push(current);
+                return Some(previous);
+            }
+        }
+        None
+    }
+    pub fn redo(&mut self, current: LayoutSnapshot) -> Option<LayoutSnapshot> {
+        while let Some(next) = self.redo_stack.pop() {
+            if next.can_be_applied_over(&current) {
+                self.undo_stack.push_back(current);
+                return Some(next);
+            }
+        }
+        None
+    }
+    pub fn forget_run_command(&mut self, pane_id: PaneId) {
+        // once a closed command pane was re-spawned, we don't want to re-spawn it again when
+        // going further back in history
+        for snapshot in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            if let Some(pane) = snapshot.tiled_panes.get_mut(&pane_id) {
+                pane.run_command = None;
+            }
+            if let Some(pane) = snapshot.floating_panes.get_mut(&pane_id) {
+                pane.run_command = None;
+            }
+        }
+    }
+}
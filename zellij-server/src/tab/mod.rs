@@ -4,6 +4,7 @@
 mod clipboard;
 mod copy_command;
 mod layout_applier;
+mod layout_history;
 mod swap_layouts;
 
 use copy_command::CopyCommand;
@@ -24,6 +25,7 @@ use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::CopyOptions;
 use crate::ui::{loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams};
 use layout_applier::LayoutApplier;
+use layout_history::{LayoutHistory, LayoutSnapshot, PaneSnapshot};
 use swap_layouts::SwapLayouts;
 
 use self::clipboard::ClipboardProvider;
@@ -32,7 +34,7 @@ use crate::{
     output::{CharacterChunk, Output, SixelImageChunk},
     panes::sixel::SixelImageStore,
     panes::{FloatingPanes, TiledPanes},
-    panes::{LinkHandler, PaneId, PluginPane, TerminalPane},
+    panes::{LinkHandler, PaneId, PaneMonitor, PluginPane, TerminalPane},
     plugins::PluginInstruction,
     pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
     thread_bus::ThreadSenders,
@@ -51,8 +53,8 @@ use zellij_utils::{
     input::{
         command::TerminalAction,
         layout::{
-            FloatingPaneLayout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
-            TiledPaneLayout,
+            DynamicTiling, FloatingPaneLayout, Run, RunPluginOrAlias, SwapFloatingLayout,
+            SwapTiledLayout, TiledPaneLayout, TilingAlgorithm,
         },
         parse_keys,
     },
@@ -184,11 +186,15 @@ pub(crate) struct Tab {
     pending_instructions: Vec<BufferedTabInstruction>, // instructions that came while the tab was
     // pending and need to be re-applied
     swap_layouts: SwapLayouts,
+    layout_history: LayoutHistory,
+    layout_before_mouse_move: Option<LayoutSnapshot>, // recorded once the floating pane is dropped
+    action_targets: HashMap<ClientId, bool>,          // bool => the targeted pane is floating
     default_shell: Option<PathBuf>,
     debug: bool,
     arrow_fonts: bool,
     styled_underlines: bool,
     explicitly_disable_kitty_keyboard_protocol: bool,
+    alerts_changed: bool, // a pane alert was either raised or seen since last checked
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -361,6 +367,29 @@ pub trait Pane {
     fn drain_clipboard_update(&mut self) -> Option<String> {
         None
     }
+    fn drain_cwd_update(&mut self) -> Option<PathBuf> {
+        None
+    }
+    fn drain_notifications(&mut self) -> Vec<(Option<String>, String)> {
+        // (title, body)
+        vec![]
+    }
+    fn set_unseen_notification(&mut self, _has_unseen_notification: bool) {}
+    fn has_unseen_notification(&self) -> bool {
+        false
+    }
+    fn drain_bell(&mut self) -> bool {
+        false
+    }
+    fn monitor(&self) -> Option<&PaneMonitor> {
+        None
+    }
+    fn monitor_mut(&mut self) -> Option<&mut PaneMonitor> {
+        None
+    }
+    fn reported_cwd(&self) -> Option<PathBuf> {
+        None
+    }
     fn render_full_viewport(&mut self) {}
     fn relative_position(&self, position_on_screen: &Position) -> Position {
         position_on_screen.relative_to(self.get_content_y(), self.get_content_x())
@@ -398,6 +427,10 @@ pub trait Pane {
     fn borderless(&self) -> bool;
     fn set_exclude_from_sync(&mut self, exclude_from_sync: bool);
     fn exclude_from_sync(&self) -> bool;
+    fn set_pinned(&mut self, _pinned: bool) {}
+    fn is_pinned(&self) -> bool {
+        false
+    }
 
     // TODO: this should probably be merged with the mouse_right_click
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
@@ -434,6 +467,22 @@ pub trait Pane {
     fn get_line_number(&self) -> Option<usize> {
         None
     }
+    fn scroll_to_previous_prompt(&mut self) {
+        // No-op by default (only terminal-panes track semantic prompts)
+    }
+    fn scroll_to_next_prompt(&mut self) {
+        // No-op by default (only terminal-panes track semantic prompts)
+    }
+    fn select_command_output(&mut self) -> bool {
+        // only terminal-panes track semantic prompts
+        false
+    }
+    fn last_command_exit_code(&self) -> Option<i32> {
+        None
+    }
+    fn selected_command_exit_code(&self) -> Option<i32> {
+        None
+    }
     fn update_search_term(&mut self, _needle: &str) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -452,6 +501,9 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -632,11 +684,15 @@ impl Tab {
             is_pending: true, // will be switched to false once the layout is applied
             pending_instructions: vec![],
             swap_layouts,
+            layout_history: LayoutHistory::default(),
+            layout_before_mouse_move: None,
+            action_targets: HashMap::new(),
             default_shell,
             debug,
             arrow_fonts,
             styled_underlines,
             explicitly_disable_kitty_keyboard_protocol,
+            alerts_changed: false,
         }
     }
 
@@ -688,9 +744,17 @@ impl Tab {
         }
         self.tiled_panes.reapply_pane_frames();
         self.is_pending = false;
+        if self.monitors_silence() {
+            self.senders
+                .send_to_background_jobs(BackgroundJob::MonitorPaneSilence)
+                .with_context(|| "failed to monitor pane silence")?;
+        }
         self.apply_buffered_instructions()?;
         Ok(())
     }
+    pub fn dynamic_tiling(&self) -> Option<DynamicTiling> {
+        self.swap_layouts.dynamic_tiling()
+    }
     pub fn swap_layout_info(&self) -> (Option<String>, bool) {
         if self.floating_panes.panes_are_visible() {
             self.swap_layouts.floating_layout_info()
@@ -760,18 +824,22 @@ impl Tab {
         } else {
             refocus_pane
         };
-        if let Some(layout_candidate) = self
-            .swap_layouts
-            .swap_tiled_panes(&self.tiled_panes, search_backwards)
-            .or_else(|| {
-                if best_effort {
-                    self.swap_layouts
-                        .best_effort_tiled_layout(&self.tiled_panes)
-                } else {
-                    None
-                }
-            })
-        {
+        let layout_candidate = if self.swap_layouts.has_dynamic_tiling() {
+            self.swap_layouts.dynamic_tiled_layout(&self.tiled_panes)
+        } else {
+            self.swap_layouts
+                .swap_tiled_panes(&self.tiled_panes, search_backwards)
+                .or_else(|| {
+                    if best_effort {
+                        self.swap_layouts
+                            .best_effort_tiled_layout(&self.tiled_panes)
+                    } else {
+                        None
+                    }
+                })
+                .map(|layout_candidate| (layout_candidate, vec![]))
+        };
+        if let Some((layout_candidate, pane_order)) = layout_candidate {
             LayoutApplier::new(
                 &self.viewport,
                 &self.senders,
@@ -795,6 +863,7 @@ impl Tab {
             )
             .apply_tiled_panes_layout_to_existing_panes(
                 &layout_candidate,
+                &pane_order,
                 refocus_pane,
                 client_id,
             )?;
@@ -806,6 +875,45 @@ impl Tab {
         self.should_clear_display_before_rendering = true;
         Ok(())
     }
+    fn relayout_dynamic_tiling(&mut self, client_id: Option<ClientId>) -> Result<()> {
+        self.relayout_tiled_panes(client_id, false, false, false)?;
+        self.set_force_render();
+        self.senders
+            .send_to_pty_writer(PtyWriteInstruction::ApplyCachedResizes)
+            .with_context(|| format!("failed to update plugins with mode info"))?;
+        Ok(())
+    }
+    pub fn set_tiling(
+        &mut self,
+        algorithm: Option<TilingAlgorithm>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        self.swap_layouts.set_dynamic_tiling(algorithm);
+        if self.swap_layouts.has_dynamic_tiling() {
+            self.relayout_dynamic_tiling(Some(client_id))?;
+        }
+        Ok(())
+    }
+    pub fn promote_focused_pane_to_master(&mut self, client_id: ClientId) -> Result<()> {
+        if !self.swap_layouts.has_dynamic_tiling() || self.floating_panes_are_visible_to(client_id)
+        {
+            return Ok(());
+        }
+        if let Some(pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            self.swap_layouts
+                .promote_to_master(pane_id, &self.tiled_panes);
+            self.relayout_dynamic_tiling(Some(client_id))?;
+        }
+        Ok(())
+    }
+    pub fn change_master_count(&mut self, change_by: isize, client_id: ClientId) -> Result<()> {
+        if !self.swap_layouts.has_dynamic_tiling() {
+            return Ok(());
+        }
+        self.swap_layouts
+            .change_master_count(change_by, &self.tiled_panes);
+        self.relayout_dynamic_tiling(Some(client_id))
+    }
     pub fn previous_swap_layout(&mut self, client_id: Option<ClientId>) -> Result<()> {
         let search_backwards = true;
         if self.floating_panes.panes_are_visible() {
@@ -978,7 +1086,7 @@ impl Tab {
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             if let Some(focused_floating_pane_id) = self.floating_panes.active_pane_id(client_id) {
                 if self.tiled_panes.has_room_for_new_pane() {
                     let floating_pane_to_embed = self
@@ -1014,12 +1122,189 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn toggle_pane_pinned(&mut self, client_id: ClientId) -> Result<()> {
+        // only floating panes can be pinned, and only while they are visible (or a pinned pane is
+        // focused) so that the user can see which pane they are pinning
+        if self.floating_panes_are_visible_to(client_id) {
+            self.floating_panes.toggle_pane_pinned(client_id);
+            self.set_force_render();
+        }
+        Ok(())
+    }
+    pub fn stack_active_pane(&mut self, client_id: ClientId, direction: Direction) -> Result<()> {
+        // only tiled panes can be stacked
+        if self.floating_panes_are_visible_to(client_id) {
+            return Ok(());
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes
+            .stack_active_pane_in_direction(client_id, direction)
+            .non_fatal();
+        self.swap_layouts.set_is_tiled_damaged();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn unstack_active_pane(&mut self, client_id: ClientId) -> Result<()> {
+        if self.floating_panes_are_visible_to(client_id) {
+            return Ok(());
+        }
+        if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            if self.tiled_panes.fullscreen_is_active() {
+                self.tiled_panes.unset_fullscreen();
+            }
+            self.tiled_panes.unstack_pane(active_pane_id).non_fatal();
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_force_render();
+        }
+        Ok(())
+    }
+    pub fn stack_all_panes(&mut self, client_id: ClientId) -> Result<()> {
+        if self.floating_panes_are_visible_to(client_id) {
+            return Ok(());
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.stack_all_panes(client_id).non_fatal();
+        self.swap_layouts.set_is_tiled_damaged();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn with_layout_history<T>(&mut self, change_layout: impl FnOnce(&mut Tab) -> T) -> T {
+        // changes that leave the layout untouched are not recorded, so that undoing always does
+        // something visible
+        let layout_before_change = self.layout_snapshot();
+        let result = change_layout(self);
+        if self.layout_snapshot() != layout_before_change {
+            self.layout_history.record(layout_before_change);
+        }
+        result
+    }
+    pub fn undo_layout_change(&mut self, client_id: ClientId) -> Result<()> {
+        let current_layout = self.layout_snapshot();
+        match self.layout_history.undo(current_layout) {
+            Some(previous_layout) => self.apply_layout_snapshot(previous_layout, client_id),
+            None => Ok(()),
+        }
+    }
+    pub fn redo_layout_change(&mut self, client_id: ClientId) -> Result<()> {
+        let current_layout = self.layout_snapshot();
+        match self.layout_history.redo(current_layout) {
+            Some(next_layout) => self.apply_layout_snapshot(next_layout, client_id),
+            None => Ok(()),
+        }
+    }
+    fn layout_snapshot(&self) -> LayoutSnapshot {
+        LayoutSnapshot {
+            tiled_panes: self
+                .tiled_panes
+                .get_panes()
+                .map(|(pane_id, pane)| (*pane_id, PaneSnapshot::new(pane)))
+                .collect(),
+            floating_panes: self
+                .floating_panes
+                .get_panes()
+                .map(|(pane_id, pane)| (*pane_id, PaneSnapshot::new(pane)))
+                .collect(),
+            floating_panes_visible: self.floating_panes.panes_are_visible(),
+        }
+    }
+    fn apply_layout_snapshot(
+        &mut self,
+        snapshot: LayoutSnapshot,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to restore layout for client {client_id}");
+        // tiled panes must cover the whole tab, so we can only restore their geometry if they are
+        // the exact same panes as the ones in the snapshot
+        let current_tiled_pane_ids: HashSet<&PaneId> = self.tiled_panes.pane_ids().collect();
+        let snapshot_tiled_pane_ids: HashSet<&PaneId> = snapshot.tiled_panes.keys().collect();
+        if current_tiled_pane_ids == snapshot_tiled_pane_ids {
+            if self.tiled_panes.fullscreen_is_active() {
+                self.tiled_panes.unset_fullscreen();
+            }
+            for (pane_id, pane_snapshot) in &snapshot.tiled_panes {
+                if let Some(pane) = self.tiled_panes.get_pane_mut(*pane_id) {
+                    pane.set_geom(pane_snapshot.geom);
+                    resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                        .with_context(err_context)?;
+                }
+            }
+            self.tiled_panes.reapply_pane_frames();
+            self.swap_layouts.set_is_tiled_damaged();
+        }
+        for (pane_id, pane_snapshot) in &snapshot.floating_panes {
+            if self.floating_panes.get_pane(*pane_id).is_some() {
+                self.floating_panes
+                    .set_pane_geom(*pane_id, pane_snapshot.geom)
+                    .with_context(err_context)?;
+                self.swap_layouts.set_is_floating_damaged();
+            }
+        }
+        if snapshot.floating_panes_visible
+            && !self.floating_panes.panes_are_visible()
+            && self.floating_panes.has_panes()
+        {
+            self.show_floating_panes();
+        } else if !snapshot.floating_panes_visible && self.floating_panes.panes_are_visible() {
+            self.hide_floating_panes();
+        }
+        self.respawn_closed_command_panes(&snapshot, client_id)
+            .with_context(err_context)?;
+        self.set_force_render();
+        Ok(())
+    }
+    fn respawn_closed_command_panes(
+        &mut self,
+        snapshot: &LayoutSnapshot,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let existing_pane_ids = self.get_all_pane_ids();
+        let tiled_panes = snapshot.tiled_panes.iter().map(|(id, p)| (id, p, false));
+        let floating_panes = snapshot.floating_panes.iter().map(|(id, p)| (id, p, true));
+        for (pane_id, pane_snapshot, should_float) in tiled_panes.chain(floating_panes) {
+            if existing_pane_ids.contains(pane_id) {
+                continue;
+            }
+            if let Some(mut run_command) = pane_snapshot.run_command.clone() {
+                // closed panes cannot be brought back, so we open a new pane with their command
+                // held until the user chooses to run it again
+                run_command.hold_on_start = true;
+                let floating_pane_coordinates = if should_float {
+                    Some(
+                        FloatingPaneCoordinates::default()
+                            .with_x_fixed(pane_snapshot.geom.x)
+                            .with_y_fixed(pane_snapshot.geom.y)
+                            .with_width_fixed(pane_snapshot.geom.cols.as_usize())
+                            .with_height_fixed(pane_snapshot.geom.rows.as_usize()),
+                    )
+                } else {
+                    None
+                };
+                self.senders
+                    .send_to_pty(PtyInstruction::SpawnTerminal(
+                        Some(TerminalAction::RunCommand(run_command)),
+                        Some(should_float),
+                        None,
+                        floating_pane_coordinates,
+                        ClientTabIndexOrPaneId::ClientId(client_id),
+                    ))
+                    .with_context(|| format!("failed to re-spawn closed pane {pane_id:?}"))?;
+                self.layout_history.forget_run_command(*pane_id);
+            }
+        }
+        Ok(())
+    }
     pub fn toggle_floating_panes(
         &mut self,
         client_id: Option<ClientId>,
         default_shell: Option<TerminalAction>,
     ) -> Result<()> {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes.panes_are_visible()
+            && !self.floating_panes.is_showing_pinned_panes_only()
+        {
             self.hide_floating_panes();
             self.set_force_render();
         } else {
@@ -1131,15 +1416,15 @@ impl Tab {
             self.add_tiled_pane(new_pane, pid, client_id)
         }
     }
-    pub fn replace_active_pane_with_editor_pane(
+    pub fn replace_pane_with_editor_pane(
         &mut self,
         pid: PaneId,
-        client_id: ClientId,
+        replaced_pane_id: PaneId,
     ) -> Result<()> {
-        // this method creates a new pane from pid and replaces it with the active pane
-        // the active pane is then suppressed (hidden and not rendered) until the current
+        // this method creates a new pane from pid and replaces the pane whose scrollback it edits
+        // with it, the replaced pane is then suppressed (hidden and not rendered) until the
         // created pane is closed, in which case it will be replaced back by it
-        let err_context = || format!("failed to suppress active pane for client {client_id}");
+        let err_context = || format!("failed to suppress pane {replaced_pane_id:?}");
 
         match pid {
             PaneId::Terminal(pid) => {
@@ -1165,24 +1450,27 @@ impl Tab {
                 new_pane.update_name("EDITING SCROLLBACK"); // we do this here and not in the
                                                             // constructor so it won't be overrided
                                                             // by the editor
-                let replaced_pane = if self.floating_panes.panes_are_visible() {
+                let replaced_pane = if self.floating_panes.panes_contain(&replaced_pane_id) {
                     self.floating_panes
-                        .replace_active_pane(Box::new(new_pane), client_id)
+                        .replace_pane(replaced_pane_id, Box::new(new_pane))
                         .ok()
                 } else {
                     self.tiled_panes
-                        .replace_active_pane(Box::new(new_pane), client_id)
+                        .replace_pane(replaced_pane_id, Box::new(new_pane))
                 };
                 match replaced_pane {
                     Some(replaced_pane) => {
                         let is_scrollback_editor = true;
                         self.suppressed_panes
                             .insert(PaneId::Terminal(pid), (is_scrollback_editor, replaced_pane));
-                        self.get_active_pane(client_id)
-                            .with_context(|| format!("no active pane found for client {client_id}"))
-                            .and_then(|current_active_pane| {
+                        let editor_pane_id = PaneId::Terminal(pid);
+                        self.tiled_panes
+                            .get_pane(editor_pane_id)
+                            .or_else(|| self.floating_panes.get_pane(editor_pane_id))
+                            .with_context(|| format!("no editor pane found with id {pid}"))
+                            .and_then(|editor_pane| {
                                 resize_pty!(
-                                    current_active_pane,
+                                    editor_pane,
                                     self.os_api,
                                     self.senders,
                                     self.character_cell_size
@@ -1192,7 +1480,8 @@ impl Tab {
                     },
                     None => {
                         Err::<(), _>(anyhow!(
-                            "Could not find editor pane to replace - is no pane focused?"
+                            "Could not find pane {:?} to replace with the editor pane",
+                            replaced_pane_id
                         ))
                         .with_context(err_context)
                         .non_fatal();
@@ -1448,7 +1737,7 @@ impl Tab {
 
     pub fn get_active_pane(&self, client_id: ClientId) -> Option<&dyn Pane> {
         self.get_active_pane_id(client_id).and_then(|ap| {
-            if self.floating_panes.panes_are_visible() {
+            if self.floating_panes_are_visible_to(client_id) {
                 self.floating_panes.get_pane(ap).map(Box::as_ref)
             } else {
                 self.tiled_panes.get_pane(ap).map(Box::as_ref)
@@ -1457,7 +1746,7 @@ impl Tab {
     }
     pub fn get_active_pane_mut(&mut self, client_id: ClientId) -> Option<&mut Box<dyn Pane>> {
         self.get_active_pane_id(client_id).and_then(|ap| {
-            if self.floating_panes.panes_are_visible() {
+            if self.floating_panes_are_visible_to(client_id) {
                 self.floating_panes.get_pane_mut(ap)
             } else {
                 self.tiled_panes.get_pane_mut(ap)
@@ -1468,14 +1757,14 @@ impl Tab {
         &mut self,
         client_id: ClientId,
     ) -> Option<&mut Box<dyn Pane>> {
-        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
+        if self.floating_panes_are_visible_to(client_id) && self.floating_panes.has_active_panes() {
             self.floating_panes.get_active_pane_mut(client_id)
         } else {
             self.get_active_pane_mut(client_id)
         }
     }
     pub fn get_active_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.get_active_pane_id(client_id)
         } else {
             self.tiled_panes.get_active_pane_id(client_id)
@@ -1506,6 +1795,48 @@ impl Tab {
                 .values()
                 .any(|s_p| s_p.1.pid() == PaneId::Plugin(plugin_id))
     }
+    /// Make the actions of this client apply to the given pane, or to the focused pane of this tab
+    /// if there is none, without moving the focus of any other client
+    pub fn set_action_target(
+        &mut self,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let pane_id = match pane_id {
+            Some(pane_id) => pane_id,
+            None if self.floating_panes.panes_are_visible() => self
+                .floating_panes
+                .first_active_floating_pane_id()
+                .context("no focused floating pane in tab")?,
+            None => self
+                .tiled_panes
+                .any_active_pane_id()
+                .context("no focused pane in tab")?,
+        };
+        // the pane is targeted in its own layer, whether or not the floating panes are visible
+        if self.floating_panes.panes_contain(&pane_id) {
+            self.floating_panes.set_action_target(pane_id, client_id);
+            self.action_targets.insert(client_id, true);
+        } else if self.tiled_panes.panes_contain(&pane_id) {
+            self.tiled_panes.set_action_target(pane_id, client_id);
+            self.action_targets.insert(client_id, false);
+        } else {
+            return Err(anyhow!("Pane {:?} is not visible", pane_id));
+        }
+        Ok(())
+    }
+    pub fn clear_action_target(&mut self, client_id: ClientId) {
+        self.tiled_panes.clear_action_target(client_id);
+        self.floating_panes.clear_action_target(client_id);
+        self.action_targets.remove(&client_id);
+    }
+    // whether the actions of this client apply to the floating panes rather than to the tiled ones
+    fn floating_panes_are_visible_to(&self, client_id: ClientId) -> bool {
+        self.action_targets
+            .get(&client_id)
+            .copied()
+            .unwrap_or_else(|| self.floating_panes.panes_are_visible())
+    }
     pub fn has_pane_with_pid(&self, pid: &PaneId) -> bool {
         self.tiled_panes.panes_contain(pid)
             || self.floating_panes.panes_contain(pid)
@@ -1584,6 +1915,8 @@ impl Tab {
     }
     fn process_pty_bytes(&mut self, pid: u32, bytes: VteBytes) -> Result<()> {
         let err_context = || format!("failed to process pty bytes from pid {pid}");
+        let mut notifications = vec![];
+        let mut rang_bell = None;
 
         if let Some(terminal_output) = self
             .tiled_panes
@@ -1608,6 +1941,13 @@ impl Tab {
             terminal_output.handle_pty_bytes(bytes);
             let messages_to_pty = terminal_output.drain_messages_to_pty();
             let clipboard_update = terminal_output.drain_clipboard_update();
+            notifications = terminal_output.drain_notifications();
+            rang_bell = Some(terminal_output.drain_bell());
+            if let Some(cwd) = terminal_output.drain_cwd_update() {
+                self.senders
+                    .send_to_pty(PtyInstruction::ReportTerminalCwd(pid, cwd))
+                    .with_context(err_context)?;
+            }
             for message in messages_to_pty {
                 self.write_to_pane_id_without_preprocessing(message, PaneId::Terminal(pid))
                     .with_context(err_context)?;
@@ -1617,8 +1957,143 @@ impl Tab {
                     .with_context(err_context)?;
             }
         }
+        if let Some(rang_bell) = rang_bell {
+            self.record_pane_output(pid, rang_bell);
+        }
+        if !notifications.is_empty() {
+            self.handle_pane_notifications(pid, notifications)
+                .with_context(err_context)?;
+        }
         Ok(())
     }
+    fn pane_is_focused(&self, pane_id: PaneId) -> bool {
+        self.connected_clients
+            .borrow()
+            .iter()
+            .any(|client_id| self.get_active_pane_id(*client_id) == Some(pane_id))
+    }
+    pub fn get_pane_with_id_mut(&mut self, pane_id: PaneId) -> Option<&mut Box<dyn Pane>> {
+        self.tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+    }
+    fn record_pane_output(&mut self, pid: u32, rang_bell: bool) {
+        let pane_id = PaneId::Terminal(pid);
+        let pane_is_focused = self.pane_is_focused(pane_id);
+        let alerts_changed = self
+            .get_pane_with_id_mut(pane_id)
+            .and_then(|pane| pane.monitor_mut())
+            .map(|monitor| monitor.record_output(rang_bell, pane_is_focused))
+            .unwrap_or(false);
+        if alerts_changed {
+            self.alerts_changed = true;
+        }
+    }
+    fn handle_pane_notifications(
+        &mut self,
+        pid: u32,
+        notifications: Vec<(Option<String>, String)>,
+    ) -> Result<()> {
+        let pane_id = PaneId::Terminal(pid);
+        if !self.pane_is_focused(pane_id) {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.set_unseen_notification(true);
+                self.alerts_changed = true;
+            }
+        }
+        let plugin_updates = notifications
+            .into_iter()
+            .map(|(title, body)| {
+                (
+                    None,
+                    None,
+                    Event::PaneNotification {
+                        pane_id: pid,
+                        title,
+                        body,
+                    },
+                )
+            })
+            .collect();
+        self.senders
+            .send_to_plugin(PluginInstruction::Update(plugin_updates))
+            .with_context(|| format!("failed to send notifications of pane {pid} to plugins"))
+    }
+    fn clear_alerts_of_focused_panes(&mut self) {
+        let connected_clients: Vec<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        let mut cleared_alert = false;
+        for client_id in connected_clients {
+            if let Some(active_pane) = self.get_active_pane_mut(client_id) {
+                if active_pane.has_unseen_notification() {
+                    active_pane.set_unseen_notification(false);
+                    cleared_alert = true;
+                }
+                if let Some(monitor) = active_pane.monitor_mut() {
+                    cleared_alert |= monitor.clear();
+                }
+            }
+        }
+        if cleared_alert {
+            self.alerts_changed = true;
+        }
+    }
+    pub fn check_pane_silence(&mut self) -> bool {
+        // returns true if a pane became silent
+        let pane_ids: Vec<PaneId> = self
+            .get_tiled_panes()
+            .chain(self.get_floating_panes())
+            .map(|(pane_id, _)| *pane_id)
+            .chain(self.get_suppressed_panes().map(|(_, (_, pane))| pane.pid()))
+            .collect();
+        let mut became_silent = false;
+        for pane_id in pane_ids {
+            let pane_is_focused = self.pane_is_focused(pane_id);
+            if let Some(monitor) = self
+                .get_pane_with_id_mut(pane_id)
+                .and_then(|pane| pane.monitor_mut())
+            {
+                became_silent |= monitor.check_silence(pane_is_focused);
+            }
+        }
+        became_silent
+    }
+    pub fn monitors_silence(&self) -> bool {
+        self.has_pane_matching(|pane| {
+            pane.monitor()
+                .map(|monitor| monitor.monitor_silence.is_some())
+                .unwrap_or(false)
+        })
+    }
+    fn has_pane_matching(&self, predicate: impl Fn(&Box<dyn Pane>) -> bool) -> bool {
+        self.get_tiled_panes()
+            .chain(self.get_floating_panes())
+            .any(|(_, pane)| predicate(pane))
+            || self
+                .get_suppressed_panes()
+                .any(|(_, (_, pane))| predicate(pane))
+    }
+    pub fn has_unseen_notification(&self) -> bool {
+        self.has_pane_matching(|pane| pane.has_unseen_notification())
+    }
+    pub fn has_bell(&self) -> bool {
+        self.has_pane_matching(|pane| pane.monitor().map(|m| m.has_bell).unwrap_or(false))
+    }
+    pub fn has_activity(&self) -> bool {
+        self.has_pane_matching(|pane| pane.monitor().map(|m| m.has_activity).unwrap_or(false))
+    }
+    pub fn has_silence(&self) -> bool {
+        self.has_pane_matching(|pane| pane.monitor().map(|m| m.has_silence).unwrap_or(false))
+    }
+    pub fn take_alerts_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.alerts_changed, false)
+    }
 
     pub fn write_to_terminals_on_current_tab(
         &mut self,
@@ -1664,7 +2139,7 @@ impl Tab {
         };
 
         self.clear_search(client_id); // this is an inexpensive operation if empty, if we need more such cleanups we should consider moving this and the rest to some sort of cleanup method
-        let pane_id = if self.floating_panes.panes_are_visible() {
+        let pane_id = if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .get_active_pane_id(client_id)
                 .or_else(|| self.tiled_panes.get_active_pane_id(client_id))
@@ -1872,7 +2347,7 @@ impl Tab {
         client_id: ClientId,
     ) -> Option<(usize, usize)> {
         // (x, y)
-        let active_pane_id = if self.floating_panes.panes_are_visible() {
+        let active_pane_id = if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .get_active_pane_id(client_id)
                 .or_else(|| self.tiled_panes.get_active_pane_id(client_id))?
@@ -1892,7 +2367,7 @@ impl Tab {
             })
     }
     pub fn toggle_active_pane_fullscreen(&mut self, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             return;
         }
         self.tiled_panes.toggle_active_pane_fullscreen(client_id);
@@ -1984,6 +2459,7 @@ impl Tab {
         }
         self.update_active_panes_in_pty_thread()
             .with_context(err_context)?;
+        self.clear_alerts_of_focused_panes();
 
         let floating_panes_stack = self.floating_panes.stack();
         output.add_clients(
@@ -1995,7 +2471,9 @@ impl Tab {
         self.tiled_panes
             .render(output, self.floating_panes.panes_are_visible())
             .with_context(err_context)?;
-        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
+        if (self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes())
+            || self.floating_panes.has_pinned_panes()
+        {
             self.floating_panes
                 .render(output)
                 .with_context(err_context)?;
@@ -2081,6 +2559,15 @@ impl Tab {
         // bool => is_scrollback_editor
         self.suppressed_panes.iter()
     }
+    pub fn extract_suppressed_panes(&mut self) -> HashMap<PaneId, (bool, Box<dyn Pane>)> {
+        self.suppressed_panes.drain().collect()
+    }
+    pub fn add_suppressed_panes(
+        &mut self,
+        suppressed_panes: HashMap<PaneId, (bool, Box<dyn Pane>)>,
+    ) {
+        self.suppressed_panes.extend(suppressed_panes);
+    }
     fn get_selectable_tiled_panes(&self) -> impl Iterator<Item = (&PaneId, &Box<dyn Pane>)> {
         self.get_tiled_panes().filter(|(_, p)| p.selectable())
     }
@@ -2157,7 +2644,7 @@ impl Tab {
         let err_context = || format!("unable to resize pane");
         self.swap_layouts.set_is_floating_damaged();
         self.swap_layouts.set_is_tiled_damaged();
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             let successfully_resized = self
                 .floating_panes
                 .resize_active_pane(client_id, &mut self.os_api, &strategy)
@@ -2219,7 +2706,7 @@ impl Tab {
         self.tiled_panes.focus_previous_pane(client_id);
     }
     pub fn focus_pane_on_edge(&mut self, direction: Direction, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.focus_pane_on_edge(direction, client_id);
         } else if self.has_selectable_panes() && !self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.focus_pane_on_edge(direction, client_id);
@@ -2229,7 +2716,7 @@ impl Tab {
     pub fn move_focus_left(&mut self, client_id: ClientId) -> Result<bool> {
         let err_context = || format!("failed to move focus left for client {}", client_id);
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_focus(
                     client_id,
@@ -2251,7 +2738,7 @@ impl Tab {
     pub fn move_focus_down(&mut self, client_id: ClientId) -> Result<bool> {
         let err_context = || format!("failed to move focus down for client {}", client_id);
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_focus(
                     client_id,
@@ -2273,7 +2760,7 @@ impl Tab {
     pub fn move_focus_up(&mut self, client_id: ClientId) -> Result<bool> {
         let err_context = || format!("failed to move focus up for client {}", client_id);
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_focus(
                     client_id,
@@ -2296,7 +2783,7 @@ impl Tab {
     pub fn move_focus_right(&mut self, client_id: ClientId) -> Result<bool> {
         let err_context = || format!("failed to move focus right for client {}", client_id);
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_focus(
                     client_id,
@@ -2323,7 +2810,7 @@ impl Tab {
             return;
         }
         let search_backwards = false;
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_active_pane(search_backwards, &mut self.os_api, client_id);
         } else {
@@ -2339,7 +2826,7 @@ impl Tab {
             return;
         }
         let search_backwards = true;
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes
                 .move_active_pane(search_backwards, &mut self.os_api, client_id);
         } else {
@@ -2348,7 +2835,7 @@ impl Tab {
         }
     }
     pub fn move_active_pane_down(&mut self, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.move_active_pane_down(client_id);
             self.swap_layouts.set_is_floating_damaged();
             self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" behind
@@ -2363,7 +2850,7 @@ impl Tab {
         }
     }
     pub fn move_active_pane_up(&mut self, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.move_active_pane_up(client_id);
             self.swap_layouts.set_is_floating_damaged();
             self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" behind
@@ -2378,7 +2865,7 @@ impl Tab {
         }
     }
     pub fn move_active_pane_right(&mut self, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.move_active_pane_right(client_id);
             self.swap_layouts.set_is_floating_damaged();
             self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" behind
@@ -2393,7 +2880,7 @@ impl Tab {
         }
     }
     pub fn move_active_pane_left(&mut self, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             self.floating_panes.move_active_pane_left(client_id);
             self.swap_layouts.set_is_floating_damaged();
             self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" behind
@@ -2422,6 +2909,17 @@ impl Tab {
     pub fn get_tiled_pane_ids(&self) -> Vec<PaneId> {
         self.get_tiled_panes().map(|(&pid, _)| pid).collect()
     }
+    pub fn get_selectable_tiled_pane_ids(&self) -> Vec<PaneId> {
+        self.get_selectable_tiled_panes()
+            .map(|(&pid, _)| pid)
+            .collect()
+    }
+    pub fn get_floating_pane_ids(&self) -> Vec<PaneId> {
+        self.get_floating_panes().map(|(&pid, _)| pid).collect()
+    }
+    pub fn has_room_for_new_tiled_pane(&mut self) -> bool {
+        self.tiled_panes.has_room_for_new_pane()
+    }
     pub fn get_all_pane_ids(&self) -> Vec<PaneId> {
         // this is here just as a naming thing to make things more explicit
         self.get_static_and_floating_pane_ids()
@@ -2512,7 +3010,9 @@ impl Tab {
             let closed_pane = self.tiled_panes.remove_pane(id);
             self.set_force_render();
             self.tiled_panes.set_force_render();
-            if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
+            if self.swap_layouts.has_dynamic_tiling() {
+                self.relayout_dynamic_tiling(client_id).non_fatal();
+            } else if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
                 self.swap_layouts.set_is_tiled_damaged();
                 // only relayout if the user is already "in" a layout, otherwise this might be
                 // confusing
@@ -2551,7 +3051,9 @@ impl Tab {
             let closed_pane = self.tiled_panes.remove_pane(id);
             self.set_force_render();
             self.tiled_panes.set_force_render();
-            if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
+            if self.swap_layouts.has_dynamic_tiling() {
+                self.relayout_dynamic_tiling(client_id).non_fatal();
+            } else if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
                 self.swap_layouts.set_is_tiled_damaged();
                 // only relayout if the user is already "in" a layout, otherwise this might be
                 // confusing
@@ -2642,7 +3144,7 @@ impl Tab {
             format!("failed to close focused pane (ID {pane_id:?}) for client {client_id}")
         };
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes_are_visible_to(client_id) {
             if let Some(active_floating_pane_id) = self.floating_panes.active_pane_id(client_id) {
                 self.close_pane(active_floating_pane_id, false, Some(client_id));
                 self.senders
@@ -2693,13 +3195,18 @@ impl Tab {
             true,
         )
         .with_context(err_context)?;
-        let line_number = self
-            .get_active_pane(client_id)
-            .and_then(|a_t| a_t.get_line_number());
+        // the editor replaces the pane whose scrollback was dumped, even if it's no longer the
+        // active one once the editor is opened
+        let (replaced_pane_id, line_number) =
+            match self.get_active_pane_or_floating_pane_mut(client_id) {
+                Some(active_pane) => (active_pane.pid(), active_pane.get_line_number()),
+                None => return Ok(()),
+            };
         self.senders
             .send_to_pty(PtyInstruction::OpenInPlaceEditor(
                 file,
                 line_number,
+                replaced_pane_id,
                 client_id,
             ))
             .with_context(err_context)
@@ -2801,6 +3308,29 @@ impl Tab {
         Ok(())
     }
 
+    pub fn scroll_active_terminal_to_previous_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_previous_prompt();
+        }
+        Ok(())
+    }
+
+    pub fn scroll_active_terminal_to_next_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll to next prompt in active pane for client {client_id}");
+
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_next_prompt();
+            if !active_pane.is_scrolled() {
+                if let PaneId::Terminal(raw_fd) = active_pane.pid() {
+                    self.process_pending_vte_events(raw_fd)
+                        .with_context(err_context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) -> Result<()> {
         // TODO: is this a thing?
         let err_context =
@@ -2891,7 +3421,7 @@ impl Tab {
     ) -> Result<Option<&mut Box<dyn Pane>>> {
         let err_context = || format!("failed to get pane at position {point:?}");
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes.panes_are_visible() || self.floating_panes.has_pinned_panes() {
             if let Some(pane_id) = self
                 .floating_panes
                 .get_pane_id_at(point, search_selectable)
@@ -2952,11 +3482,13 @@ impl Tab {
             .with_context(err_context)?;
 
         let search_selectable = false;
+        let layout_before_mouse_move = self.layout_snapshot();
         if self.floating_panes.panes_are_visible()
             && self
                 .floating_panes
                 .move_pane_with_mouse(*position, search_selectable)
         {
+            self.layout_before_mouse_move = Some(layout_before_mouse_move);
             self.swap_layouts.set_is_floating_damaged();
             self.set_force_render();
             return Ok(());
@@ -3053,12 +3585,19 @@ impl Tab {
         let err_context =
             || format!("failed to focus pane at position {point:?} for client {client_id}");
 
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes.panes_are_visible() || self.floating_panes.has_pinned_panes() {
             if let Some(clicked_pane) = self
                 .floating_panes
                 .get_pane_id_at(point, true)
                 .with_context(err_context)?
             {
+                if !self.floating_panes.panes_are_visible() {
+                    // clicking a pinned pane focuses it without bringing back the rest of the
+                    // floating panes
+                    self.floating_panes.show_pinned_panes();
+                    self.tiled_panes.unfocus_all_panes();
+                    self.set_force_render();
+                }
                 self.floating_panes.focus_pane(clicked_pane, client_id);
                 self.set_pane_active_at(clicked_pane);
                 return Ok(());
@@ -3156,6 +3695,11 @@ impl Tab {
             && self.floating_panes.pane_is_being_moved_with_mouse()
         {
             self.floating_panes.stop_moving_pane_with_mouse(*position);
+            if let Some(layout_before_mouse_move) = self.layout_before_mouse_move.take() {
+                if self.layout_snapshot() != layout_before_mouse_move {
+                    self.layout_history.record(layout_before_mouse_move);
+                }
+            }
             return Ok(());
         }
 
@@ -3396,6 +3940,18 @@ impl Tab {
         Ok(())
     }
 
+    pub fn copy_command_output(&mut self, client_id: ClientId) -> Result<()> {
+        let found_command_output = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.select_command_output())
+            .unwrap_or(false);
+        if found_command_output {
+            self.copy_selection(client_id)
+                .with_context(|| format!("failed to copy command output for client {client_id}"))?;
+        }
+        Ok(())
+    }
+
     fn write_selection_to_clipboard(&self, selection: &str) -> Result<()> {
         let err_context = || format!("failed to write selection to clipboard: '{}'", selection);
 
@@ -3589,6 +4145,12 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
@@ -3800,6 +4362,20 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn change_floating_pane_coordinates(
+        &mut self,
+        pane_id: &PaneId,
+        floating_pane_coordinates: FloatingPaneCoordinates,
+    ) -> Result<()> {
+        if !self.floating_panes.panes_contain(pane_id) {
+            return Err(anyhow!("Pane {:?} is not a floating pane", pane_id));
+        }
+        self.floating_panes
+            .change_pane_coordinates(*pane_id, floating_pane_coordinates)?;
+        self.swap_layouts.set_is_floating_damaged();
+        self.set_force_render();
+        Ok(())
+    }
     pub fn add_tiled_pane(
         &mut self,
         mut pane: Box<dyn Pane>,
@@ -3809,10 +4385,13 @@ impl Tab {
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
+        // only floating panes can be pinned
+        pane.set_pinned(false);
         let should_auto_layout = self.auto_layout && !self.swap_layouts.is_tiled_damaged();
+        let has_dynamic_tiling = self.swap_layouts.has_dynamic_tiling();
         if self.tiled_panes.has_room_for_new_pane() {
             pane.set_active_at(Instant::now());
-            if should_auto_layout {
+            if should_auto_layout && !has_dynamic_tiling {
                 // no need to relayout here, we'll do it when reapplying the swap layout
                 // below
                 self.tiled_panes.insert_pane_without_relayout(pane_id, pane);
@@ -3824,7 +4403,9 @@ impl Tab {
                 self.tiled_panes.focus_pane(pane_id, client_id);
             }
         }
-        if should_auto_layout {
+        if has_dynamic_tiling {
+            self.relayout_dynamic_tiling(client_id)?;
+        } else if should_auto_layout {
             // only do this if we're already in this layout, otherwise it might be
             // confusing and not what the user intends
             self.swap_layouts.set_is_tiled_damaged(); // we do this so that we won't skip to the
@@ -3865,7 +4446,16 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info.title = pane.current_title();
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
+    pane_info.last_command_exit_code = pane.last_command_exit_code();
+    pane_info.selected_command_exit_code = pane.selected_command_exit_code();
+    pane_info.cwd = pane.reported_cwd();
+    if let Some(monitor) = pane.monitor() {
+        pane_info.has_bell = monitor.has_bell;
+        pane_info.has_activity = monitor.has_activity;
+        pane_info.has_silence = monitor.has_silence;
+    }
     pane_info.is_held = pane.is_held();
+    pane_info.is_pinned = pane.is_pinned();
 
     match pane_id {
         PaneId::Terminal(terminal_id) => {
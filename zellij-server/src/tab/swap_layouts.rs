@@ -1,10 +1,11 @@
-use crate::panes::{FloatingPanes, TiledPanes};
+use crate::panes::{FloatingPanes, PaneId, TiledPanes};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 use zellij_utils::{
     input::layout::{
-        FloatingPaneLayout, LayoutConstraint, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+        DynamicTiling, FloatingPaneLayout, LayoutConstraint, Run, SplitSize, SwapFloatingLayout,
+        SwapTiledLayout, TiledPaneLayout, TilingAlgorithm,
     },
     pane_size::{PaneGeom, Size},
 };
@@ -18,6 +19,9 @@ pub struct SwapLayouts {
     is_floating_damaged: bool,
     is_tiled_damaged: bool,
     display_area: Rc<RefCell<Size>>, // includes all panes (including eg. the status bar and tab bar in the default layout)
+    base_tiled_layout: Option<TiledPaneLayout>,
+    dynamic_tiling: Option<DynamicTiling>,
+    dynamic_tiling_order: Vec<PaneId>, // master panes first
 }
 
 impl SwapLayouts {
@@ -40,6 +44,8 @@ impl SwapLayouts {
         let mut base_swap_floating_layout = BTreeMap::new();
         let tiled_panes_count = layout.0.pane_count();
         let floating_panes_count = layout.1.len();
+        self.dynamic_tiling = layout.0.dynamic_tiling;
+        self.base_tiled_layout = Some(layout.0.clone());
         // we set ExactPanes to the current panes in the layout, because the base layout is not
         // intended to be progressive - i.e. to have additional panes added to it
         // we also don't want it to be applied for less than the expected amount of panes, because
@@ -69,6 +75,9 @@ impl SwapLayouts {
     }
     pub fn tiled_layout_info(&self) -> (Option<String>, bool) {
         // (swap_layout_name, is_swap_layout_dirty)
+        if let Some(dynamic_tiling) = self.dynamic_tiling {
+            return (Some(dynamic_tiling.algorithm.to_string()), false);
+        }
         match self
             .swap_tiled_layouts
             .iter()
@@ -289,4 +298,94 @@ impl SwapLayouts {
         log::error!("Could not find layout that would fit on screen!");
         None
     }
+    pub fn dynamic_tiling(&self) -> Option<DynamicTiling> {
+        self.dynamic_tiling
+    }
+    pub fn has_dynamic_tiling(&self) -> bool {
+        self.dynamic_tiling.is_some()
+    }
+    pub fn set_dynamic_tiling(&mut self, algorithm: Option<TilingAlgorithm>) {
+        self.dynamic_tiling = match (algorithm, self.dynamic_tiling) {
+            (Some(algorithm), Some(mut dynamic_tiling)) => {
+                // keep the master ratio and count when switching between algorithms
+                dynamic_tiling.algorithm = algorithm;
+                Some(dynamic_tiling)
+            },
+            (Some(algorithm), None) => Some(DynamicTiling::new(algorithm)),
+            (None, _) => None,
+        };
+    }
+    pub fn promote_to_master(&mut self, pane_id: PaneId, tiled_panes: &TiledPanes) {
+        self.sync_dynamic_tiling_order(tiled_panes);
+        if let Some(position) = self.dynamic_tiling_order.iter().position(|p| *p == pane_id) {
+            let pane_id = self.dynamic_tiling_order.remove(position);
+            self.dynamic_tiling_order.insert(0, pane_id);
+        }
+    }
+    pub fn change_master_count(&mut self, change_by: isize, tiled_panes: &TiledPanes) {
+        self.sync_dynamic_tiling_order(tiled_panes);
+        let max_master_count = self.dynamic_tiling_order.len();
+        if let Some(dynamic_tiling) = self.dynamic_tiling.as_mut() {
+            dynamic_tiling.change_master_count(change_by);
+            dynamic_tiling.master_count =
+                std::cmp::min(dynamic_tiling.master_count, max_master_count);
+        }
+    }
+    pub fn dynamic_tiled_layout(
+        &mut self,
+        tiled_panes: &TiledPanes,
+    ) -> Option<(TiledPaneLayout, Vec<PaneId>)> {
+        // returns the layout along with the order in which the selectable panes should be placed
+        // in its empty slots
+        let dynamic_tiling = self.dynamic_tiling?;
+        self.sync_dynamic_tiling_order(tiled_panes);
+        let mut tiled_area = dynamic_tiling.tiled_layout(self.dynamic_tiling_order.len());
+        let mut layout = self.base_tiled_layout.clone().unwrap_or_default();
+        // plugins with a fixed size at the root of the base layout (eg. the tab-bar and the
+        // status-bar) stay where they are, the rest of the space is tiled
+        let is_fixed_plugin = |pane: &TiledPaneLayout| {
+            pane.children.is_empty()
+                && matches!(pane.run, Some(Run::Plugin(_)))
+                && matches!(pane.split_size, Some(SplitSize::Fixed(_)))
+        };
+        match layout.children.iter().position(|c| !is_fixed_plugin(c)) {
+            Some(tiled_area_index) if layout.children.iter().any(is_fixed_plugin) => {
+                layout.children.retain(is_fixed_plugin);
+                tiled_area.split_size = None;
+                layout.children.insert(tiled_area_index, tiled_area);
+            },
+            _ => {
+                layout = tiled_area;
+            },
+        }
+        let pane_count = tiled_panes.visible_panes_count();
+        if layout.pane_count() != pane_count {
+            log::error!("Failed to fit all panes in the dynamic tiling layout");
+            return None;
+        }
+        let display_area = PaneGeom::from(&*self.display_area.borrow());
+        if layout
+            .position_panes_in_space(&display_area, Some(pane_count), false)
+            .is_err()
+        {
+            log::error!("Dynamic tiling layout does not fit on screen");
+            return None;
+        }
+        Some((layout, self.dynamic_tiling_order.clone()))
+    }
+    fn sync_dynamic_tiling_order(&mut self, tiled_panes: &TiledPanes) {
+        let mut selectable_panes: Vec<(PaneId, PaneGeom)> = tiled_panes
+            .get_panes()
+            .filter(|(_, p)| p.selectable())
+            .map(|(pid, p)| (*pid, p.position_and_size()))
+            .collect();
+        self.dynamic_tiling_order
+            .retain(|pane_id| selectable_panes.iter().any(|(pid, _)| pid == pane_id));
+        // panes we have not seen before are placed at the end of the stack, in their on-screen
+        // order
+        selectable_panes.retain(|(pid, _)| !self.dynamic_tiling_order.contains(pid));
+        selectable_panes.sort_by(|(_, a), (_, b)| (a.y, a.x).cmp(&(b.y, b.x)));
+        self.dynamic_tiling_order
+            .extend(selectable_panes.iter().map(|(pid, _)| *pid));
+    }
 }
@@ -2167,7 +2167,7 @@ fn suppress_tiled_pane() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.replace_active_pane_with_editor_pane(new_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(new_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2196,7 +2196,7 @@ fn suppress_floating_pane() {
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(editor_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(3, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2220,7 +2220,7 @@ fn close_suppressing_tiled_pane() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.replace_active_pane_with_editor_pane(new_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(new_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2252,7 +2252,7 @@ fn close_suppressing_floating_pane() {
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(editor_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(3, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2279,7 +2279,7 @@ fn suppress_tiled_pane_float_it_and_close() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.replace_active_pane_with_editor_pane(new_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(new_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2312,7 +2312,7 @@ fn suppress_floating_pane_embed_it_and_close_it() {
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(editor_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(3, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2340,7 +2340,7 @@ fn resize_whole_tab_while_tiled_pane_is_suppressed() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.replace_active_pane_with_editor_pane(new_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(new_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -2374,7 +2374,7 @@ fn resize_whole_tab_while_floting_pane_is_suppressed() {
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
+    tab.replace_pane_with_editor_pane(editor_pane_id, tab.get_active_pane_id(client_id).unwrap())
         .unwrap();
     tab.handle_pty_bytes(3, Vec::from("\n\n\nI am an editor pane".as_bytes()))
         .unwrap();
@@ -6964,3 +6964,85 @@ fn can_define_expanded_pane_in_stack() {
     );
     assert_snapshot!(snapshot);
 }
+
+#[test]
+fn pane_notification_is_indicated_until_pane_is_focused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.handle_pty_bytes(1, Vec::from("\u{1b}]9;Build finished\u{7}".as_bytes()))
+        .unwrap();
+    tab.handle_pty_bytes(2, Vec::from("\u{1b}]9;Ignored\u{7}".as_bytes()))
+        .unwrap();
+    assert!(
+        tab.has_unseen_notification(),
+        "notification of unfocused pane is unseen"
+    );
+    let mut output = Output::default();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+    assert!(tab.take_alerts_changed());
+    tab.move_focus_left(client_id).unwrap();
+    tab.render(&mut output).unwrap();
+    assert!(
+        !tab.has_unseen_notification(),
+        "notification is seen once the pane is focused"
+    );
+    assert!(tab.take_alerts_changed());
+}
+
+#[test]
+fn pane_bell_activity_and_silence_are_indicated_until_pane_is_focused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let monitor = tab
+        .get_pane_with_id_mut(PaneId::Terminal(1))
+        .and_then(|pane| pane.monitor_mut())
+        .unwrap();
+    monitor.monitor_activity = true;
+    monitor.monitor_silence = Some(std::time::Duration::ZERO);
+    tab.handle_pty_bytes(2, Vec::from("\u{7}".as_bytes()))
+        .unwrap();
+    assert!(!tab.has_bell(), "bell of focused pane is ignored");
+    assert!(!tab.take_alerts_changed());
+    tab.handle_pty_bytes(1, Vec::from("\u{7}".as_bytes()))
+        .unwrap();
+    assert!(tab.has_bell(), "bell of unfocused pane is indicated");
+    assert!(
+        tab.has_activity(),
+        "activity of monitored pane is indicated"
+    );
+    assert!(!tab.has_silence(), "pane with output is not silent");
+    assert!(tab.take_alerts_changed());
+    assert!(tab.check_pane_silence(), "monitored pane became silent");
+    assert!(tab.has_silence(), "silence of monitored pane is indicated");
+    tab.move_focus_left(client_id).unwrap();
+    let mut output = Output::default();
+    tab.render(&mut output).unwrap();
+    assert!(
+        !tab.has_bell() && !tab.has_activity() && !tab.has_silence(),
+        "alerts are cleared once the pane is focused"
+    );
+    assert!(tab.take_alerts_changed());
+    assert!(
+        !tab.check_pane_silence(),
+        "silence of focused pane is not indicated"
+    );
+}
@@ -4,15 +4,20 @@ use crate::screen::CopyOptions;
 use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
     panes::PaneId,
+    pty::PtyInstruction,
     thread_bus::ThreadSenders,
     ClientId,
 };
 use std::path::PathBuf;
-use zellij_utils::data::{Direction, Resize, ResizeStrategy};
+use zellij_utils::channels::{self, ChannelWithContext, SenderWithContext};
+use zellij_utils::data::{Direction, FloatingPaneCoordinates, Resize, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
-use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
+use zellij_utils::input::layout::{
+    Run, SplitDirection, SplitSize, TiledPaneLayout, TilingAlgorithm,
+};
 use zellij_utils::ipc::IpcReceiverWithContext;
-use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::pane_size::{PaneGeom, Size, SizeInPixels};
+use zellij_utils::position::Position;
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -351,7 +356,7 @@ fn write_to_suppressed_pane() {
     tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
 
     // Suppress pane 2 and remove it from active panes
-    tab.replace_active_pane_with_editor_pane(PaneId::Terminal(2), 1)
+    tab.replace_pane_with_editor_pane(PaneId::Terminal(2), tab.get_active_pane_id(1).unwrap())
         .unwrap();
     tab.tiled_panes.remove_pane(PaneId::Terminal(2));
 
@@ -14436,3 +14441,598 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+fn master_stack_tiling_places_new_panes_in_the_stack() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_tiling(Some(TilingAlgorithm::MasterStack), 1)
+        .unwrap();
+    for i in 2..4 {
+        tab.new_pane(PaneId::Terminal(i), None, None, None, None, Some(1))
+            .unwrap();
+    }
+    let master = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        (master.x(), master.y(), master.cols(), master.rows()),
+        (0, 0, 73, 20),
+        "first pane is the master and takes the master ratio of the tab"
+    );
+    let first_in_stack = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    let second_in_stack = tab.tiled_panes.panes.get(&PaneId::Terminal(3)).unwrap();
+    assert_eq!(
+        (first_in_stack.x(), first_in_stack.y()),
+        (73, 0),
+        "second pane is at the top of the stack"
+    );
+    assert_eq!(
+        (second_in_stack.x(), second_in_stack.y()),
+        (73, 10),
+        "third pane is below it in the stack"
+    );
+}
+
+#[test]
+fn promote_focused_pane_to_master() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_tiling(Some(TilingAlgorithm::MasterStack), 1)
+        .unwrap();
+    for i in 2..4 {
+        tab.new_pane(PaneId::Terminal(i), None, None, None, None, Some(1))
+            .unwrap();
+    }
+    tab.promote_focused_pane_to_master(1).unwrap();
+    let master = tab.tiled_panes.panes.get(&PaneId::Terminal(3)).unwrap();
+    assert_eq!(
+        (master.x(), master.y(), master.rows()),
+        (0, 0, 20),
+        "focused pane was promoted to master"
+    );
+    let previous_master = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        (previous_master.x(), previous_master.y()),
+        (73, 0),
+        "previous master was moved to the top of the stack"
+    );
+}
+
+#[test]
+fn change_master_count_in_master_stack_tiling() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_tiling(Some(TilingAlgorithm::MasterStack), 1)
+        .unwrap();
+    for i in 2..4 {
+        tab.new_pane(PaneId::Terminal(i), None, None, None, None, Some(1))
+            .unwrap();
+    }
+    tab.change_master_count(1, 1).unwrap();
+    let second_master = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    assert_eq!(
+        (second_master.x(), second_master.y()),
+        (0, 10),
+        "second pane was added to the master area"
+    );
+    let stacked = tab.tiled_panes.panes.get(&PaneId::Terminal(3)).unwrap();
+    assert_eq!(
+        (stacked.x(), stacked.y(), stacked.rows()),
+        (73, 0, 20),
+        "third pane is alone in the stack"
+    );
+}
+
+#[test]
+fn dynamic_tiling_relayouts_panes_on_pane_close() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_tiling(Some(TilingAlgorithm::MasterStack), 1)
+        .unwrap();
+    for i in 2..4 {
+        tab.new_pane(PaneId::Terminal(i), None, None, None, None, Some(1))
+            .unwrap();
+    }
+    tab.close_pane(PaneId::Terminal(1), false, None);
+    let master = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    assert_eq!(
+        (master.x(), master.y(), master.cols(), master.rows()),
+        (0, 0, 73, 20),
+        "top of the stack became the master"
+    );
+    let stacked = tab.tiled_panes.panes.get(&PaneId::Terminal(3)).unwrap();
+    assert_eq!(
+        (stacked.x(), stacked.y(), stacked.rows()),
+        (73, 0, 20),
+        "remaining pane fills the stack"
+    );
+}
+
+#[test]
+fn change_floating_pane_coordinates() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.new_pane(PaneId::Terminal(2), None, Some(true), None, None, Some(1))
+        .unwrap();
+    let coordinates = FloatingPaneCoordinates::default()
+        .with_x_fixed(5)
+        .with_y_percent(50)
+        .with_width_fixed(30)
+        .with_height_fixed(8);
+    tab.change_floating_pane_coordinates(&PaneId::Terminal(2), coordinates)
+        .unwrap();
+    let floating_pane = tab.floating_panes.get_pane(PaneId::Terminal(2)).unwrap();
+    assert_eq!(
+        (
+            floating_pane.x(),
+            floating_pane.y(),
+            floating_pane.cols(),
+            floating_pane.rows()
+        ),
+        (5, 10, 30, 8),
+        "floating pane moved and resized"
+    );
+    tab.change_floating_pane_coordinates(
+        &PaneId::Terminal(2),
+        FloatingPaneCoordinates::default()
+            .with_x_fixed(100)
+            .with_width_fixed(50),
+    )
+    .unwrap();
+    let floating_pane = tab.floating_panes.get_pane(PaneId::Terminal(2)).unwrap();
+    assert_eq!(
+        (
+            floating_pane.x(),
+            floating_pane.y(),
+            floating_pane.cols(),
+            floating_pane.rows()
+        ),
+        (71, 10, 50, 8),
+        "floating pane kept inside the viewport"
+    );
+    assert!(
+        tab.change_floating_pane_coordinates(
+            &PaneId::Terminal(1),
+            FloatingPaneCoordinates::default().with_x_fixed(5)
+        )
+        .is_err(),
+        "tiled panes cannot be given floating coordinates"
+    );
+}
+
+#[test]
+fn change_floating_pane_coordinates_in_a_viewport_without_room_for_new_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.new_pane(PaneId::Terminal(2), None, Some(true), None, None, Some(1))
+        .unwrap();
+    tab.resize_whole_tab(Size { cols: 8, rows: 4 }).unwrap();
+    assert!(
+        tab.floating_panes.find_room_for_new_pane().is_none(),
+        "no room for a new floating pane"
+    );
+    tab.change_floating_pane_coordinates(
+        &PaneId::Terminal(2),
+        FloatingPaneCoordinates::default()
+            .with_x_fixed(2)
+            .with_y_fixed(1)
+            .with_width_fixed(5)
+            .with_height_fixed(3),
+    )
+    .unwrap();
+    let floating_pane = tab.floating_panes.get_pane(PaneId::Terminal(2)).unwrap();
+    assert_eq!(
+        (
+            floating_pane.x(),
+            floating_pane.y(),
+            floating_pane.cols(),
+            floating_pane.rows()
+        ),
+        (2, 1, 5, 3),
+        "floating pane moved and resized"
+    );
+}
+
+#[test]
+fn pinned_floating_pane_stays_visible_when_floating_panes_are_hidden() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(true),
+        None,
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.toggle_pane_pinned(client_id).unwrap();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    assert!(
+        !tab.floating_panes.panes_are_visible(),
+        "floating panes hidden"
+    );
+    assert_eq!(
+        tab.floating_panes.stack().map(|s| s.layers.len()),
+        Some(1),
+        "pinned pane still part of the floating panes stack"
+    );
+    assert!(
+        tab.pane_infos()
+            .iter()
+            .find(|p| p.id == 2 && p.is_floating)
+            .unwrap()
+            .is_pinned,
+        "pane reported as pinned"
+    );
+    tab.toggle_pane_pinned(client_id).unwrap();
+    assert!(
+        tab.floating_panes.has_pinned_panes(),
+        "panes cannot be unpinned while floating panes are hidden"
+    );
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.toggle_pane_pinned(client_id).unwrap();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    assert!(
+        tab.floating_panes.stack().is_none(),
+        "unpinned pane hidden along with the other floating panes"
+    );
+}
+
+#[test]
+fn clicking_a_pinned_pane_focuses_it_without_showing_the_other_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(true),
+        None,
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.new_pane(
+        PaneId::Terminal(3),
+        None,
+        Some(true),
+        None,
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.toggle_pane_pinned(client_id).unwrap();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    let pinned_pane = tab.floating_panes.get_pane(PaneId::Terminal(3)).unwrap();
+    let position = Position::new(pinned_pane.y() as i32 + 2, pinned_pane.x() as u16 + 2);
+    tab.handle_left_click(&position, client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(3)),
+        "pinned pane focused"
+    );
+    assert_eq!(
+        tab.floating_panes.stack().map(|s| s.layers.len()),
+        Some(1),
+        "other floating panes still hidden"
+    );
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    assert_eq!(
+        tab.floating_panes.stack().map(|s| s.layers.len()),
+        Some(2),
+        "toggling the floating panes shows all of them"
+    );
+}
+
+#[test]
+fn undo_and_redo_layout_change() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let pane_cols = |tab: &Tab, pane_id| {
+        tab.tiled_panes
+            .get_pane(pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    let cols_before_resize = pane_cols(&tab, PaneId::Terminal(2));
+    tab.with_layout_history(|tab| tab_resize_left(tab, client_id));
+    let cols_after_resize = pane_cols(&tab, PaneId::Terminal(2));
+    assert!(
+        cols_after_resize > cols_before_resize,
+        "pane resized ({} => {})",
+        cols_before_resize,
+        cols_after_resize
+    );
+    tab.undo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        cols_before_resize,
+        "resize undone"
+    );
+    tab.redo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        cols_after_resize,
+        "resize redone"
+    );
+    tab.undo_layout_change(client_id).unwrap();
+    tab.undo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        cols_before_resize,
+        "undoing with an empty history does nothing"
+    );
+}
+
+#[test]
+fn undo_skips_layout_changes_that_no_longer_apply() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let pane_cols = |tab: &Tab, pane_id| {
+        tab.tiled_panes
+            .get_pane(pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    tab.with_layout_history(|tab| tab_resize_left(tab, client_id));
+    // the split is not recorded, so the resize before it can no longer be undone
+    tab.vertical_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    let cols_after_split = pane_cols(&tab, PaneId::Terminal(2));
+    tab.undo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        cols_after_split,
+        "stale entry changes nothing"
+    );
+    tab.close_pane(PaneId::Terminal(3), false, None);
+    let cols_after_close = pane_cols(&tab, PaneId::Terminal(2));
+    tab.undo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        cols_after_close,
+        "stale entry was dropped rather than kept for later"
+    );
+}
+
+#[test]
+fn undo_closing_a_command_pane_re_spawns_it() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::unbounded();
+    tab.senders.to_pty = Some(SenderWithContext::new(to_pty));
+    let run_command = RunCommand {
+        command: PathBuf::from("htop"),
+        ..Default::default()
+    };
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(false),
+        Some(Run::Command(run_command.clone())),
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.with_layout_history(|tab| tab.close_pane(PaneId::Terminal(2), false, None));
+    tab.undo_layout_change(client_id).unwrap();
+    let spawned_commands: Vec<RunCommand> = pty_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            PtyInstruction::SpawnTerminal(
+                Some(TerminalAction::RunCommand(run_command)),
+                Some(false),
+                ..,
+            ) => Some(run_command),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        spawned_commands,
+        vec![RunCommand {
+            hold_on_start: true,
+            ..run_command
+        }],
+        "closed command pane re-spawned, held until it is run again"
+    );
+    tab.redo_layout_change(client_id).unwrap();
+    tab.undo_layout_change(client_id).unwrap();
+    assert_eq!(
+        pty_receiver.try_iter().count(),
+        0,
+        "the pane is only re-spawned once"
+    );
+}
+
+#[test]
+fn undo_layout_change_restores_floating_panes_visibility() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(true),
+        None,
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.with_layout_history(|tab| tab.toggle_floating_panes(Some(client_id), None))
+        .unwrap();
+    assert!(
+        !tab.floating_panes.panes_are_visible(),
+        "floating panes hidden"
+    );
+    tab.undo_layout_change(client_id).unwrap();
+    assert!(
+        tab.floating_panes.panes_are_visible(),
+        "floating panes visible again"
+    );
+}
+
+#[test]
+fn stack_pane_onto_neighbour_and_unstack_it() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.stack_active_pane(client_id, Direction::Left).unwrap();
+    let geom = |tab: &Tab, pane_id| {
+        tab.tiled_panes
+            .get_pane(pane_id)
+            .unwrap()
+            .position_and_size()
+    };
+    let (pane_1_geom, pane_2_geom) = (
+        geom(&tab, PaneId::Terminal(1)),
+        geom(&tab, PaneId::Terminal(2)),
+    );
+    assert!(
+        pane_1_geom.is_stacked && pane_2_geom.is_stacked,
+        "both panes stacked"
+    );
+    assert_eq!(
+        (
+            pane_1_geom.x,
+            pane_1_geom.cols.as_usize(),
+            pane_1_geom.y,
+            pane_1_geom.rows.as_usize()
+        ),
+        (0, 121, 0, 1),
+        "neighbour collapsed to the top of the stack and took the whole width"
+    );
+    assert_eq!(
+        (
+            pane_2_geom.x,
+            pane_2_geom.cols.as_usize(),
+            pane_2_geom.y,
+            pane_2_geom.rows.as_usize()
+        ),
+        (0, 121, 1, 19),
+        "stacked pane expanded below"
+    );
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2)),
+        "stacked pane still focused"
+    );
+
+    tab.unstack_active_pane(client_id).unwrap();
+    let (pane_1_geom, pane_2_geom) = (
+        geom(&tab, PaneId::Terminal(1)),
+        geom(&tab, PaneId::Terminal(2)),
+    );
+    assert!(
+        !pane_1_geom.is_stacked && !pane_2_geom.is_stacked,
+        "no panes stacked"
+    );
+    assert_eq!(
+        (pane_1_geom.y, pane_1_geom.rows.as_usize()),
+        (0, 10),
+        "remaining pane took the top half"
+    );
+    assert_eq!(
+        (pane_2_geom.y, pane_2_geom.rows.as_usize()),
+        (10, 10),
+        "unstacked pane took the bottom half"
+    );
+}
+
+#[test]
+fn stack_all_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    tab.stack_all_panes(client_id).unwrap();
+    let mut stacked_geoms: Vec<(PaneId, PaneGeom)> = tab
+        .tiled_panes
+        .get_panes()
+        .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+        .collect();
+    stacked_geoms.sort_by_key(|(_, geom)| geom.y);
+    assert!(
+        stacked_geoms
+            .iter()
+            .all(|(_, geom)| geom.is_stacked && geom.x == 0 && geom.cols.as_usize() == 121),
+        "all panes stacked over the whole width: {:?}",
+        stacked_geoms
+    );
+    let total_rows: usize = stacked_geoms
+        .iter()
+        .map(|(_, geom)| geom.rows.as_usize())
+        .sum();
+    assert_eq!(total_rows, 20, "stack covers the whole height");
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(3)),
+        "focused pane kept focus"
+    );
+    assert!(
+        stacked_geoms
+            .iter()
+            .find(|(pane_id, _)| *pane_id == PaneId::Terminal(3))
+            .map(|(_, geom)| geom.rows.as_usize() > 1)
+            .unwrap(),
+        "focused pane is expanded in the stack"
+    );
+}
@@ -75,6 +75,8 @@ pub struct PaneFrame {
     pub other_focused_clients: Vec<ClientId>,
     exit_status: Option<ExitStatus>,
     is_first_run: bool,
+    has_unseen_notification: bool,
+    is_pinned: bool,
     pane_is_stacked_over: bool,
     pane_is_stacked_under: bool,
     should_draw_pane_frames: bool,
@@ -99,6 +101,8 @@ impl PaneFrame {
             other_cursors_exist_in_session: frame_params.other_cursors_exist_in_session,
             exit_status: None,
             is_first_run: false,
+            has_unseen_notification: false,
+            is_pinned: false,
             pane_is_stacked_over: frame_params.pane_is_stacked_over,
             pane_is_stacked_under: frame_params.pane_is_stacked_under,
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
@@ -113,6 +117,12 @@ impl PaneFrame {
     pub fn indicate_first_run(&mut self) {
         self.is_first_run = true;
     }
+    pub fn indicate_unseen_notification(&mut self) {
+        self.has_unseen_notification = true;
+    }
+    pub fn indicate_pinned(&mut self) {
+        self.is_pinned = true;
+    }
     pub fn override_color(&mut self, color: PaletteColor) {
         self.color = Some(color);
     }
@@ -341,7 +351,16 @@ impl PaneFrame {
     fn render_title_left_side(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
         let middle_truncated_sign = "[..]";
         let middle_truncated_sign_long = "[...]";
-        let full_text = format!(" {} ", &self.title);
+        let pinned_indication = if self.is_pinned { "(PIN) " } else { "" };
+        let notification_indication = if self.has_unseen_notification {
+            "(!) "
+        } else {
+            ""
+        };
+        let full_text = format!(
+            " {}{}{} ",
+            pinned_indication, notification_indication, &self.title
+        );
         if max_length <= 6 || self.title.is_empty() {
             None
         } else if full_text.width() <= max_length {
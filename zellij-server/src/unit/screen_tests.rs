@@ -10,7 +10,7 @@ use crate::{
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize, Style};
+use zellij_utils::data::{Event, Resize, Style, TabInfo};
 use zellij_utils::errors::{prelude::*, ErrorContext};
 use zellij_utils::input::actions::Action;
 use zellij_utils::input::command::{RunCommand, TerminalAction};
@@ -110,9 +110,26 @@ fn send_cli_action_to_server(
     session_metadata: &SessionMetaData,
     cli_action: CliAction,
     client_id: ClientId,
+) {
+    send_targeted_cli_action_to_server(session_metadata, cli_action, None, None, client_id);
+}
+
+fn send_targeted_cli_action_to_server(
+    session_metadata: &SessionMetaData,
+    cli_action: CliAction,
+    pane_id: Option<zellij_utils::data::PaneId>,
+    tab_position: Option<u32>,
+    client_id: ClientId,
 ) {
     let get_current_dir = || PathBuf::from(".");
-    let actions = Action::actions_from_cli(cli_action, Box::new(get_current_dir), None).unwrap();
+    let actions = Action::targeted_actions_from_cli(
+        cli_action,
+        pane_id,
+        tab_position,
+        Box::new(get_current_dir),
+        None,
+    )
+    .unwrap();
     let senders = session_metadata.senders.clone();
     let capabilities = PluginCapabilities::default();
     let client_attributes = ClientAttributes::default();
@@ -644,6 +661,54 @@ fn new_tab(screen: &mut Screen, pid: u32, tab_index: usize) {
         .expect("TEST");
 }
 
+fn new_tab_with_default_layout(
+    screen: &mut Screen,
+    terminal_id: u32,
+    plugin_ids: Vec<u32>,
+    tab_index: usize,
+) {
+    // the default layout has a tab-bar and a status-bar around a single terminal pane, which we
+    // mark as unselectable like these plugins do on startup
+    let client_id = 1;
+    let layout = Layout::from_kdl(
+        &Layout::stringified_default_from_assets().unwrap(),
+        "default".into(),
+        None,
+        None,
+    )
+    .unwrap();
+    let (tiled_panes_layout, _floating_panes_layout) = layout.new_tab();
+    let mut new_plugin_ids: HashMap<RunPluginOrAlias, Vec<u32>> = HashMap::new();
+    let plugin_runs = tiled_panes_layout
+        .extract_run_instructions()
+        .into_iter()
+        .filter_map(|run| match run {
+            Some(Run::Plugin(run)) => Some(run),
+            _ => None,
+        });
+    for (run, plugin_id) in plugin_runs.zip(plugin_ids.iter()) {
+        new_plugin_ids.entry(run).or_default().push(*plugin_id);
+    }
+    screen
+        .new_tab(tab_index, (vec![], vec![]), None, client_id)
+        .expect("TEST");
+    screen
+        .apply_layout(
+            tiled_panes_layout,
+            vec![], // floating panes layout
+            vec![(terminal_id, None)],
+            vec![], // new floating terminal ids
+            new_plugin_ids,
+            tab_index,
+            client_id,
+        )
+        .expect("TEST");
+    let tab = screen.tabs.get_mut(&tab_index).unwrap();
+    for plugin_id in plugin_ids {
+        tab.set_pane_selectable(PaneId::Plugin(plugin_id), false);
+    }
+}
+
 #[test]
 fn open_new_tab() {
     let size = Size {
@@ -799,6 +864,308 @@ fn move_focus_left_at_left_screen_edge_changes_tab() {
     );
 }
 
+#[test]
+fn move_pane_to_tab_by_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    new_tab(&mut screen, 3, 1);
+    screen
+        .move_pane_to_tab(
+            PaneId::Terminal(2),
+            Some(2),
+            None,
+            false,
+            None,
+            Box::new(Layout::default()),
+            1,
+        )
+        .expect("TEST");
+
+    assert!(
+        !screen
+            .tabs
+            .get(&0)
+            .unwrap()
+            .has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane removed from source tab"
+    );
+    assert!(
+        screen
+            .tabs
+            .get(&1)
+            .unwrap()
+            .has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane added to target tab"
+    );
+}
+
+#[test]
+fn move_pane_to_tab_by_name_as_floating_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    new_tab(&mut screen, 3, 1);
+    screen.tabs.get_mut(&1).unwrap().name = "target".to_owned();
+    screen
+        .move_pane_to_tab(
+            PaneId::Terminal(2),
+            None,
+            Some("target".to_owned()),
+            true,
+            None,
+            Box::new(Layout::default()),
+            1,
+        )
+        .expect("TEST");
+
+    let target_tab = screen.tabs.get(&1).unwrap();
+    assert!(
+        target_tab.has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane added to target tab"
+    );
+    assert_eq!(
+        target_tab.get_visible_selectable_floating_panes_count(),
+        1,
+        "Pane added as a floating pane"
+    );
+}
+
+#[test]
+fn move_pane_to_tab_with_unknown_name_creates_new_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    screen
+        .move_pane_to_tab(
+            PaneId::Terminal(2),
+            None,
+            Some("new tab".to_owned()),
+            false,
+            None,
+            Box::new(Layout::default()),
+            1,
+        )
+        .expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "New tab created");
+    let new_tab = screen.tabs.values().find(|t| t.name == "new tab").unwrap();
+    assert!(
+        new_tab.has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane moved to the new tab"
+    );
+}
+
+#[test]
+fn merge_active_tab_into_another_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    {
+        let active_tab = screen.get_active_tab_mut(1).unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(3), None, None, None, None, Some(1))
+            .unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(4), None, Some(true), None, None, Some(1))
+            .unwrap();
+    }
+    screen.merge_tabs(None, 1, 1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Source tab closed");
+    let active_tab = screen.get_active_tab(1).unwrap();
+    assert_eq!(active_tab.index, 0, "Client moved to the target tab");
+    for pid in 1..=4 {
+        assert!(
+            active_tab.has_pane_with_pid(&PaneId::Terminal(pid)),
+            "Pane {} in merged tab",
+            pid
+        );
+    }
+    assert_eq!(
+        active_tab.get_visible_selectable_floating_panes_count(),
+        1,
+        "Floating pane remains floating"
+    );
+}
+
+#[test]
+fn merge_tab_by_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.merge_tabs(Some(1), 2, 1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Source tab closed");
+    let target_tab = screen.tabs.get(&1).unwrap();
+    assert_eq!(target_tab.position, 0, "Tab positions updated");
+    assert!(
+        target_tab.has_pane_with_pid(&PaneId::Terminal(1)),
+        "Pane moved to target tab"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().index,
+        2,
+        "Focused tab did not change"
+    );
+}
+
+#[test]
+fn merge_tabs_with_the_default_layout() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    // plugin panes need somewhere to send their instructions
+    let (to_plugin, _plugin_receiver): ChannelWithContext<PluginInstruction> =
+        channels::unbounded();
+    screen.bus.senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+
+    new_tab_with_default_layout(&mut screen, 1, vec![1, 2], 0);
+    new_tab_with_default_layout(&mut screen, 2, vec![3, 4], 1);
+    {
+        let source_tab = screen.tabs.get_mut(&1).unwrap();
+        source_tab
+            .new_pane(PaneId::Terminal(3), None, None, None, None, Some(1))
+            .unwrap();
+        source_tab.suppress_pane(PaneId::Terminal(3), 1);
+    }
+    screen.merge_tabs(Some(2), 1, 1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Source tab closed");
+    let target_tab = screen.tabs.get(&0).unwrap();
+    assert!(
+        target_tab.has_pane_with_pid(&PaneId::Terminal(2)),
+        "Terminal pane moved to the target tab"
+    );
+    let mut plugin_pane_ids: Vec<PaneId> = target_tab
+        .get_all_pane_ids()
+        .into_iter()
+        .filter(|pane_id| matches!(pane_id, PaneId::Plugin(_)))
+        .collect();
+    plugin_pane_ids.sort();
+    assert_eq!(
+        plugin_pane_ids,
+        vec![PaneId::Plugin(1), PaneId::Plugin(2)],
+        "Only the target tab's tab-bar and status-bar remain"
+    );
+    assert!(
+        target_tab
+            .get_suppressed_panes()
+            .any(|(pane_id, _)| *pane_id == PaneId::Terminal(3)),
+        "Suppressed pane moved to the target tab"
+    );
+}
+
+#[test]
+fn merge_tabs_into_a_full_tab_does_nothing() {
+    let size = Size { cols: 8, rows: 4 };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen.merge_tabs(Some(2), 1, 1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Source tab not closed");
+    assert!(
+        screen
+            .tabs
+            .get(&1)
+            .unwrap()
+            .has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane left in the source tab"
+    );
+    assert!(
+        !screen
+            .tabs
+            .get(&0)
+            .unwrap()
+            .has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane not moved to the full tab"
+    );
+}
+
+#[test]
+fn merge_tabs_floats_the_panes_that_do_not_fit_in_the_target_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    {
+        let source_tab = screen.tabs.get_mut(&1).unwrap();
+        for pid in 3..=4 {
+            source_tab
+                .new_pane(PaneId::Terminal(pid), None, None, None, None, Some(1))
+                .unwrap();
+        }
+    }
+    // the target tab only has room for one more pane
+    screen
+        .tabs
+        .get_mut(&0)
+        .unwrap()
+        .resize_whole_tab(Size { cols: 20, rows: 10 })
+        .unwrap();
+    screen.merge_tabs(Some(2), 1, 1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Source tab closed");
+    let target_tab = screen.tabs.get(&0).unwrap();
+    for pid in 1..=4 {
+        assert!(
+            target_tab.has_pane_with_pid(&PaneId::Terminal(pid)),
+            "Pane {} in merged tab",
+            pid
+        );
+    }
+    assert!(
+        target_tab.get_visible_selectable_floating_panes_count() > 0,
+        "Panes with no room were floated"
+    );
+}
+
 #[test]
 fn basic_move_of_active_tab_to_left() {
     let mut screen = create_fixed_size_screen();
@@ -1276,6 +1643,45 @@ fn attach_after_first_tab_closed() {
     screen.add_client(1).expect("TEST");
 }
 
+#[test]
+fn list_clients_indicates_read_only_clients() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen.add_client(2).expect("TEST");
+    screen.read_only_clients.insert(2);
+    let clients_metadata = screen.get_layout_metadata(None).list_clients_metadata();
+    let mut lines = clients_metadata.lines();
+    assert_eq!(
+        lines.next(),
+        Some("CLIENT_ID ZELLIJ_PANE_ID READ_ONLY RUNNING_COMMAND")
+    );
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .starts_with("1         terminal_1     false"),
+        "writer client listed as such"
+    );
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .starts_with("2         terminal_1     true"),
+        "read-only client listed as such"
+    );
+
+    screen.remove_client(2).expect("TEST");
+    assert!(
+        !screen.read_only_clients.contains(&2),
+        "read-only state forgotten once the client leaves"
+    );
+}
+
 #[test]
 fn open_new_floating_pane_with_custom_coordinates() {
     let size = Size {
@@ -1675,11 +2081,16 @@ pub fn send_cli_edit_scrollback_action() {
         .clone();
     let mut found_instruction = false;
     for instruction in received_pty_instructions.lock().unwrap().iter() {
-        if let PtyInstruction::OpenInPlaceEditor(scrollback_contents_file, terminal_id, client_id) =
-            instruction
+        if let PtyInstruction::OpenInPlaceEditor(
+            scrollback_contents_file,
+            terminal_id,
+            replaced_pane_id,
+            client_id,
+        ) = instruction
         {
             assert_eq!(scrollback_contents_file, &PathBuf::from(&dumped_file_name));
             assert_eq!(terminal_id, &Some(1));
+            assert_eq!(replaced_pane_id, &PaneId::Terminal(0));
             assert_eq!(client_id, &1);
             found_instruction = true;
         }
@@ -1687,6 +2098,55 @@ pub fn send_cli_edit_scrollback_action() {
     assert!(found_instruction);
 }
 
+#[test]
+pub fn send_cli_edit_scrollback_action_to_targeted_pane() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        CliAction::EditScrollback,
+        Some(zellij_utils::data::PaneId::Terminal(1)),
+        None,
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let replaced_pane_ids: Vec<PaneId> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyInstruction::OpenInPlaceEditor(
+                _file,
+                _line_number,
+                replaced_pane_id,
+                _client_id,
+            ) => Some(*replaced_pane_id),
+            _ => None,
+        })
+        .collect();
+    // the pane is carried through the pty thread, which only answers once the action target of
+    // the client has been cleared
+    assert_eq!(
+        replaced_pane_ids,
+        vec![PaneId::Terminal(1)],
+        "the editor replaces the targeted pane rather than the focused one"
+    );
+}
+
 #[test]
 pub fn send_cli_scroll_up_action() {
     let size = Size { cols: 80, rows: 10 };
@@ -2191,6 +2651,7 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -2234,6 +2695,7 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -2277,6 +2739,7 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -2320,6 +2783,7 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
         x: Some("10".to_owned()),
@@ -2929,7 +3393,7 @@ pub fn send_cli_query_tab_names_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let query_tab_names = CliAction::QueryTabNames;
+    let query_tab_names = CliAction::QueryTabNames { json: false };
     send_cli_action_to_server(&session_metadata, query_tab_names, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_thread, screen_thread]);
@@ -2945,6 +3409,627 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_query_tab_names_action_with_json_output() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let query_tab_names = CliAction::QueryTabNames { json: true };
+    send_cli_action_to_server(&session_metadata, query_tab_names, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_lines = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        })
+        .expect("tab infos were logged");
+    assert_eq!(
+        logged_lines.len(),
+        1,
+        "tab infos logged as a single json line"
+    );
+    let tab_infos: Vec<TabInfo> = serde_json::from_str(&logged_lines[0]).unwrap();
+    let tab_names: Vec<String> = tab_infos.iter().map(|t| t.name.clone()).collect();
+    assert_eq!(tab_names, vec!["Tab #1".to_owned(), "Tab #2".to_owned()]);
+    assert!(tab_infos[1].active, "focused tab indicated");
+}
+
+#[test]
+pub fn send_cli_list_tabs_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let list_tabs = CliAction::ListTabs { json: false };
+    send_cli_action_to_server(&session_metadata, list_tabs, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let log_tabs_instruction = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|instruction| match instruction {
+            ServerInstruction::Log(..) => true,
+            _ => false,
+        })
+        .cloned();
+    assert_snapshot!(format!("{:#?}", log_tabs_instruction));
+}
+
+#[test]
+pub fn send_cli_list_tabs_action_with_json_output() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let list_tabs = CliAction::ListTabs { json: true };
+    send_cli_action_to_server(&session_metadata, list_tabs, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let logged_lines = received_server_instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        })
+        .expect("tabs were logged");
+    let tabs: Vec<serde_json::Value> = serde_json::from_str(&logged_lines[0]).unwrap();
+    let tab_indices: Vec<&serde_json::Value> = tabs.iter().map(|tab| &tab["tab_index"]).collect();
+    assert_eq!(tab_indices, vec![1, 2], "tabs listed with their index");
+    assert!(
+        tabs.iter()
+            .all(|tab| tab["pane_count"].is_u64() && tab["name"].is_string()),
+        "tabs listed with their pane count and name: {:?}",
+        tabs
+    );
+}
+
+#[test]
+pub fn send_cli_list_panes_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let list_panes = CliAction::ListPanes {
+        tab_index: None,
+        tab_name: None,
+        json: false,
+    };
+    send_cli_action_to_server(&session_metadata, list_panes, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let log_panes_instruction = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|instruction| match instruction {
+            ServerInstruction::Log(..) => true,
+            _ => false,
+        })
+        .cloned();
+    assert_snapshot!(format!("{:#?}", log_panes_instruction));
+}
+
+#[test]
+pub fn send_cli_list_panes_action_with_tab_filter_and_json_output() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let list_panes = CliAction::ListPanes {
+        tab_index: None,
+        tab_name: Some("Tab #2".to_owned()),
+        json: true,
+    };
+    send_cli_action_to_server(&session_metadata, list_panes, client_id);
+    let list_panes_of_missing_tab = CliAction::ListPanes {
+        tab_index: Some(3),
+        tab_name: None,
+        json: true,
+    };
+    send_cli_action_to_server(&session_metadata, list_panes_of_missing_tab, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let logged_lines = received_server_instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        })
+        .expect("panes were logged");
+    let panes: Vec<serde_json::Value> = serde_json::from_str(&logged_lines[0]).unwrap();
+    assert!(!panes.is_empty(), "panes of the tab were listed");
+    assert!(
+        panes
+            .iter()
+            .all(|pane| pane["tab_index"] == 2 && pane["id"].is_u64()),
+        "only the panes of the second tab were listed, with their ids: {:?}",
+        panes
+    );
+    let logged_error =
+        received_server_instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                ServerInstruction::LogError(lines, _client_id) => Some(lines.clone()),
+                _ => None,
+            });
+    assert_eq!(
+        logged_error,
+        Some(vec!["No tab matches the given index or name".to_owned()]),
+        "error reported for a missing tab"
+    );
+}
+
+#[test]
+pub fn send_cli_write_chars_action_to_targeted_pane() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let write_chars = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        write_chars,
+        Some(zellij_utils::data::PaneId::Terminal(1)),
+        None,
+        client_id,
+    );
+    let list_panes = CliAction::ListPanes {
+        tab_index: None,
+        tab_name: None,
+        json: true,
+    };
+    send_cli_action_to_server(&session_metadata, list_panes, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, server_thread, screen_thread]);
+    let written_terminal_ids: Vec<u32> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(_bytes, terminal_id) => Some(*terminal_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_terminal_ids,
+        vec![1],
+        "chars written to the targeted pane"
+    );
+    let logged_lines = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        })
+        .expect("panes were logged");
+    let panes: Vec<serde_json::Value> = serde_json::from_str(&logged_lines[0]).unwrap();
+    let focused_pane_ids: Vec<&serde_json::Value> = panes
+        .iter()
+        .filter(|pane| pane["is_focused"] == true)
+        .map(|pane| &pane["id"])
+        .collect();
+    assert_eq!(
+        focused_pane_ids,
+        vec![&serde_json::Value::from(0)],
+        "focus did not move to the targeted pane"
+    );
+}
+
+#[test]
+pub fn send_cli_write_chars_action_to_targeted_pane_under_floating_panes() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let should_float = true;
+    let _ = mock_screen.to_screen.send(ScreenInstruction::NewPane(
+        PaneId::Terminal(2),
+        None,
+        Some(should_float),
+        None,
+        None,
+        None,
+        crate::pty::ClientTabIndexOrPaneId::ClientId(1),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let write_chars = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        write_chars,
+        Some(zellij_utils::data::PaneId::Terminal(1)),
+        None,
+        client_id,
+    );
+    let write_chars = CliAction::WriteChars {
+        chars: "input from the user".into(),
+    };
+    send_cli_action_to_server(&session_metadata, write_chars, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, server_thread, screen_thread]);
+    let written_terminal_ids: Vec<u32> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(_bytes, terminal_id) => Some(*terminal_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_terminal_ids,
+        vec![1, 2],
+        "chars written to the targeted tiled pane, then to the focused floating pane"
+    );
+    assert!(
+        !received_server_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|instruction| matches!(instruction, ServerInstruction::LogError(..))),
+        "the tiled pane could be targeted while the floating panes are visible"
+    );
+}
+
+#[test]
+pub fn targeted_cli_new_pane_action_is_refused() {
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec![],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        in_place: false,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        env: vec![],
+        configuration: None,
+        skip_plugin_cache: false,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
+    };
+    let get_current_dir = || PathBuf::from(".");
+    let actions = Action::targeted_actions_from_cli(
+        cli_new_pane_action,
+        None,
+        Some(2),
+        Box::new(get_current_dir),
+        None,
+    );
+    assert!(
+        actions.is_err(),
+        "new panes are opened through the pty thread, after the target is cleared"
+    );
+}
+
+#[test]
+pub fn send_cli_action_to_missing_target_is_dropped() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let write_chars = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_targeted_cli_action_to_server(&session_metadata, write_chars, None, Some(2), client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, server_thread, screen_thread]);
+    assert!(
+        !received_pty_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|instruction| matches!(instruction, PtyWriteInstruction::Write(..))),
+        "chars were not written to the focused pane instead"
+    );
+    let logged_error = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::LogError(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        logged_error,
+        Some(vec!["No tab at position 2".to_owned()]),
+        "error reported for a missing tab"
+    );
+}
+
+#[test]
+pub fn send_cli_wait_for_action_with_match() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let wait_for = CliAction::WaitFor {
+        pane_id: zellij_utils::data::PaneId::Terminal(0),
+        exit: false,
+        pattern: Some(r"listening on port \d+".to_owned()),
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, wait_for, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "server listening on port 8080".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let output = received_server_instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::CliPipeOutput(_pipe_id, output) => Some(output.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        output,
+        Some("listening on port 8080".to_owned()),
+        "matching text sent to the cli client"
+    );
+    assert!(
+        received_server_instructions
+            .iter()
+            .any(|instruction| matches!(instruction, ServerInstruction::UnblockCliPipeInput(..))),
+        "cli client unblocked"
+    );
+}
+
+#[test]
+pub fn send_cli_wait_for_action_with_match_ignores_earlier_output() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "server listening on port 8080\n\r".as_bytes().to_vec(),
+    ));
+    let wait_for = CliAction::WaitFor {
+        pane_id: zellij_utils::data::PaneId::Terminal(0),
+        exit: false,
+        pattern: Some(r"^server listening on port \d+".to_owned()),
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, wait_for, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "shutting down\n\rserver \u{1b}[32mlisten"
+            .as_bytes()
+            .to_vec(),
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "ing\u{1b}[m on port 9090\n\r".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let outputs: Vec<String> = received_server_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::CliPipeOutput(_pipe_id, output) => Some(output.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        outputs,
+        vec!["server listening on port 9090".to_owned()],
+        "only output printed after starting to wait is matched, across chunks and styling"
+    );
+}
+
+#[test]
+pub fn send_cli_wait_for_action_with_exit() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let wait_for = CliAction::WaitFor {
+        pane_id: zellij_utils::data::PaneId::Terminal(0),
+        exit: true,
+        pattern: None,
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, wait_for, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(3),
+        RunCommand::default(),
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let output = received_server_instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::CliPipeOutput(_pipe_id, output) => Some(output.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        output,
+        Some("3".to_owned()),
+        "exit status sent to the cli client"
+    );
+}
+
+#[test]
+pub fn send_cli_wait_for_action_with_missing_pane() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let wait_for = CliAction::WaitFor {
+        pane_id: zellij_utils::data::PaneId::Terminal(5),
+        exit: true,
+        pattern: None,
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, wait_for, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_error = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::LogError(lines, _client_id) => Some(lines.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        logged_error,
+        Some(vec!["No pane with id Terminal(5)".to_owned()]),
+        "error reported for a missing pane"
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
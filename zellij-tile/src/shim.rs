@@ -273,6 +273,14 @@ pub fn new_tabs_with_layout(layout: &str) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Provide a stringified [`layout`](https://zellij.dev/documentation/layouts.html) along with values for the variables it declares in its `vars` block. If the layout has multiple tabs, they will all be opened.
+pub fn new_tabs_with_layout_and_vars(layout: &str, layout_vars: BTreeMap<String, String>) {
+    let plugin_command = PluginCommand::NewTabsWithLayoutAndVars(layout.to_owned(), layout_vars);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Provide a LayoutInfo to be applied to the current session in a new tab. If the layout has multiple tabs, they will all be opened.
 pub fn new_tabs_with_layout_info(layout_info: LayoutInfo) {
     let plugin_command = PluginCommand::NewTabsWithLayoutInfo(layout_info);
@@ -615,6 +623,60 @@ pub fn close_plugin_pane(plugin_pane_id: u32) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Moves the pane with the specified id to the tab at the given position (starting at 1) or with
+/// the given name, creating the tab if it does not exist
+pub fn move_pane_to_tab(
+    pane_id: PaneId,
+    tab_position: Option<u32>,
+    tab_name: Option<&str>,
+    should_float: bool,
+) {
+    let plugin_command = PluginCommand::MovePaneToTab(
+        pane_id,
+        tab_position,
+        tab_name.map(|n| n.to_owned()),
+        should_float,
+    );
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Changes the coordinates and/or size of one or more floating panes, given as fixed or percent
+/// values
+pub fn change_floating_panes_coordinates(
+    pane_ids_and_coordinates: Vec<(PaneId, FloatingPaneCoordinates)>,
+) {
+    let plugin_command = PluginCommand::ChangeFloatingPanesCoordinates(pane_ids_and_coordinates);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stack the focused pane onto the pane next to it in the specified direction
+pub fn stack_pane(direction: Direction) {
+    let plugin_command = PluginCommand::StackPane(direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Take the focused pane out of its stack
+pub fn unstack_pane() {
+    let plugin_command = PluginCommand::UnstackPane;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stack all the panes of the focused tab together with the focused pane
+pub fn stack_all_panes() {
+    let plugin_command = PluginCommand::StackAllPanes;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Changes the focus to the terminal pane with the specified id, unsuppressing it if it was suppressed and switching to its tab and layer (eg. floating/tiled).
 pub fn focus_terminal_pane(terminal_pane_id: u32, should_float_if_hidden: bool) {
     let plugin_command = PluginCommand::FocusTerminalPane(terminal_pane_id, should_float_if_hidden);
@@ -5,7 +5,7 @@ pub struct Action {
     pub name: i32,
     #[prost(
         oneof = "action::OptionalPayload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53"
     )]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
@@ -108,6 +108,16 @@ pub mod action {
         MessagePayload(super::CliPipePayload),
         #[prost(enumeration = "super::MoveTabDirection", tag = "48")]
         MoveTabPayload(i32),
+        #[prost(string, tag = "49")]
+        SetTilingPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "50")]
+        MovePaneToTabPayload(super::MovePaneToTabPayload),
+        #[prost(message, tag = "51")]
+        MergeTabPayload(super::MergeTabPayload),
+        #[prost(enumeration = "super::super::resize::ResizeDirection", tag = "52")]
+        StackPanePayload(i32),
+        #[prost(message, tag = "53")]
+        ChangeFloatingPaneCoordinatesPayload(super::ChangeFloatingPaneCoordinatesPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -140,6 +150,52 @@ pub struct PaneIdAndShouldFloat {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MovePaneToTabPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(uint32, optional, tag = "3")]
+    pub tab_position: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "4")]
+    pub tab_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "5")]
+    pub should_float: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MergeTabPayload {
+    #[prost(uint32, optional, tag = "1")]
+    pub source_tab_position: ::core::option::Option<u32>,
+    #[prost(uint32, tag = "2")]
+    pub target_tab_position: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeFloatingPaneCoordinatesPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(message, optional, tag = "3")]
+    pub x: ::core::option::Option<SplitSizeValue>,
+    #[prost(message, optional, tag = "4")]
+    pub y: ::core::option::Option<SplitSizeValue>,
+    #[prost(message, optional, tag = "5")]
+    pub width: ::core::option::Option<SplitSizeValue>,
+    #[prost(message, optional, tag = "6")]
+    pub height: ::core::option::Option<SplitSizeValue>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplitSizeValue {
+    #[prost(uint32, tag = "1")]
+    pub value: u32,
+    #[prost(bool, tag = "2")]
+    pub is_percent: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewPluginPanePayload {
     #[prost(string, tag = "1")]
     pub plugin_url: ::prost::alloc::string::String,
@@ -271,6 +327,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag = "7")]
     pub hold_on_start: bool,
+    #[prost(message, repeated, tag = "8")]
+    pub env: ::prost::alloc::vec::Vec<NameAndValue>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -318,6 +376,7 @@ pub enum SearchOption {
     CaseSensitivity = 0,
     WholeWord = 1,
     Wrap = 2,
+    Regex = 3,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -329,6 +388,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "CaseSensitivity",
             SearchOption::WholeWord => "WholeWord",
             SearchOption::Wrap => "Wrap",
+            SearchOption::Regex => "Regex",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -337,6 +397,7 @@ impl SearchOption {
             "CaseSensitivity" => Some(Self::CaseSensitivity),
             "WholeWord" => Some(Self::WholeWord),
             "Wrap" => Some(Self::Wrap),
+            "Regex" => Some(Self::Regex),
             _ => None,
         }
     }
@@ -455,6 +516,22 @@ pub enum ActionName {
     CliPipe = 82,
     MoveTab = 83,
     KeybindPipe = 84,
+    ScrollToPreviousPrompt = 85,
+    ScrollToNextPrompt = 86,
+    CopyCommandOutput = 87,
+    SetTiling = 88,
+    PromoteToMaster = 89,
+    IncreaseMasterCount = 90,
+    DecreaseMasterCount = 91,
+    MovePaneToTab = 92,
+    MergeTab = 93,
+    TogglePanePinned = 94,
+    UndoLayoutChange = 95,
+    RedoLayoutChange = 96,
+    StackPane = 97,
+    UnstackPane = 98,
+    StackAllPanes = 99,
+    ChangeFloatingPaneCoordinates = 100,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -548,6 +625,22 @@ impl ActionName {
             ActionName::CliPipe => "CliPipe",
             ActionName::MoveTab => "MoveTab",
             ActionName::KeybindPipe => "KeybindPipe",
+            ActionName::ScrollToPreviousPrompt => "ScrollToPreviousPrompt",
+            ActionName::ScrollToNextPrompt => "ScrollToNextPrompt",
+            ActionName::CopyCommandOutput => "CopyCommandOutput",
+            ActionName::SetTiling => "SetTiling",
+            ActionName::PromoteToMaster => "PromoteToMaster",
+            ActionName::IncreaseMasterCount => "IncreaseMasterCount",
+            ActionName::DecreaseMasterCount => "DecreaseMasterCount",
+            ActionName::MovePaneToTab => "MovePaneToTab",
+            ActionName::MergeTab => "MergeTab",
+            ActionName::TogglePanePinned => "TogglePanePinned",
+            ActionName::UndoLayoutChange => "UndoLayoutChange",
+            ActionName::RedoLayoutChange => "RedoLayoutChange",
+            ActionName::StackPane => "StackPane",
+            ActionName::UnstackPane => "UnstackPane",
+            ActionName::StackAllPanes => "StackAllPanes",
+            ActionName::ChangeFloatingPaneCoordinates => "ChangeFloatingPaneCoordinates",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -638,6 +731,22 @@ impl ActionName {
             "CliPipe" => Some(Self::CliPipe),
             "MoveTab" => Some(Self::MoveTab),
             "KeybindPipe" => Some(Self::KeybindPipe),
+            "ScrollToPreviousPrompt" => Some(Self::ScrollToPreviousPrompt),
+            "ScrollToNextPrompt" => Some(Self::ScrollToNextPrompt),
+            "CopyCommandOutput" => Some(Self::CopyCommandOutput),
+            "SetTiling" => Some(Self::SetTiling),
+            "PromoteToMaster" => Some(Self::PromoteToMaster),
+            "IncreaseMasterCount" => Some(Self::IncreaseMasterCount),
+            "DecreaseMasterCount" => Some(Self::DecreaseMasterCount),
+            "MovePaneToTab" => Some(Self::MovePaneToTab),
+            "MergeTab" => Some(Self::MergeTab),
+            "TogglePanePinned" => Some(Self::TogglePanePinned),
+            "UndoLayoutChange" => Some(Self::UndoLayoutChange),
+            "RedoLayoutChange" => Some(Self::RedoLayoutChange),
+            "StackPane" => Some(Self::StackPane),
+            "UnstackPane" => Some(Self::UnstackPane),
+            "StackAllPanes" => Some(Self::StackAllPanes),
+            "ChangeFloatingPaneCoordinates" => Some(Self::ChangeFloatingPaneCoordinates),
             _ => None,
         }
     }
@@ -11,7 +11,7 @@ pub struct Event {
     pub name: i32,
     #[prost(
         oneof = "event::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16"
     )]
     pub payload: ::core::option::Option<event::Payload>,
 }
@@ -48,6 +48,8 @@ pub mod event {
         RunCommandResultPayload(super::RunCommandResultPayload),
         #[prost(message, tag = "15")]
         WebRequestResultPayload(super::WebRequestResultPayload),
+        #[prost(message, tag = "16")]
+        PaneNotificationPayload(super::PaneNotificationPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -84,6 +86,16 @@ pub struct WebRequestResultPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneNotificationPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, optional, tag = "2")]
+    pub title: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "3")]
+    pub body: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ContextItem {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
@@ -255,6 +267,20 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(int32, optional, tag = "23")]
+    pub last_command_exit_code: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "24")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "25")]
+    pub has_bell: bool,
+    #[prost(bool, tag = "26")]
+    pub has_activity: bool,
+    #[prost(bool, tag = "27")]
+    pub has_silence: bool,
+    #[prost(bool, tag = "28")]
+    pub is_pinned: bool,
+    #[prost(int32, optional, tag = "29")]
+    pub selected_command_exit_code: ::core::option::Option<i32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -279,6 +305,14 @@ pub struct TabInfo {
     pub active_swap_layout_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "10")]
     pub is_swap_layout_dirty: bool,
+    #[prost(bool, tag = "11")]
+    pub has_unseen_notification: bool,
+    #[prost(bool, tag = "12")]
+    pub has_bell: bool,
+    #[prost(bool, tag = "13")]
+    pub has_activity: bool,
+    #[prost(bool, tag = "14")]
+    pub has_silence: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -347,6 +381,8 @@ pub enum EventType {
     SessionUpdate = 16,
     RunCommandResult = 17,
     WebRequestResult = 18,
+    /// A terminal pane sent a desktop notification (through OSC 9 or OSC 777)
+    PaneNotification = 19,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -374,6 +410,7 @@ impl EventType {
             EventType::SessionUpdate => "SessionUpdate",
             EventType::RunCommandResult => "RunCommandResult",
             EventType::WebRequestResult => "WebRequestResult",
+            EventType::PaneNotification => "PaneNotification",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -398,6 +435,7 @@ impl EventType {
             "SessionUpdate" => Some(Self::SessionUpdate),
             "RunCommandResult" => Some(Self::RunCommandResult),
             "WebRequestResult" => Some(Self::WebRequestResult),
+            "PaneNotification" => Some(Self::PaneNotification),
             _ => None,
         }
     }
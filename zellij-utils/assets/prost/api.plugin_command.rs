@@ -5,7 +5,7 @@ pub struct PluginCommand {
     pub name: i32,
     #[prost(
         oneof = "plugin_command::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66"
     )]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
@@ -118,10 +118,26 @@ pub mod plugin_command {
         ScanHostFolderPayload(::prost::alloc::string::String),
         #[prost(message, tag = "62")]
         NewTabsWithLayoutInfoPayload(super::NewTabsWithLayoutInfoPayload),
+        #[prost(message, tag = "63")]
+        NewTabsWithLayoutAndVarsPayload(super::NewTabsWithLayoutAndVarsPayload),
+        #[prost(message, tag = "64")]
+        MovePaneToTabPayload(super::super::action::MovePaneToTabPayload),
+        #[prost(message, tag = "65")]
+        ChangeFloatingPanesCoordinatesPayload(super::ChangeFloatingPanesCoordinatesPayload),
+        #[prost(message, tag = "66")]
+        StackPanePayload(super::MovePayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NewTabsWithLayoutAndVarsPayload {
+    #[prost(string, tag = "1")]
+    pub layout: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub layout_vars: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewTabsWithLayoutInfoPayload {
     #[prost(message, optional, tag = "1")]
     pub layout_info: ::core::option::Option<super::event::LayoutInfo>,
@@ -341,6 +357,21 @@ pub struct FixedOrPercentValue {
     #[prost(uint32, tag = "2")]
     pub value: u32,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeFloatingPanesCoordinatesPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub pane_ids_and_floating_panes_coordinates:
+        ::prost::alloc::vec::Vec<PaneIdAndFloatingPaneCoordinates>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneIdAndFloatingPaneCoordinates {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag = "2")]
+    pub floating_pane_coordinates: ::core::option::Option<FloatingPaneCoordinates>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum CommandName {
@@ -431,6 +462,12 @@ pub enum CommandName {
     DumpSessionLayout = 84,
     CloseSelf = 85,
     NewTabsWithLayoutInfo = 86,
+    NewTabsWithLayoutAndVars = 87,
+    MovePaneToTab = 88,
+    ChangeFloatingPanesCoordinates = 89,
+    StackPane = 90,
+    UnstackPane = 91,
+    StackAllPanes = 92,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -526,6 +563,12 @@ impl CommandName {
             CommandName::DumpSessionLayout => "DumpSessionLayout",
             CommandName::CloseSelf => "CloseSelf",
             CommandName::NewTabsWithLayoutInfo => "NewTabsWithLayoutInfo",
+            CommandName::NewTabsWithLayoutAndVars => "NewTabsWithLayoutAndVars",
+            CommandName::MovePaneToTab => "MovePaneToTab",
+            CommandName::ChangeFloatingPanesCoordinates => "ChangeFloatingPanesCoordinates",
+            CommandName::StackPane => "StackPane",
+            CommandName::UnstackPane => "UnstackPane",
+            CommandName::StackAllPanes => "StackAllPanes",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -618,6 +661,12 @@ impl CommandName {
             "DumpSessionLayout" => Some(Self::DumpSessionLayout),
             "CloseSelf" => Some(Self::CloseSelf),
             "NewTabsWithLayoutInfo" => Some(Self::NewTabsWithLayoutInfo),
+            "NewTabsWithLayoutAndVars" => Some(Self::NewTabsWithLayoutAndVars),
+            "MovePaneToTab" => Some(Self::MovePaneToTab),
+            "ChangeFloatingPanesCoordinates" => Some(Self::ChangeFloatingPanesCoordinates),
+            "StackPane" => Some(Self::StackPane),
+            "UnstackPane" => Some(Self::UnstackPane),
+            "StackAllPanes" => Some(Self::StackAllPanes),
             _ => None,
         }
     }
@@ -1,4 +1,4 @@
-use crate::data::{Direction, InputMode, Resize};
+use crate::data::{Direction, InputMode, PaneId, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
@@ -33,6 +33,31 @@ fn validate_session(name: &str) -> Result<String, String> {
     Ok(name.to_owned())
 }
 
+fn parse_env_var(env_var: &str) -> Result<(String, String), String> {
+    parse_key_value(env_var).ok_or_else(|| {
+        format!(
+            "environment variables must be in the KEY=VALUE format, got: {}",
+            env_var
+        )
+    })
+}
+
+fn parse_layout_var(layout_var: &str) -> Result<(String, String), String> {
+    parse_key_value(layout_var).ok_or_else(|| {
+        format!(
+            "layout variables must be in the NAME=VALUE format, got: {}",
+            layout_var
+        )
+    })
+}
+
+fn parse_key_value(key_value: &str) -> Option<(String, String)> {
+    match key_value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Some((key.to_owned(), value.to_owned())),
+        _ => None,
+    }
+}
+
 #[derive(Parser, Default, Debug, Clone, Serialize, Deserialize)]
 #[clap(version, name = "zellij")]
 pub struct CliArgs {
@@ -56,6 +81,10 @@ pub struct CliArgs {
     #[clap(short, long, value_parser, overrides_with = "layout")]
     pub layout: Option<PathBuf>,
 
+    /// Set a variable declared by the layout, can be specified multiple times (eg. --layout-var service=billing)
+    #[clap(long, value_parser = parse_layout_var)]
+    pub layout_var: Vec<(String, String)>,
+
     /// Change where zellij looks for the configuration file
     #[clap(short, long, overrides_with = "config", env = ZELLIJ_CONFIG_FILE_ENV, value_parser)]
     pub config: Option<PathBuf>,
@@ -110,6 +139,10 @@ pub enum Sessions {
         /// List the sessions in reverse order (default is ascending order)
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         reverse: bool,
+
+        /// Print the sessions as JSON (useful for scripting)
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
     },
     /// List existing plugin aliases
     #[clap(visible_alias = "la")]
@@ -140,6 +173,11 @@ pub enum Sessions {
         /// If resurrecting a dead session, immediately run all its commands on startup
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         force_run_commands: bool,
+
+        /// Attach as an observer: render the session and move between its tabs and panes without
+        /// being able to change it (the session keeps the size of the other clients)
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        read_only: bool,
     },
 
     /// Kill a specific session
@@ -182,8 +220,18 @@ pub enum Sessions {
 
     /// Send actions to a specific session
     #[clap(visible_alias = "ac")]
-    #[clap(subcommand)]
-    Action(CliAction),
+    Action {
+        /// Apply the action to this pane instead of the focused one, eg. terminal_1, plugin_2 or
+        /// 3 (for terminal_3)
+        #[clap(long, value_parser)]
+        pane_id: Option<PaneId>,
+        /// Apply the action to the focused pane of the tab at this position (starting at 1)
+        /// instead of the focused tab
+        #[clap(long, value_parser)]
+        tab: Option<u32>,
+        #[clap(subcommand)]
+        action: CliAction,
+    },
     /// Run a command in a new pane
     #[clap(visible_alias = "r")]
     Run {
@@ -227,6 +275,10 @@ pub enum Sessions {
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         start_suspended: bool,
 
+        /// Set an environment variable for the command, can be specified multiple times (eg. --env KEY=VALUE)
+        #[clap(long, value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+
         /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
         #[clap(short, long, requires("floating"))]
         x: Option<String>,
@@ -421,7 +473,11 @@ pub enum CliAction {
         full: bool,
     },
     /// Dump current layout to stdout
-    DumpLayout,
+    DumpLayout {
+        /// Print the tabs and panes of the session as JSON instead of as a KDL layout
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
     /// Open the pane scrollback in your default editor
     EditScrollback,
     /// Scroll up in the focused pane
@@ -432,6 +488,16 @@ pub enum CliAction {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt in focus pane (requires a shell emitting OSC 133
+    /// prompt marks)
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt in focus pane (requires a shell emitting OSC 133
+    /// prompt marks)
+    ScrollToNextPrompt,
+    /// Copy the output of the last command in the focused pane to the clipboard, or of the
+    /// command at the top of the viewport if the pane is scrolled (requires a shell emitting OSC
+    /// 133 prompt marks)
+    CopyCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -503,6 +569,9 @@ pub enum CliAction {
             requires("command")
         )]
         start_suspended: bool,
+        /// Set an environment variable for the command, can be specified multiple times (eg. --env KEY=VALUE)
+        #[clap(long, value_parser = parse_env_var, requires("command"))]
+        env: Vec<(String, String)>,
         #[clap(long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
         #[clap(long, value_parser)]
@@ -572,6 +641,23 @@ pub enum CliAction {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes in the current Tab, open one if none exist
     ToggleFloatingPanes,
+    /// Pin or unpin the focused floating pane, pinned panes stay visible when the floating panes
+    /// are hidden (does nothing unless a floating pane is focused)
+    TogglePanePinned,
+    /// Undo the last change to the pane layout of the current tab, closed command panes are
+    /// re-opened with their command waiting to be re-run
+    UndoLayoutChange,
+    /// Redo the last undone change to the pane layout of the current tab
+    RedoLayoutChange,
+    /// Stack the focused pane onto the pane next to it in the specified direction
+    /// [right|left|up|down]
+    StackPane {
+        direction: Direction,
+    },
+    /// Take the focused pane out of its stack
+    UnstackPane,
+    /// Stack all the panes of the current tab together with the focused pane
+    StackAllPanes,
     /// Close the focused pane.
     ClosePane,
     /// Renames the focused pane
@@ -627,8 +713,64 @@ pub enum CliAction {
     },
     PreviousSwapLayout,
     NextSwapLayout,
+    /// Set the dynamic tiling algorithm of the current tab
+    /// [master_stack|spiral|equal_columns|none]
+    SetTiling {
+        algorithm: String,
+    },
+    /// Move the focused pane to the master position of the dynamic tiling layout
+    PromoteToMaster,
+    /// Increase the number of master panes in the dynamic tiling layout
+    IncreaseMasterCount,
+    /// Decrease the number of master panes in the dynamic tiling layout
+    DecreaseMasterCount,
+    /// Move a pane to another tab, creating the tab if it does not exist
+    MovePaneToTab {
+        /// The id of the pane to move, eg. terminal_1, plugin_2 or 3 (for terminal_3)
+        #[clap(short, long, value_parser)]
+        pane_id: PaneId,
+        /// The position of the tab to move the pane to, starting at 1
+        #[clap(short, long, value_parser, conflicts_with("tab-name"))]
+        tab_index: Option<u32>,
+        /// The name of the tab to move the pane to
+        #[clap(short = 'n', long, value_parser)]
+        tab_name: Option<String>,
+        /// Make the pane floating in its new tab
+        #[clap(short, long, value_parser)]
+        floating: bool,
+    },
+    /// Change the coordinates and size of an existing floating pane
+    ChangeFloatingPaneCoordinates {
+        /// The id of the floating pane, eg. terminal_1, plugin_2 or 3 (for terminal_3)
+        #[clap(short, long, value_parser)]
+        pane_id: PaneId,
+        /// The x coordinates as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long)]
+        x: Option<String>,
+        /// The y coordinates as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long)]
+        y: Option<String>,
+        /// The width as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long)]
+        width: Option<String>,
+        /// The height as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long)]
+        height: Option<String>,
+    },
+    /// Move all panes of a tab into another tab and close it
+    MergeTab {
+        /// The position of the tab to merge into, starting at 1
+        target_index: u32,
+        /// The position of the tab to merge, starting at 1 (defaults to the focused tab)
+        #[clap(short, long, value_parser)]
+        source_index: Option<u32>,
+    },
     /// Query all tab names
-    QueryTabNames,
+    QueryTabNames {
+        /// Print the tabs as JSON instead of just their names
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
     StartOrReloadPlugin {
         url: String,
         #[clap(short, long, value_parser)]
@@ -736,5 +878,51 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
     },
-    ListClients,
+    ListClients {
+        /// Print the clients as JSON
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// List the panes of the session with their ids, titles, commands, focus and geometry
+    ListPanes {
+        /// Only list the panes of the tab in this position, starting at 1
+        #[clap(short, long, value_parser, conflicts_with("tab-name"))]
+        tab_index: Option<u32>,
+        /// Only list the panes of the tab with this name
+        #[clap(short = 'n', long, value_parser)]
+        tab_name: Option<String>,
+        /// Print the panes as JSON
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// List the tabs of the session with their index, focus and pane count
+    ListTabs {
+        /// Print the tabs as JSON
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// Wait until the command of a pane exits or until the output of a pane matches a regular
+    /// expression
+    WaitFor {
+        /// The id of the pane, eg. terminal_1 or 3 (for terminal_3)
+        #[clap(short, long, value_parser)]
+        pane_id: PaneId,
+        /// Wait for the command of the pane to exit, then print its exit status and exit with it
+        #[clap(
+            short,
+            long,
+            value_parser,
+            takes_value(false),
+            default_value("false"),
+            conflicts_with("pattern")
+        )]
+        exit: bool,
+        /// Wait for a line of output of the pane to match this regular expression, then print the
+        /// matching text (output printed before starting to wait does not count)
+        #[clap(short = 'm', long = "match", value_parser)]
+        pattern: Option<String>,
+        /// Give up after this many seconds, exiting with status 124
+        #[clap(short, long, value_parser)]
+        timeout: Option<u64>,
+    },
 }
@@ -876,6 +876,12 @@ pub enum Event {
     ),
     RunCommandResult(Option<i32>, Vec<u8>, Vec<u8>, BTreeMap<String, String>), // exit_code, STDOUT, STDERR,
     // context
+    /// A terminal pane sent a desktop notification (through OSC 9 or OSC 777)
+    PaneNotification {
+        pane_id: u32, // the id of the terminal pane
+        title: Option<String>,
+        body: String,
+    },
     WebRequestResult(
         u16,
         BTreeMap<String, String>,
@@ -1213,6 +1219,17 @@ pub struct TabInfo {
     pub active_swap_layout_name: Option<String>,
     /// Whether the user manually changed the layout, moving out of the swap layout scheme
     pub is_swap_layout_dirty: bool,
+    /// Whether a pane in this tab sent a notification that was not yet seen (its pane was not
+    /// focused since)
+    pub has_unseen_notification: bool,
+    /// Whether a pane in this tab rang the bell since it was last focused
+    pub has_bell: bool,
+    /// Whether a pane in this tab that monitors activity received output since it was last
+    /// focused
+    pub has_activity: bool,
+    /// Whether a pane in this tab that monitors silence did not receive output for its
+    /// configured duration
+    pub has_silence: bool,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -1245,6 +1262,9 @@ pub struct PaneInfo {
     pub is_fullscreen: bool,
     /// Whether a pane is floating or tiled (embedded)
     pub is_floating: bool,
+    /// Whether a floating pane is pinned - pinned panes remain visible above the tiled panes when
+    /// the floating panes are hidden
+    pub is_pinned: bool,
     /// Whether a pane is suppressed - suppressed panes are not visible to the user, but still run
     /// in the background
     pub is_suppressed: bool,
@@ -1255,6 +1275,21 @@ pub struct PaneInfo {
     pub exited: bool,
     /// The exit status of a pane if it did exit and is still in the UI
     pub exit_status: Option<i32>,
+    /// The exit code of the last command run inside this pane, as reported by the shell through
+    /// OSC 133 (semantic prompt) marks
+    pub last_command_exit_code: Option<i32>,
+    /// The exit code of the command whose output was last selected in this pane (eg. to copy it),
+    /// as reported by the shell through OSC 133 (semantic prompt) marks
+    pub selected_command_exit_code: Option<i32>,
+    /// The working directory of this pane, as reported by the shell through OSC 7
+    pub cwd: Option<PathBuf>,
+    /// Whether this pane rang the bell since it was last focused
+    pub has_bell: bool,
+    /// Whether this pane received output since it was last focused (only if it monitors activity)
+    pub has_activity: bool,
+    /// Whether this pane did not receive output for its configured duration (only if it monitors
+    /// silence)
+    pub has_silence: bool,
     /// A "held" pane is a paused pane that is waiting for user input (eg. a command pane that
     /// exited and is waiting to be re-run or closed)
     pub is_held: bool,
@@ -1363,6 +1398,7 @@ pub struct CommandToRun {
     pub path: PathBuf,
     pub args: Vec<String>,
     pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl CommandToRun {
@@ -1379,6 +1415,10 @@ impl CommandToRun {
             ..Default::default()
         }
     }
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1403,12 +1443,30 @@ pub struct NewPluginArgs {
     pub skip_cache: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum PaneId {
     Terminal(u32),
     Plugin(u32),
 }
 
+impl FromStr for PaneId {
+    type Err = String;
+    // terminal_<id>, plugin_<id> or a bare <id> for terminal panes
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_id = |id: &str| {
+            id.parse::<u32>()
+                .map_err(|_| format!("Invalid pane id: {}", s))
+        };
+        if let Some(id) = s.strip_prefix("terminal_") {
+            Ok(PaneId::Terminal(parse_id(id)?))
+        } else if let Some(id) = s.strip_prefix("plugin_") {
+            Ok(PaneId::Plugin(parse_id(id)?))
+        } else {
+            Ok(PaneId::Terminal(parse_id(s)?))
+        }
+    }
+}
+
 impl MessageToPlugin {
     pub fn new(message_name: impl Into<String>) -> Self {
         MessageToPlugin {
@@ -1733,4 +1791,12 @@ pub enum PluginCommand {
     DumpSessionLayout,
     CloseSelf,
     NewTabsWithLayoutInfo(LayoutInfo),
+    NewTabsWithLayoutAndVars(String, BTreeMap<String, String>), // raw kdl layout, layout vars
+    /// Move a pane to the tab at the given position (starting at 1) or with the given name,
+    /// floating it if the bool is true
+    MovePaneToTab(PaneId, Option<u32>, Option<String>, bool),
+    ChangeFloatingPanesCoordinates(Vec<(PaneId, FloatingPaneCoordinates)>),
+    StackPane(Direction),
+    UnstackPane,
+    StackAllPanes,
 }
@@ -219,6 +219,12 @@ pub enum ScreenContext {
     ShowFloatingPanes,
     HideFloatingPanes,
     TogglePaneEmbedOrFloating,
+    TogglePanePinned,
+    UndoLayoutChange,
+    RedoLayoutChange,
+    StackPane,
+    UnstackPane,
+    StackAllPanes,
     HorizontalSplit,
     VerticalSplit,
     WriteCharacter,
@@ -265,6 +271,8 @@ pub enum ScreenContext {
     ScrollDownAt,
     ScrollToBottom,
     ScrollToTop,
+    ScrollToPreviousPrompt,
+    ScrollToNextPrompt,
     PageScrollUp,
     PageScrollDown,
     HalfPageScrollUp,
@@ -310,6 +318,7 @@ pub enum ScreenContext {
     MouseHoldRight,
     MouseHoldMiddle,
     Copy,
+    CopyCommandOutput,
     ToggleTab,
     AddClient,
     RemoveClient,
@@ -323,10 +332,14 @@ pub enum ScreenContext {
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
     SearchToggleWrap,
+    SearchToggleRegex,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
     PreviousSwapLayout,
     NextSwapLayout,
+    SetTiling,
+    PromoteToMaster,
+    ChangeMasterCount,
     QueryTabNames,
     NewTiledPluginPane,
     StartOrReloadPluginPane,
@@ -344,15 +357,24 @@ pub enum ScreenContext {
     RenameTab,
     RequestPluginPermissions,
     BreakPane,
+    MovePaneToTab,
+    MergeTabs,
+    ChangeFloatingPanesCoordinates,
     BreakPaneRight,
     BreakPaneLeft,
     UpdateSessionInfos,
+    CheckPaneSilence,
     ReplacePane,
     NewInPlacePluginPane,
     DumpLayoutToHd,
     RenameSession,
     DumpLayoutToPlugin,
     ListClientsMetadata,
+    ListPanes,
+    ListTabs,
+    SetActionTarget,
+    ClearActionTarget,
+    WaitForPane,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -375,6 +397,7 @@ pub enum PtyContext {
     FillPluginCwd,
     DumpLayoutToPlugin,
     ListClientsMetadata,
+    ReportTerminalCwd,
     Exit,
 }
 
@@ -471,6 +494,8 @@ pub enum BackgroundJobContext {
     AnimatePluginLoading,
     StopPluginLoadingAnimation,
     ReadAllSessionInfosOnMachine,
+    MonitorPaneSilence,
+    StopMonitoringPaneSilence,
     ReportSessionInfo,
     ReportLayoutInfo,
     RunCommand,
@@ -3,15 +3,16 @@
 use super::command::RunCommandAction;
 use super::layout::{
     FloatingPaneLayout, Layout, PluginAlias, RunPlugin, RunPluginLocation, RunPluginOrAlias,
-    SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+    SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout, TilingAlgorithm,
 };
 use crate::cli::CliAction;
 use crate::data::{Direction, KeyWithModifier, Resize};
-use crate::data::{FloatingPaneCoordinates, InputMode};
+use crate::data::{FloatingPaneCoordinates, InputMode, PaneId};
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::options::OnForceClose;
 use miette::{NamedSource, Report};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use uuid::Uuid;
@@ -48,6 +49,15 @@ impl FromStr for ResizeDirection {
     }
 }
 
+/// What a CLI client waits for with `Action::WaitFor`
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum WaitCondition {
+    /// The command of the pane exited
+    Exit,
+    /// A line printed to the pane after starting to wait matches this regular expression
+    Match(String),
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum SearchDirection {
     Down,
@@ -73,6 +83,7 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
 }
 
 impl FromStr for SearchOption {
@@ -84,6 +95,7 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" => Ok(SearchOption::Regex),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -126,8 +138,8 @@ pub enum Action {
     ClearScreen,
     /// Dumps the screen to a file
     DumpScreen(String, bool),
-    /// Dumps
-    DumpLayout,
+    /// Dumps the layout of the session, as JSON if the bool is true
+    DumpLayout(bool),
     /// Scroll up in focus pane.
     EditScrollback,
     ScrollUp,
@@ -141,6 +153,10 @@ pub enum Action {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt (as marked by OSC 133) in focus pane.
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt (as marked by OSC 133) in focus pane.
+    ScrollToNextPrompt,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -184,6 +200,20 @@ pub enum Action {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes (if any) in the current Tab
     ToggleFloatingPanes,
+    /// Pin or unpin the focused floating pane, pinned panes stay visible when the floating panes
+    /// are hidden (does nothing unless a floating pane is focused)
+    TogglePanePinned,
+    /// Undo the last change to the pane layout of the current Tab (resizing, moving, closing or
+    /// toggling panes)
+    UndoLayoutChange,
+    /// Redo the last undone change to the pane layout of the current Tab
+    RedoLayoutChange,
+    /// Stack the focused pane onto the pane next to it in the given direction
+    StackPane(Direction),
+    /// Take the focused pane out of its stack
+    UnstackPane,
+    /// Stack all the panes of the current Tab together with the focused pane
+    StackAllPanes,
     /// Close the focus pane.
     CloseFocus,
     PaneNameInput(Vec<u8>),
@@ -228,6 +258,9 @@ pub enum Action {
     MouseHoldRight(Position),
     MouseHoldMiddle(Position),
     Copy,
+    /// Select and copy the output of the last command (or the command at the top of the viewport
+    /// if the pane is scrolled) in focus pane, as marked by OSC 133
+    CopyCommandOutput,
     /// Confirm a prompt
     Confirm,
     /// Deny a prompt
@@ -243,8 +276,27 @@ pub enum Action {
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
-    /// Query all tab names
-    QueryTabNames,
+    /// Set the dynamic tiling algorithm of the current tab, None turns dynamic tiling off
+    SetTiling(Option<TilingAlgorithm>),
+    /// Move the focused pane to the first master position of the dynamic tiling layout
+    PromoteToMaster,
+    /// Increase the number of master panes in the dynamic tiling layout
+    IncreaseMasterCount,
+    /// Decrease the number of master panes in the dynamic tiling layout
+    DecreaseMasterCount,
+    /// Move a pane to the tab at the given position (starting at 1) or with the given name,
+    /// creating the tab if it does not exist, and floating the pane if the bool is true
+    MovePaneToTab(PaneId, Option<u32>, Option<String>, bool),
+    /// Change the coordinates and size of an existing floating pane
+    ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
+    /// Move all panes of the tab at the source position (the active tab if None) into the tab at
+    /// the target position and close it, both positions starting at 1
+    MergeTab(Option<u32>, u32),
+    /// Query all tab names, as JSON tab infos if the bool is true
+    QueryTabNames(bool),
+    /// Apply an action to the given pane and/or the tab at the given position (starting at 1)
+    /// instead of the focused ones
+    ApplyToTarget(Option<PaneId>, Option<u32>, Box<Action>),
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPluginOrAlias, Option<String>, bool, Option<PathBuf>), // String is an optional name, bool is
     // skip_cache, Option<PathBuf> is cwd
@@ -297,7 +349,21 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
-    ListClients,
+    /// List the connected clients, as JSON if the bool is true
+    ListClients(bool),
+    /// List the panes, only those of the tab at the given position (starting at 1) or with the
+    /// given name if either is set, as JSON if the bool is true
+    ListPanes(Option<u32>, Option<String>, bool),
+    /// List the tabs along with their position, whether they are active and their pane count, as
+    /// JSON if the bool is true
+    ListTabs(bool),
+    /// Block the CLI client until a pane meets the given condition
+    WaitFor {
+        pipe_id: String,
+        pane_id: PaneId,
+        condition: WaitCondition,
+        timeout: Option<u64>, // in seconds
+    },
 }
 
 impl Action {
@@ -311,6 +377,54 @@ impl Action {
         }
     }
 
+    /// Like `actions_from_cli`, but applies the resulting actions to the given pane and/or tab
+    /// rather than to the ones focused by the CLI client
+    pub fn targeted_actions_from_cli(
+        cli_action: CliAction,
+        pane_id: Option<PaneId>,
+        tab_position: Option<u32>,
+        get_current_dir: Box<dyn Fn() -> PathBuf>,
+        config: Option<Config>,
+    ) -> Result<Vec<Action>, String> {
+        let actions = Action::actions_from_cli(cli_action, get_current_dir, config)?;
+        if pane_id.is_none() && tab_position.is_none() {
+            return Ok(actions);
+        }
+        if tab_position == Some(0) {
+            return Err("Tab positions start at 1".to_owned());
+        }
+        if let Some(action) = actions.iter().find(|action| !action.can_be_targeted()) {
+            return Err(format!(
+                "--pane-id and --tab are not supported for {:?}",
+                action
+            ));
+        }
+        Ok(actions
+            .into_iter()
+            .map(|action| Action::ApplyToTarget(pane_id, tab_position, Box::new(action)))
+            .collect())
+    }
+    // actions that open panes or pipes reach the screen through the pty or plugin threads, only
+    // after the target of the client was cleared
+    fn can_be_targeted(&self) -> bool {
+        !matches!(
+            self,
+            Action::NewPane(..)
+                | Action::NewFloatingPane(..)
+                | Action::NewTiledPane(..)
+                | Action::NewInPlacePane(..)
+                | Action::EditFile(..)
+                | Action::Run(..)
+                | Action::NewTiledPluginPane(..)
+                | Action::NewFloatingPluginPane(..)
+                | Action::NewInPlacePluginPane(..)
+                | Action::StartOrReloadPlugin(..)
+                | Action::LaunchOrFocusPlugin(..)
+                | Action::LaunchPlugin(..)
+                | Action::CliPipe { .. }
+                | Action::KeybindPipe { .. }
+        )
+    }
     pub fn actions_from_cli(
         cli_action: CliAction,
         get_current_dir: Box<dyn Fn() -> PathBuf>,
@@ -332,12 +446,15 @@ impl Action {
                 path.as_os_str().to_string_lossy().into(),
                 full,
             )]),
-            CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
+            CliAction::DumpLayout { json } => Ok(vec![Action::DumpLayout(json)]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
             CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottom]),
             CliAction::ScrollToTop => Ok(vec![Action::ScrollToTop]),
+            CliAction::ScrollToPreviousPrompt => Ok(vec![Action::ScrollToPreviousPrompt]),
+            CliAction::ScrollToNextPrompt => Ok(vec![Action::ScrollToNextPrompt]),
+            CliAction::CopyCommandOutput => Ok(vec![Action::CopyCommandOutput]),
             CliAction::PageScrollUp => Ok(vec![Action::PageScrollUp]),
             CliAction::PageScrollDown => Ok(vec![Action::PageScrollDown]),
             CliAction::HalfPageScrollUp => Ok(vec![Action::HalfPageScrollUp]),
@@ -355,6 +472,7 @@ impl Action {
                 name,
                 close_on_exit,
                 start_suspended,
+                env,
                 configuration,
                 skip_plugin_cache,
                 x,
@@ -432,6 +550,7 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        env: env.into_iter().collect(),
                     };
                     if floating {
                         Ok(vec![Action::NewFloatingPane(
@@ -498,6 +617,12 @@ impl Action {
                 Ok(vec![Action::SwitchModeForAllClients(input_mode)])
             },
             CliAction::TogglePaneEmbedOrFloating => Ok(vec![Action::TogglePaneEmbedOrFloating]),
+            CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
+            CliAction::UndoLayoutChange => Ok(vec![Action::UndoLayoutChange]),
+            CliAction::RedoLayoutChange => Ok(vec![Action::RedoLayoutChange]),
+            CliAction::StackPane { direction } => Ok(vec![Action::StackPane(direction)]),
+            CliAction::UnstackPane => Ok(vec![Action::UnstackPane]),
+            CliAction::StackAllPanes => Ok(vec![Action::StackAllPanes]),
             CliAction::ToggleFloatingPanes => Ok(vec![Action::ToggleFloatingPanes]),
             CliAction::ClosePane => Ok(vec![Action::CloseFocus]),
             CliAction::RenamePane { name } => Ok(vec![
@@ -614,7 +739,44 @@ impl Action {
             },
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
-            CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
+            CliAction::SetTiling { algorithm } => {
+                if algorithm.eq_ignore_ascii_case("none") {
+                    Ok(vec![Action::SetTiling(None)])
+                } else {
+                    let algorithm =
+                        TilingAlgorithm::from_str(&algorithm).map_err(|e| e.to_string())?;
+                    Ok(vec![Action::SetTiling(Some(algorithm))])
+                }
+            },
+            CliAction::PromoteToMaster => Ok(vec![Action::PromoteToMaster]),
+            CliAction::IncreaseMasterCount => Ok(vec![Action::IncreaseMasterCount]),
+            CliAction::DecreaseMasterCount => Ok(vec![Action::DecreaseMasterCount]),
+            CliAction::MovePaneToTab {
+                pane_id,
+                tab_index,
+                tab_name,
+                floating,
+            } => Ok(vec![Action::MovePaneToTab(
+                pane_id, tab_index, tab_name, floating,
+            )]),
+            CliAction::ChangeFloatingPaneCoordinates {
+                pane_id,
+                x,
+                y,
+                width,
+                height,
+            } => match FloatingPaneCoordinates::new(x, y, width, height) {
+                Some(coordinates) => Ok(vec![Action::ChangeFloatingPaneCoordinates(
+                    pane_id,
+                    coordinates,
+                )]),
+                None => Err("At least one of x, y, width or height must be specified".to_owned()),
+            },
+            CliAction::MergeTab {
+                target_index,
+                source_index,
+            } => Ok(vec![Action::MergeTab(source_index, target_index)]),
+            CliAction::QueryTabNames { json } => Ok(vec![Action::QueryTabNames(json)]),
             CliAction::StartOrReloadPlugin { url, configuration } => {
                 let current_dir = get_current_dir();
                 let run_plugin_or_alias = RunPluginOrAlias::from_url(
@@ -706,7 +868,35 @@ impl Action {
                     skip_cache,
                 }])
             },
-            CliAction::ListClients => Ok(vec![Action::ListClients]),
+            CliAction::ListClients { json } => Ok(vec![Action::ListClients(json)]),
+            CliAction::ListPanes {
+                tab_index,
+                tab_name,
+                json,
+            } => Ok(vec![Action::ListPanes(tab_index, tab_name, json)]),
+            CliAction::ListTabs { json } => Ok(vec![Action::ListTabs(json)]),
+            CliAction::WaitFor {
+                pane_id,
+                exit,
+                pattern,
+                timeout,
+            } => {
+                let condition = match pattern {
+                    Some(pattern) => {
+                        Regex::new(&pattern)
+                            .map_err(|e| format!("Invalid regular expression: {}", e))?;
+                        WaitCondition::Match(pattern)
+                    },
+                    None if exit => WaitCondition::Exit,
+                    None => return Err("Either --exit or --match must be specified".to_owned()),
+                };
+                Ok(vec![Action::WaitFor {
+                    pipe_id: Uuid::new_v4().to_string(),
+                    pane_id,
+                    condition,
+                    timeout,
+                }])
+            },
         }
     }
 }
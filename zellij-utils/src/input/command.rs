@@ -1,6 +1,7 @@
 //! Trigger a command
 use crate::data::Direction;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -35,6 +36,8 @@ pub struct RunCommand {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>, // environment variables set for the command
 }
 
 impl std::fmt::Display for RunCommand {
@@ -68,6 +71,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>, // environment variables set for the command
 }
 
 impl From<RunCommandAction> for RunCommand {
@@ -78,6 +83,7 @@ impl From<RunCommandAction> for RunCommand {
             cwd: action.cwd,
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
+            env: action.env,
         }
     }
 }
@@ -91,6 +97,7 @@ impl From<RunCommand> for RunCommandAction {
             direction: None,
             hold_on_close: run_command.hold_on_close,
             hold_on_start: run_command.hold_on_start,
+            env: run_command.env,
         }
     }
 }
@@ -320,6 +320,7 @@ mod config_test {
             mirror_session true
             on_force_close "quit"
             scroll_buffer_size 100000
+            scrollback_to_disk true
             copy_command "/path/to/my/copy-command"
             copy_clipboard "primary"
             copy_on_select false
@@ -393,6 +394,11 @@ mod config_test {
             Some(100000),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.scrollback_to_disk,
+            Some(true),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.copy_command,
             Some(String::from("/path/to/my/copy-command")),
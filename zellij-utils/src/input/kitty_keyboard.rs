@@ -0,0 +1,401 @@
+//! Parsing and serialization of key events in the kitty keyboard protocol, for more info please
+//! see: https://sw.kovidgoyal.net/kitty/keyboard-protocol
+use crate::data::{BareKey, KeyModifier, KeyWithModifier};
+
+bitflags::bitflags! {
+    /// The progressive enhancements an application can request from the terminal
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct KittyKeyboardFlags: u8 {
+        const DISAMBIGUATE_ESCAPE_CODES = 0b0000_0001;
+        const REPORT_EVENT_TYPES = 0b0000_0010;
+        const REPORT_ALTERNATE_KEYS = 0b0000_0100;
+        const REPORT_ALL_KEYS_AS_ESCAPE_CODES = 0b0000_1000;
+        const REPORT_ASSOCIATED_TEXT = 0b0001_0000;
+    }
+}
+
+// modifier bits, as they appear in the protocol (after subtracting 1 from the modifier parameter)
+const SHIFT: u8 = 0b0000_0001;
+const ALT: u8 = 0b0000_0010;
+const CONTROL: u8 = 0b0000_0100;
+const SUPER: u8 = 0b0000_1000;
+const HYPER: u8 = 0b0001_0000;
+const META: u8 = 0b0010_0000;
+const CAPS_LOCK: u8 = 0b0100_0000;
+const NUM_LOCK: u8 = 0b1000_0000;
+const LOCK_MODIFIERS: u8 = CAPS_LOCK | NUM_LOCK;
+
+// the key codes of functional keys without a unicode representation live in the private use area
+const FUNCTIONAL_KEY_CODES: std::ops::RangeInclusive<u32> = 57344..=63743;
+const F13: u32 = 57376;
+const F35: u32 = 57398;
+const KP_0: u32 = 57399;
+const KP_9: u32 = 57408;
+
+// we give up on sequences longer than this, they are certainly not key events
+const MAX_PARAMS_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventType {
+    Press,
+    Repeat,
+    Release,
+}
+
+impl KeyEventType {
+    fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(KeyEventType::Press),
+            2 => Some(KeyEventType::Repeat),
+            3 => Some(KeyEventType::Release),
+            _ => None,
+        }
+    }
+    fn to_number(self) -> u32 {
+        match self {
+            KeyEventType::Press => 1,
+            KeyEventType::Repeat => 2,
+            KeyEventType::Release => 3,
+        }
+    }
+}
+
+/// A key event as reported by a terminal in the kitty keyboard protocol:
+/// CSI key-code[:shifted-key[:base-layout-key]] ; modifiers[:event-type] ; text-as-codepoints u
+/// (or ~ and the legacy letter terminators for some functional keys)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KittyKeyEvent {
+    pub key_code: u32,
+    pub shifted_key: Option<u32>,
+    pub base_layout_key: Option<u32>,
+    pub modifiers: u8,
+    pub event_type: KeyEventType,
+    pub text: Option<String>,
+    pub terminator: u8,
+}
+
+impl KittyKeyEvent {
+    /// Parses the key event at the start of `bytes`, returning it along with the number of bytes
+    /// it spans
+    pub fn parse(bytes: &[u8]) -> Option<(Self, usize)> {
+        if !bytes.starts_with(b"\x1b[") {
+            return None;
+        }
+        let terminator_index = bytes
+            .iter()
+            .skip(2)
+            .take(MAX_PARAMS_LENGTH)
+            .position(|b| !(b.is_ascii_digit() || *b == b';' || *b == b':'))?
+            + 2;
+        let terminator = bytes[terminator_index];
+        let params = std::str::from_utf8(&bytes[2..terminator_index]).ok()?;
+        let mut params = params.split(';');
+
+        let mut key_codes = params.next().unwrap_or("").split(':');
+        let key_code = optional_number(key_codes.next())?;
+        let shifted_key = optional_number(key_codes.next())?;
+        let base_layout_key = optional_number(key_codes.next())?;
+        if key_codes.next().is_some() {
+            return None;
+        }
+
+        let mut modifier_params = params.next().unwrap_or("").split(':');
+        let modifiers = optional_number(modifier_params.next())?.unwrap_or(1);
+        let modifiers = u8::try_from(modifiers.checked_sub(1)?).ok()?;
+        let event_type =
+            KeyEventType::from_number(optional_number(modifier_params.next())?.unwrap_or(1))?;
+        if modifier_params.next().is_some() {
+            return None;
+        }
+
+        let text = match params.next() {
+            Some(text) => Some(
+                text.split(':')
+                    .map(|c| c.parse::<u32>().ok().and_then(char::from_u32))
+                    .collect::<Option<String>>()?,
+            ),
+            None => None,
+        };
+        if params.next().is_some() {
+            return None;
+        }
+
+        let key_code = match (terminator, key_code) {
+            (b'u', Some(key_code)) => key_code,
+            (b'~', Some(key_code))
+                if BareKey::from_bytes_with_tilde(key_code.to_string().as_bytes()).is_some() =>
+            {
+                key_code
+            },
+            (
+                b'A' | b'B' | b'C' | b'D' | b'E' | b'F' | b'H' | b'P' | b'Q' | b'S',
+                None | Some(1),
+            ) => 1,
+            _ => return None,
+        };
+        Some((
+            KittyKeyEvent {
+                key_code,
+                shifted_key,
+                base_layout_key,
+                modifiers,
+                event_type,
+                text,
+                terminator,
+            },
+            terminator_index + 1,
+        ))
+    }
+    /// Parses a buffer holding exactly one key event
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        KittyKeyEvent::parse(bytes)
+            .and_then(|(key_event, length)| (length == bytes.len()).then_some(key_event))
+    }
+    pub fn from_key_with_modifier(key: &KeyWithModifier) -> Option<Self> {
+        let mut modifiers = 0;
+        for modifier in &key.key_modifiers {
+            modifiers |= match modifier {
+                KeyModifier::Shift => SHIFT,
+                KeyModifier::Alt => ALT,
+                KeyModifier::Ctrl => CONTROL,
+                KeyModifier::Super => SUPER,
+                KeyModifier::Hyper => HYPER,
+                KeyModifier::Meta => META,
+                KeyModifier::CapsLock => CAPS_LOCK,
+                KeyModifier::NumLock => NUM_LOCK,
+            };
+        }
+        let mut shifted_key = None;
+        let (key_code, terminator) = match key.bare_key {
+            BareKey::Up => (1, b'A'),
+            BareKey::Down => (1, b'B'),
+            BareKey::Right => (1, b'C'),
+            BareKey::Left => (1, b'D'),
+            BareKey::End => (1, b'F'),
+            BareKey::Home => (1, b'H'),
+            BareKey::F(1) => (1, b'P'),
+            BareKey::F(2) => (1, b'Q'),
+            BareKey::F(4) => (1, b'S'),
+            BareKey::Insert => (2, b'~'),
+            BareKey::Delete => (3, b'~'),
+            BareKey::PageUp => (5, b'~'),
+            BareKey::PageDown => (6, b'~'),
+            BareKey::F(3) => (13, b'~'),
+            BareKey::F(5) => (15, b'~'),
+            BareKey::F(index @ 6..=10) => (index as u32 + 11, b'~'),
+            BareKey::F(index @ 11..=12) => (index as u32 + 12, b'~'),
+            BareKey::F(index @ 13..=35) => (F13 + index as u32 - 13, b'u'),
+            BareKey::F(_) => return None,
+            BareKey::Enter => (13, b'u'),
+            BareKey::Tab => (9, b'u'),
+            BareKey::Backspace => (127, b'u'),
+            BareKey::Esc => (27, b'u'),
+            BareKey::CapsLock => (57358, b'u'),
+            BareKey::ScrollLock => (57359, b'u'),
+            BareKey::NumLock => (57360, b'u'),
+            BareKey::PrintScreen => (57361, b'u'),
+            BareKey::Pause => (57362, b'u'),
+            BareKey::Menu => (57363, b'u'),
+            BareKey::Char(character) => {
+                // the protocol reports the unshifted key along with the shift modifier
+                let mut lowercase = character.to_lowercase();
+                match (lowercase.next(), lowercase.next()) {
+                    (Some(lowercase), None) if lowercase != character => {
+                        modifiers |= SHIFT;
+                        shifted_key = Some(character as u32);
+                        (lowercase as u32, b'u')
+                    },
+                    _ => (character as u32, b'u'),
+                }
+            },
+        };
+        Some(KittyKeyEvent {
+            key_code,
+            shifted_key,
+            base_layout_key: None,
+            modifiers,
+            event_type: KeyEventType::Press,
+            text: None,
+            terminator,
+        })
+    }
+    pub fn is_release(&self) -> bool {
+        self.event_type == KeyEventType::Release
+    }
+    /// The key as Zellij represents it (eg. for matching keybindings), lock modifiers are left out
+    /// so that they do not get in the way
+    pub fn to_key_with_modifier(&self) -> Option<KeyWithModifier> {
+        let bare_key = match self.terminator {
+            b'u' => bare_key_from_key_code(self.key_code)?,
+            b'~' => BareKey::from_bytes_with_tilde(self.key_code.to_string().as_bytes())?,
+            terminator => BareKey::from_bytes_with_no_ending_byte(&[terminator])?,
+        };
+        let modifiers = (self.modifiers & !LOCK_MODIFIERS) + 1;
+        Some(KeyWithModifier::new_with_modifiers(
+            bare_key,
+            KeyModifier::from_bytes(modifiers.to_string().as_bytes()),
+        ))
+    }
+    /// Serializes this key event for an application that requested the given enhancements (no
+    /// enhancements meaning the legacy encoding), returns None if the application should not
+    /// receive it at all
+    pub fn serialize(&self, flags: KittyKeyboardFlags) -> Option<Vec<u8>> {
+        if self.is_release() && !flags.contains(KittyKeyboardFlags::REPORT_EVENT_TYPES) {
+            return None;
+        }
+        let report_all_keys_as_escape_codes =
+            flags.contains(KittyKeyboardFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES);
+        let modifiers = if report_all_keys_as_escape_codes {
+            self.modifiers
+        } else {
+            self.modifiers & !LOCK_MODIFIERS
+        };
+        if !report_all_keys_as_escape_codes {
+            // text is sent as is, as are the unmodified Enter, Tab and Backspace keys (so that
+            // eg. a shell stays usable if an application crashed without resetting the
+            // keyboard mode) - their release events are not reported
+            if modifiers & !SHIFT == 0 {
+                if let Some(text) = self.text() {
+                    return (!self.is_release()).then(|| text.into_bytes());
+                }
+            }
+            if modifiers == 0 && self.terminator == b'u' {
+                let legacy_bytes: Option<&[u8]> = match self.key_code {
+                    13 => Some(b"\r"),
+                    9 => Some(b"\t"),
+                    127 => Some(b"\x7f"),
+                    _ => None,
+                };
+                if let Some(legacy_bytes) = legacy_bytes {
+                    return (!self.is_release()).then(|| legacy_bytes.to_vec());
+                }
+            }
+        }
+        if flags.is_empty() {
+            self.serialize_legacy()
+        } else {
+            Some(self.serialize_escape_code(flags, modifiers))
+        }
+    }
+    fn text(&self) -> Option<String> {
+        if self.text.is_some() {
+            return self.text.clone();
+        }
+        if self.terminator != b'u'
+            || matches!(self.key_code, 9 | 13 | 27 | 127)
+            || FUNCTIONAL_KEY_CODES.contains(&self.key_code)
+        {
+            return None;
+        }
+        let character = char::from_u32(self.key_code).filter(|c| !c.is_control())?;
+        if self.modifiers & (SHIFT | CAPS_LOCK) == 0 {
+            return Some(character.to_string());
+        }
+        match self.shifted_key.and_then(char::from_u32) {
+            Some(shifted_character) if self.modifiers & SHIFT != 0 => {
+                Some(shifted_character.to_string())
+            },
+            _ => Some(character.to_uppercase().collect()),
+        }
+    }
+    fn serialize_legacy(&self) -> Option<Vec<u8>> {
+        if self.is_release() {
+            return None;
+        }
+        self.to_key_with_modifier()
+            .and_then(|key| key.serialize_non_kitty())
+            .map(|serialized| serialized.into_bytes())
+    }
+    fn serialize_escape_code(&self, flags: KittyKeyboardFlags, modifiers: u8) -> Vec<u8> {
+        let mut key_code = match self.terminator {
+            b'u' | b'~' => self.key_code.to_string(),
+            _ => String::new(),
+        };
+        if self.terminator == b'u'
+            && flags.contains(KittyKeyboardFlags::REPORT_ALTERNATE_KEYS)
+            && (self.shifted_key.is_some() || self.base_layout_key.is_some())
+        {
+            key_code.push(':');
+            if let Some(shifted_key) = self.shifted_key {
+                key_code.push_str(&shifted_key.to_string());
+            }
+            if let Some(base_layout_key) = self.base_layout_key {
+                key_code.push_str(&format!(":{}", base_layout_key));
+            }
+        }
+        let event_type = (flags.contains(KittyKeyboardFlags::REPORT_EVENT_TYPES)
+            && self.event_type != KeyEventType::Press)
+            .then(|| self.event_type.to_number());
+        let text = self
+            .text
+            .as_ref()
+            .filter(|_| {
+                flags.contains(KittyKeyboardFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES)
+                    && flags.contains(KittyKeyboardFlags::REPORT_ASSOCIATED_TEXT)
+                    && self.terminator == b'u'
+                    && !self.is_release()
+            })
+            .map(|text| {
+                text.chars()
+                    .map(|c| (c as u32).to_string())
+                    .collect::<Vec<_>>()
+                    .join(":")
+            });
+
+        let mut params = key_code;
+        if modifiers != 0 || event_type.is_some() || text.is_some() {
+            if params.is_empty() {
+                params.push('1');
+            }
+            params.push_str(&format!(";{}", modifiers as u32 + 1));
+            if let Some(event_type) = event_type {
+                params.push_str(&format!(":{}", event_type));
+            }
+        }
+        if let Some(text) = text {
+            params.push_str(&format!(";{}", text));
+        }
+        format!("\u{1b}[{}{}", params, self.terminator as char).into_bytes()
+    }
+}
+
+fn optional_number(field: Option<&str>) -> Option<Option<u32>> {
+    // None if the field is malformed, Some(None) if it is empty
+    match field {
+        None | Some("") => Some(None),
+        Some(field) => field.parse::<u32>().ok().map(Some),
+    }
+}
+
+fn bare_key_from_key_code(key_code: u32) -> Option<BareKey> {
+    match key_code {
+        0..=255 | 57358..=57363 => BareKey::from_bytes_with_u(key_code.to_string().as_bytes()),
+        F13..=F35 => Some(BareKey::F((key_code - F13 + 13) as u8)),
+        KP_0..=KP_9 => char::from_digit(key_code - KP_0, 10).map(BareKey::Char),
+        57409 => Some(BareKey::Char('.')),
+        57410 => Some(BareKey::Char('/')),
+        57411 => Some(BareKey::Char('*')),
+        57412 => Some(BareKey::Char('-')),
+        57413 => Some(BareKey::Char('+')),
+        57414 => Some(BareKey::Enter),
+        57415 => Some(BareKey::Char('=')),
+        57417 => Some(BareKey::Left),
+        57418 => Some(BareKey::Right),
+        57419 => Some(BareKey::Up),
+        57420 => Some(BareKey::Down),
+        57421 => Some(BareKey::PageUp),
+        57422 => Some(BareKey::PageDown),
+        57423 => Some(BareKey::Home),
+        57424 => Some(BareKey::End),
+        57425 => Some(BareKey::Insert),
+        57426 => Some(BareKey::Delete),
+        key_code if FUNCTIONAL_KEY_CODES.contains(&key_code) => None,
+        key_code => char::from_u32(key_code)
+            .filter(|c| !c.is_control())
+            .map(BareKey::Char),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/kitty_keyboard_test.rs"]
+mod kitty_keyboard_test;
@@ -252,6 +252,12 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                for (key, value) in &base_run_command.env {
+                    merged
+                        .env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -353,6 +359,25 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        // overrides the environment variables of a Run::Command with the same keys
+        if let Some(env) = env {
+            if let Run::Command(run_command) = self {
+                run_command.env.extend(env);
+            }
+        }
+    }
+    pub fn add_default_env(&mut self, env: &BTreeMap<String, String>) {
+        // only adds environment variables that the Run::Command does not already set
+        if let Run::Command(run_command) = self {
+            for (key, value) in env {
+                run_command
+                    .env
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
     pub fn is_same_category(first: &Option<Run>, second: &Option<Run>) -> bool {
         match (first, second) {
             (Some(Run::Plugin(..)), Some(Run::Plugin(..))) => true,
@@ -652,6 +677,117 @@ pub type SwapFloatingLayout = (
     Option<String>,
 ); // Option<String> is the swap layout name
 
+pub const DEFAULT_MASTER_RATIO: usize = 60;
+pub const DEFAULT_MASTER_COUNT: usize = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TilingAlgorithm {
+    MasterStack,
+    Spiral,
+    EqualColumns,
+}
+
+impl Display for TilingAlgorithm {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TilingAlgorithm::MasterStack => write!(f, "master_stack"),
+            TilingAlgorithm::Spiral => write!(f, "spiral"),
+            TilingAlgorithm::EqualColumns => write!(f, "equal_columns"),
+        }
+    }
+}
+
+impl FromStr for TilingAlgorithm {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "master_stack" => Ok(TilingAlgorithm::MasterStack),
+            "spiral" => Ok(TilingAlgorithm::Spiral),
+            "equal_columns" => Ok(TilingAlgorithm::EqualColumns),
+            _ => Err(format!(
+                "Unknown tiling algorithm: {}, expected one of: master_stack, spiral, equal_columns",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicTiling {
+    pub algorithm: TilingAlgorithm,
+    pub master_ratio: usize, // the percent of the tab's width taken by the master panes
+    pub master_count: usize,
+}
+
+impl DynamicTiling {
+    pub fn new(algorithm: TilingAlgorithm) -> Self {
+        DynamicTiling {
+            algorithm,
+            master_ratio: DEFAULT_MASTER_RATIO,
+            master_count: DEFAULT_MASTER_COUNT,
+        }
+    }
+    pub fn tiled_layout(&self, pane_count: usize) -> TiledPaneLayout {
+        // the panes of the returned layout are ordered such that the master panes are first
+        match self.algorithm {
+            TilingAlgorithm::MasterStack => {
+                let master_count = std::cmp::min(self.master_count, pane_count);
+                if master_count == 0 || master_count == pane_count {
+                    return TiledPaneLayout::with_equal_splits(
+                        SplitDirection::Horizontal,
+                        pane_count,
+                    );
+                }
+                let mut master_panes =
+                    TiledPaneLayout::with_equal_splits(SplitDirection::Horizontal, master_count);
+                master_panes.split_size = Some(SplitSize::Percent(self.master_ratio));
+                let stacked_panes = TiledPaneLayout::with_equal_splits(
+                    SplitDirection::Horizontal,
+                    pane_count - master_count,
+                );
+                TiledPaneLayout {
+                    children_split_direction: SplitDirection::Vertical,
+                    children: vec![master_panes, stacked_panes],
+                    ..Default::default()
+                }
+            },
+            TilingAlgorithm::Spiral => {
+                // each pane takes half of the space left by the panes before it, alternating
+                // between vertical and horizontal splits
+                let mut spiral = TiledPaneLayout::default();
+                for index in (0..pane_count.saturating_sub(1)).rev() {
+                    let children_split_direction = if index % 2 == 0 {
+                        SplitDirection::Vertical
+                    } else {
+                        SplitDirection::Horizontal
+                    };
+                    spiral = TiledPaneLayout {
+                        children_split_direction,
+                        children: vec![TiledPaneLayout::default(), spiral],
+                        ..Default::default()
+                    };
+                }
+                spiral
+            },
+            TilingAlgorithm::EqualColumns => {
+                TiledPaneLayout::with_equal_splits(SplitDirection::Vertical, pane_count)
+            },
+        }
+    }
+    pub fn change_master_count(&mut self, change_by: isize) {
+        self.master_count = self.master_count.saturating_add_signed(change_by);
+    }
+}
+
+/// What a layout is parsed with besides its own text
+#[derive(Debug, Clone, Default)]
+pub struct LayoutParseOptions {
+    pub cwd: Option<PathBuf>,
+    pub layout_vars: BTreeMap<String, String>, // values for the variables declared by the layout
+    pub layout_dir: Option<PathBuf>,           // where files included by the layout are looked for
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct Layout {
     pub tabs: Vec<(Option<String>, TiledPaneLayout, Vec<FloatingPaneLayout>)>,
@@ -677,6 +813,15 @@ impl From<Dimension> for PercentOrFixed {
     }
 }
 
+impl From<SplitSize> for PercentOrFixed {
+    fn from(split_size: SplitSize) -> Self {
+        match split_size {
+            SplitSize::Percent(percent) => PercentOrFixed::Percent(percent),
+            SplitSize::Fixed(fixed_size) => PercentOrFixed::Fixed(fixed_size),
+        }
+    }
+}
+
 impl PercentOrFixed {
     pub fn to_position(&self, whole: usize) -> usize {
         match self {
@@ -732,6 +877,9 @@ pub struct FloatingPaneLayout {
     pub focus: Option<bool>,
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
+    pub monitor_activity: Option<bool>,
+    pub monitor_silence: Option<u64>, // seconds
+    pub pinned: Option<bool>,
 }
 
 impl FloatingPaneLayout {
@@ -748,6 +896,11 @@ impl FloatingPaneLayout {
             run.add_start_suspended(start_suspended);
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        if let Some(run) = self.run.as_mut() {
+            run.add_default_env(env);
+        }
+    }
 }
 
 impl From<&TiledPaneLayout> for FloatingPaneLayout {
@@ -756,6 +909,8 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            monitor_activity: pane_layout.monitor_activity,
+            monitor_silence: pane_layout.monitor_silence,
             ..Default::default()
         }
     }
@@ -777,9 +932,22 @@ pub struct TiledPaneLayout {
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub monitor_activity: Option<bool>,
+    pub monitor_silence: Option<u64>,          // seconds
+    pub dynamic_tiling: Option<DynamicTiling>, // only relevant if this is the base layout
 }
 
 impl TiledPaneLayout {
+    pub fn with_equal_splits(children_split_direction: SplitDirection, pane_count: usize) -> Self {
+        if pane_count <= 1 {
+            return TiledPaneLayout::default();
+        }
+        TiledPaneLayout {
+            children_split_direction,
+            children: vec![TiledPaneLayout::default(); pane_count],
+            ..Default::default()
+        }
+    }
     pub fn insert_children_layout(
         &mut self,
         children_layout: &mut TiledPaneLayout,
@@ -945,6 +1113,14 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        if let Some(run) = self.run.as_mut() {
+            run.add_default_env(env);
+        }
+        for child in self.children.iter_mut() {
+            child.add_env_to_layout(env);
+        }
+    }
     pub fn populate_plugin_aliases_in_layout(&mut self, plugin_aliases: &PluginAliases) {
         match self.run.as_mut() {
             Some(run) => run.populate_run_plugin_if_needed(plugin_aliases),
@@ -1138,26 +1314,27 @@ impl Layout {
         layout_dir: &Option<PathBuf>,
         layout_info: LayoutInfo,
     ) -> Result<Layout, ConfigError> {
+        let layout_dir = layout_dir.clone().or_else(default_layout_dir);
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) = match layout_info {
-            LayoutInfo::File(layout_name_without_extension) => {
-                let layout_dir = layout_dir.clone().or_else(|| default_layout_dir());
-                Self::stringified_from_dir(
-                    &PathBuf::from(layout_name_without_extension),
-                    layout_dir.as_ref(),
-                )?
-            },
+            LayoutInfo::File(layout_name_without_extension) => Self::stringified_from_dir(
+                &PathBuf::from(layout_name_without_extension),
+                layout_dir.as_ref(),
+            )?,
             LayoutInfo::BuiltIn(layout_name) => {
                 Self::stringified_from_default_assets(&PathBuf::from(layout_name))?
             },
             LayoutInfo::Url(url) => (url.clone(), Self::stringified_from_url(&url)?, None),
         };
-        Layout::from_kdl(
+        Layout::from_kdl_with_options(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
-            None,
+            LayoutParseOptions {
+                layout_dir,
+                ..Default::default()
+            },
         )
     }
     pub fn stringified_from_path_or_default(
@@ -1202,22 +1379,31 @@ impl Layout {
         layout_path: Option<&PathBuf>,
         layout_dir: Option<PathBuf>,
         config: Config,
+        layout_vars: BTreeMap<String, String>,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_options(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
-            None,
+            LayoutParseOptions {
+                layout_vars,
+                layout_dir,
+                ..Default::default()
+            },
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(not(target_family = "wasm"))]
-    pub fn from_url(url: &str, config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        url: &str,
+        config: Config,
+        layout_vars: BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         let raw_layout = task::block_on(async move {
             let download = Downloader::download_without_cache(url).await;
             match download {
@@ -1225,12 +1411,24 @@ impl Layout {
                 Err(e) => Err(ConfigError::DownloadError(format!("{}", e))),
             }
         })?;
-        let layout = Layout::from_kdl(&raw_layout, url.into(), None, None)?;
+        let layout = Layout::from_kdl_with_options(
+            &raw_layout,
+            url.into(),
+            None,
+            LayoutParseOptions {
+                layout_vars,
+                ..Default::default()
+            },
+        )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(target_family = "wasm")]
-    pub fn from_url(url: &str, config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        url: &str,
+        config: Config,
+        layout_vars: BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         Err(ConfigError::DownloadError(format!(
             "Unsupported platform, cannot download layout from the web"
         )))
@@ -1240,14 +1438,17 @@ impl Layout {
         layout_dir: Option<PathBuf>,
     ) -> Result<Layout, ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_options(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
-            None,
+            LayoutParseOptions {
+                layout_dir,
+                ..Default::default()
+            },
         )?;
         Ok(layout)
     }
@@ -12,6 +12,10 @@ pub mod theme;
 #[cfg(not(target_family = "wasm"))]
 pub mod mouse;
 
+// Can't use this in wasm due to dependency on the `termwiz` crate.
+#[cfg(not(target_family = "wasm"))]
+pub mod kitty_keyboard;
+
 #[cfg(not(target_family = "wasm"))]
 pub use not_wasm::*;
 
@@ -85,6 +85,12 @@ pub struct Options {
     #[clap(long, value_parser)]
     pub scroll_buffer_size: Option<usize>,
 
+    /// Whether scrollback lines beyond the scroll buffer size are kept on disk rather than being
+    /// discarded, making the scrollback of panes unlimited (true or false), default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub scrollback_to_disk: Option<bool>,
+
     /// Switch to using a user supplied command for clipboard instead of OSC52
     #[clap(long, value_parser)]
     #[serde(default)]
@@ -214,6 +220,7 @@ impl Options {
         let theme = other.theme.or_else(|| self.theme.clone());
         let on_force_close = other.on_force_close.or(self.on_force_close);
         let scroll_buffer_size = other.scroll_buffer_size.or(self.scroll_buffer_size);
+        let scrollback_to_disk = other.scrollback_to_disk.or(self.scrollback_to_disk);
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
@@ -254,6 +261,7 @@ impl Options {
             mirror_session,
             on_force_close,
             scroll_buffer_size,
+            scrollback_to_disk,
             copy_command,
             copy_clipboard,
             copy_on_select,
@@ -305,6 +313,7 @@ impl Options {
         let theme = other.theme.or_else(|| self.theme.clone());
         let on_force_close = other.on_force_close.or(self.on_force_close);
         let scroll_buffer_size = other.scroll_buffer_size.or(self.scroll_buffer_size);
+        let scrollback_to_disk = other.scrollback_to_disk.or(self.scrollback_to_disk);
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
@@ -341,6 +350,7 @@ impl Options {
             mirror_session,
             on_force_close,
             scroll_buffer_size,
+            scrollback_to_disk,
             copy_command,
             copy_clipboard,
             copy_on_select,
@@ -407,6 +417,7 @@ impl From<CliOptions> for Options {
             mirror_session: opts.mirror_session,
             on_force_close: opts.on_force_close,
             scroll_buffer_size: opts.scroll_buffer_size,
+            scrollback_to_disk: opts.scrollback_to_disk,
             copy_command: opts.copy_command,
             copy_clipboard: opts.copy_clipboard,
             copy_on_select: opts.copy_on_select,
@@ -0,0 +1,1939 @@
+use super::super::kitty_keyboard::*;
+use crate::data::{BareKey, KeyWithModifier};
+
+fn key_event(bytes: &str) -> KittyKeyEvent {
+    KittyKeyEvent::from_bytes(bytes.as_bytes()).unwrap()
+}
+
+fn parse_key(bytes: &[u8]) -> Option<KeyWithModifier> {
+    KittyKeyEvent::from_bytes(bytes).and_then(|key_event| key_event.to_key_with_modifier())
+}
+
+fn serialize(bytes: &str, flags: u8) -> Option<String> {
+    key_event(bytes)
+        .serialize(KittyKeyboardFlags::from_bits_truncate(flags))
+        .map(|serialized| String::from_utf8(serialized).unwrap())
+}
+
+#[test]
+fn can_parse_all_the_fields_of_a_key_event() {
+    assert_eq!(
+        key_event("\u{1b}[97:65:113;2:3;65u"),
+        KittyKeyEvent {
+            key_code: 97,
+            shifted_key: Some(65),
+            base_layout_key: Some(113),
+            modifiers: 1,
+            event_type: KeyEventType::Release,
+            text: Some("A".to_owned()),
+            terminator: b'u',
+        }
+    );
+    assert_eq!(
+        key_event("\u{1b}[97::113u").base_layout_key,
+        Some(113),
+        "empty shifted key"
+    );
+    assert_eq!(
+        key_event("\u{1b}[1;5:2A"),
+        KittyKeyEvent {
+            key_code: 1,
+            shifted_key: None,
+            base_layout_key: None,
+            modifiers: 4,
+            event_type: KeyEventType::Repeat,
+            text: None,
+            terminator: b'A',
+        }
+    );
+}
+
+#[test]
+fn can_parse_consecutive_key_events() {
+    let bytes = "\u{1b}[97u\u{1b}[97;1:3uabc";
+    let (first, first_length) = KittyKeyEvent::parse(bytes.as_bytes()).unwrap();
+    let (second, second_length) = KittyKeyEvent::parse(&bytes.as_bytes()[first_length..]).unwrap();
+    assert_eq!(first.event_type, KeyEventType::Press);
+    assert_eq!(second.event_type, KeyEventType::Release);
+    assert_eq!(&bytes[first_length + second_length..], "abc");
+    assert!(KittyKeyEvent::from_bytes(bytes.as_bytes()).is_none());
+}
+
+#[test]
+fn does_not_parse_other_sequences_as_key_events() {
+    for bytes in [
+        "\u{1b}[200~",         // bracketed paste
+        "\u{1b}[<0;10;5M",     // mouse event
+        "\u{1b}[?1u",          // keyboard protocol query response
+        "\u{1b}[12;40R",       // cursor position report
+        "\u{1b}[5;1A",         // letter terminators are only used with the number 1
+        "\u{1b}[97;1:4u",      // no such event type
+        "\u{1b}[97;0u",        // modifiers start from 1
+        "\u{1b}]11;rgb:0/0/0", // OSC
+        "a",
+    ] {
+        assert!(
+            KittyKeyEvent::parse(bytes.as_bytes()).is_none(),
+            "{:?} is not a key event",
+            bytes
+        );
+    }
+}
+
+#[test]
+fn converts_key_events_to_zellij_keys() {
+    assert_eq!(
+        key_event("\u{1b}[112;5u").to_key_with_modifier(),
+        Some(KeyWithModifier::new(BareKey::Char('p')).with_ctrl_modifier())
+    );
+    assert_eq!(
+        key_event("\u{1b}[112;197u").to_key_with_modifier(),
+        Some(KeyWithModifier::new(BareKey::Char('p')).with_ctrl_modifier()),
+        "lock modifiers are left out"
+    );
+    assert_eq!(
+        key_event("\u{1b}[1;3:3D").to_key_with_modifier(),
+        Some(KeyWithModifier::new(BareKey::Left).with_alt_modifier())
+    );
+    assert_eq!(
+        key_event("\u{1b}[57414u").to_key_with_modifier(),
+        Some(KeyWithModifier::new(BareKey::Enter)),
+        "keypad enter"
+    );
+    assert_eq!(
+        key_event("\u{1b}[8364u").to_key_with_modifier(),
+        Some(KeyWithModifier::new(BareKey::Char('€')))
+    );
+    assert_eq!(
+        key_event("\u{1b}[57441;2u").to_key_with_modifier(),
+        None,
+        "left shift on its own"
+    );
+}
+
+#[test]
+fn serializes_key_events_for_the_legacy_encoding() {
+    assert_eq!(serialize("\u{1b}[97u", 0), Some("a".to_owned()));
+    assert_eq!(serialize("\u{1b}[97:65;2u", 0), Some("A".to_owned()));
+    assert_eq!(serialize("\u{1b}[97;2;65u", 0), Some("A".to_owned()));
+    assert_eq!(serialize("\u{1b}[97;5u", 0), Some("\u{1}".to_owned()));
+    assert_eq!(serialize("\u{1b}[97;3u", 0), Some("\u{1b}a".to_owned()));
+    assert_eq!(serialize("\u{1b}[13u", 0), Some("\r".to_owned()));
+    assert_eq!(serialize("\u{1b}[27u", 0), Some("\u{1b}".to_owned()));
+    assert_eq!(serialize("\u{1b}[1;5C", 0), Some("\u{1b}[1;5C".to_owned()));
+    assert_eq!(
+        serialize("\u{1b}[97;1:2u", 0),
+        Some("a".to_owned()),
+        "repeat"
+    );
+    assert_eq!(serialize("\u{1b}[97;1:3u", 0), None, "release");
+    assert_eq!(serialize("\u{1b}[57441;2u", 0), None, "left shift");
+}
+
+#[test]
+fn serializes_key_events_for_disambiguated_escape_codes() {
+    assert_eq!(serialize("\u{1b}[97;129u", 1), Some("a".to_owned()));
+    assert_eq!(
+        serialize("\u{1b}[97;5u", 1),
+        Some("\u{1b}[97;5u".to_owned())
+    );
+    assert_eq!(serialize("\u{1b}[27u", 1), Some("\u{1b}[27u".to_owned()));
+    assert_eq!(serialize("\u{1b}[13u", 1), Some("\r".to_owned()));
+    assert_eq!(
+        serialize("\u{1b}[13;2u", 1),
+        Some("\u{1b}[13;2u".to_owned())
+    );
+    assert_eq!(serialize("\u{1b}[1;1:2A", 1), Some("\u{1b}[A".to_owned()));
+    assert_eq!(
+        serialize("\u{1b}[97:65;6u", 1),
+        Some("\u{1b}[97;6u".to_owned())
+    );
+    assert_eq!(serialize("\u{1b}[97;5:3u", 1), None);
+}
+
+#[test]
+fn serializes_key_events_with_event_types() {
+    assert_eq!(
+        serialize("\u{1b}[97;5:3u", 3),
+        Some("\u{1b}[97;5:3u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[1;1:2A", 3),
+        Some("\u{1b}[1;1:2A".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[3;1:3~", 3),
+        Some("\u{1b}[3;1:3~".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[97;1:3u", 3),
+        None,
+        "text keys are not released"
+    );
+    assert_eq!(
+        serialize("\u{1b}[13;1:3u", 3),
+        None,
+        "nor are Enter, Tab and Backspace"
+    );
+}
+
+#[test]
+fn serializes_key_events_with_alternate_keys() {
+    assert_eq!(
+        serialize("\u{1b}[97:65:113;6u", 5),
+        Some("\u{1b}[97:65:113;6u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[97::113;5u", 5),
+        Some("\u{1b}[97::113;5u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[97:65:113;6u", 1),
+        Some("\u{1b}[97;6u".to_owned())
+    );
+}
+
+#[test]
+fn serializes_all_keys_as_escape_codes_with_associated_text() {
+    assert_eq!(
+        serialize("\u{1b}[97;;97u", 9),
+        Some("\u{1b}[97u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[97;129;97u", 9),
+        Some("\u{1b}[97;129u".to_owned())
+    );
+    assert_eq!(serialize("\u{1b}[13u", 9), Some("\u{1b}[13u".to_owned()));
+    assert_eq!(
+        serialize("\u{1b}[97:65;2;65u", 31),
+        Some("\u{1b}[97:65;2;65u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[97:65;2:3;65u", 31),
+        Some("\u{1b}[97:65;2:3u".to_owned()),
+        "releases carry no text"
+    );
+    assert_eq!(
+        serialize("\u{1b}[97;;97u", 25),
+        Some("\u{1b}[97;1;97u".to_owned())
+    );
+    assert_eq!(
+        serialize("\u{1b}[57441;2u", 8),
+        Some("\u{1b}[57441;2u".to_owned())
+    );
+}
+
+#[test]
+fn converts_zellij_keys_to_key_events() {
+    let serialize_key = |key: KeyWithModifier, flags: u8| {
+        KittyKeyEvent::from_key_with_modifier(&key)
+            .and_then(|k| k.serialize(KittyKeyboardFlags::from_bits_truncate(flags)))
+            .map(|serialized| String::from_utf8(serialized).unwrap())
+    };
+    assert_eq!(
+        serialize_key(KeyWithModifier::new(BareKey::Char('A')), 1),
+        Some("A".to_owned())
+    );
+    assert_eq!(
+        serialize_key(KeyWithModifier::new(BareKey::Char('A')), 8),
+        Some("\u{1b}[97;2u".to_owned())
+    );
+    assert_eq!(
+        serialize_key(KeyWithModifier::new(BareKey::F(5)).with_ctrl_modifier(), 1),
+        Some("\u{1b}[15;5~".to_owned())
+    );
+    assert_eq!(
+        serialize_key(KeyWithModifier::new(BareKey::Esc), 1),
+        Some("\u{1b}[27u".to_owned())
+    );
+}
+
+#[test]
+fn can_parse_bare_keys() {
+    let key = "\u{1b}[97u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a'))),
+        "Can parse a bare 'a' keypress"
+    );
+    let key = "\u{1b}[49u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('1'))),
+        "Can parse a bare '1' keypress"
+    );
+    let key = "\u{1b}[27u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Esc)),
+        "Can parse a bare 'ESC' keypress"
+    );
+    let key = "\u{1b}[13u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Enter)),
+        "Can parse a bare 'ENTER' keypress"
+    );
+    let key = "\u{1b}[9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Tab)),
+        "Can parse a bare 'Tab' keypress"
+    );
+    let key = "\u{1b}[127u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Backspace)),
+        "Can parse a bare 'Backspace' keypress"
+    );
+    let key = "\u{1b}[57358u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::CapsLock)),
+        "Can parse a bare 'CapsLock' keypress"
+    );
+    let key = "\u{1b}[57359u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::ScrollLock)),
+        "Can parse a bare 'ScrollLock' keypress"
+    );
+    let key = "\u{1b}[57360u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::NumLock)),
+        "Can parse a bare 'NumLock' keypress"
+    );
+    let key = "\u{1b}[57361u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PrintScreen)),
+        "Can parse a bare 'PrintScreen' keypress"
+    );
+    let key = "\u{1b}[57362u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Pause)),
+        "Can parse a bare 'Pause' keypress"
+    );
+    let key = "\u{1b}[57363u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Menu)),
+        "Can parse a bare 'Menu' keypress"
+    );
+
+    let key = "\u{1b}[2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Insert)),
+        "Can parse a bare 'Insert' keypress"
+    );
+    let key = "\u{1b}[3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Delete)),
+        "Can parse a bare 'Delete' keypress"
+    );
+    let key = "\u{1b}[5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageUp)),
+        "Can parse a bare 'PageUp' keypress"
+    );
+    let key = "\u{1b}[6~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageDown)),
+        "Can parse a bare 'PageDown' keypress"
+    );
+    let key = "\u{1b}[7~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home)),
+        "Can parse a bare 'Home' keypress"
+    );
+    let key = "\u{1b}[8~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End)),
+        "Can parse a bare 'End' keypress"
+    );
+    let key = "\u{1b}[11~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1))),
+        "Can parse a bare 'F1' keypress"
+    );
+    let key = "\u{1b}[12~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2))),
+        "Can parse a bare 'F2' keypress"
+    );
+    let key = "\u{1b}[13~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(3))),
+        "Can parse a bare 'F3' keypress"
+    );
+    let key = "\u{1b}[14~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4))),
+        "Can parse a bare 'F4' keypress"
+    );
+    let key = "\u{1b}[15~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(5))),
+        "Can parse a bare 'F5' keypress"
+    );
+    let key = "\u{1b}[17~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(6))),
+        "Can parse a bare 'F6' keypress"
+    );
+    let key = "\u{1b}[18~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(7))),
+        "Can parse a bare 'F7' keypress"
+    );
+    let key = "\u{1b}[19~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(8))),
+        "Can parse a bare 'F8' keypress"
+    );
+    let key = "\u{1b}[20~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(9))),
+        "Can parse a bare 'F9' keypress"
+    );
+    let key = "\u{1b}[21~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(10))),
+        "Can parse a bare 'F10' keypress"
+    );
+    let key = "\u{1b}[23~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(11))),
+        "Can parse a bare 'F11' keypress"
+    );
+    let key = "\u{1b}[24~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(12))),
+        "Can parse a bare 'F12' keypress"
+    );
+    let key = "\u{1b}[D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Left)),
+        "Can parse a bare 'Left' keypress"
+    );
+    let key = "\u{1b}[C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Right)),
+        "Can parse a bare 'Right' keypress"
+    );
+    let key = "\u{1b}[A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up)),
+        "Can parse a bare 'Up' keypress"
+    );
+    let key = "\u{1b}[B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Down)),
+        "Can parse a bare 'Down' keypress"
+    );
+    let key = "\u{1b}[H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home)),
+        "Can parse a bare 'Home' keypress"
+    );
+    let key = "\u{1b}[F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End)),
+        "Can parse a bare 'End' keypress"
+    );
+    let key = "\u{1b}[P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1))),
+        "Can parse a bare 'F1 (alternate)' keypress"
+    );
+    let key = "\u{1b}[Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2))),
+        "Can parse a bare 'F2 (alternate)' keypress"
+    );
+    let key = "\u{1b}[S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4))),
+        "Can parse a bare 'F4 (alternate)' keypress"
+    );
+}
+
+#[test]
+fn can_parse_keys_with_shift_modifier() {
+    let key = "\u{1b}[97;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a')).with_shift_modifier()),
+        "Can parse a bare 'a' keypress with shift"
+    );
+    let key = "\u{1b}[49;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('1')).with_shift_modifier()),
+        "Can parse a bare '1' keypress with shift"
+    );
+    let key = "\u{1b}[27;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Esc).with_shift_modifier()),
+        "Can parse a bare 'ESC' keypress with shift"
+    );
+    let key = "\u{1b}[13;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Enter).with_shift_modifier()),
+        "Can parse a bare 'ENTER' keypress with shift"
+    );
+    let key = "\u{1b}[9;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Tab).with_shift_modifier()),
+        "Can parse a bare 'Tab' keypress with shift"
+    );
+    let key = "\u{1b}[127;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Backspace).with_shift_modifier()),
+        "Can parse a bare 'Backspace' keypress with shift"
+    );
+    let key = "\u{1b}[57358;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::CapsLock).with_shift_modifier()),
+        "Can parse a bare 'CapsLock' keypress with shift"
+    );
+    let key = "\u{1b}[57359;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::ScrollLock).with_shift_modifier()),
+        "Can parse a bare 'ScrollLock' keypress with shift"
+    );
+    let key = "\u{1b}[57360;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::NumLock).with_shift_modifier()),
+        "Can parse a bare 'NumLock' keypress with shift"
+    );
+    let key = "\u{1b}[57361;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PrintScreen).with_shift_modifier()),
+        "Can parse a bare 'PrintScreen' keypress with shift"
+    );
+    let key = "\u{1b}[57362;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Pause).with_shift_modifier()),
+        "Can parse a bare 'Pause' keypress with shift"
+    );
+    let key = "\u{1b}[57363;2u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Menu).with_shift_modifier()),
+        "Can parse a bare 'Menu' keypress with shift"
+    );
+
+    let key = "\u{1b}[2;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Insert).with_shift_modifier()),
+        "Can parse a bare 'Insert' keypress with shift"
+    );
+    let key = "\u{1b}[3;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Delete).with_shift_modifier()),
+        "Can parse a bare 'Delete' keypress with shift"
+    );
+    let key = "\u{1b}[5;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageUp).with_shift_modifier()),
+        "Can parse a bare 'PageUp' keypress with shift"
+    );
+    let key = "\u{1b}[6;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageDown).with_shift_modifier()),
+        "Can parse a bare 'PageDown' keypress with shift"
+    );
+    let key = "\u{1b}[7;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_shift_modifier()),
+        "Can parse a bare 'Home' keypress with shift"
+    );
+    let key = "\u{1b}[8;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_shift_modifier()),
+        "Can parse a bare 'End' keypress with shift"
+    );
+    let key = "\u{1b}[11;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_shift_modifier()),
+        "Can parse a bare 'F1' keypress with shift"
+    );
+    let key = "\u{1b}[12;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_shift_modifier()),
+        "Can parse a bare 'F2' keypress with shift"
+    );
+    let key = "\u{1b}[13;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(3)).with_shift_modifier()),
+        "Can parse a bare 'F3' keypress with shift"
+    );
+    let key = "\u{1b}[14;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_shift_modifier()),
+        "Can parse a bare 'F4' keypress with shift"
+    );
+    let key = "\u{1b}[15;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(5)).with_shift_modifier()),
+        "Can parse a bare 'F5' keypress with shift"
+    );
+    let key = "\u{1b}[17;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(6)).with_shift_modifier()),
+        "Can parse a bare 'F6' keypress with shift"
+    );
+    let key = "\u{1b}[18;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(7)).with_shift_modifier()),
+        "Can parse a bare 'F7' keypress with shift"
+    );
+    let key = "\u{1b}[19;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(8)).with_shift_modifier()),
+        "Can parse a bare 'F8' keypress with shift"
+    );
+    let key = "\u{1b}[20;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(9)).with_shift_modifier()),
+        "Can parse a bare 'F9' keypress with shift"
+    );
+    let key = "\u{1b}[21;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(10)).with_shift_modifier()),
+        "Can parse a bare 'F10' keypress with shift"
+    );
+    let key = "\u{1b}[23;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(11)).with_shift_modifier()),
+        "Can parse a bare 'F11' keypress with shift"
+    );
+    let key = "\u{1b}[24;2~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(12)).with_shift_modifier()),
+        "Can parse a bare 'F12' keypress with shift"
+    );
+    let key = "\u{1b}[1;2D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Left).with_shift_modifier()),
+        "Can parse a bare 'Left' keypress with shift"
+    );
+    let key = "\u{1b}[1;2C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Right).with_shift_modifier()),
+        "Can parse a bare 'Right' keypress with shift"
+    );
+    let key = "\u{1b}[1;2A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up).with_shift_modifier()),
+        "Can parse a bare 'Up' keypress with shift"
+    );
+    let key = "\u{1b}[1;2B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Down).with_shift_modifier()),
+        "Can parse a bare 'Down' keypress with shift"
+    );
+    let key = "\u{1b}[1;2H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_shift_modifier()),
+        "Can parse a bare 'Home' keypress with shift"
+    );
+    let key = "\u{1b}[1;2F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_shift_modifier()),
+        "Can parse a bare 'End' keypress with shift"
+    );
+    let key = "\u{1b}[1;2P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_shift_modifier()),
+        "Can parse a bare 'F1 (alternate)' keypress with shift"
+    );
+    let key = "\u{1b}[1;2Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_shift_modifier()),
+        "Can parse a bare 'F2 (alternate)' keypress with shift"
+    );
+    let key = "\u{1b}[1;2S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_shift_modifier()),
+        "Can parse a bare 'F4 (alternate)' keypress with shift"
+    );
+}
+
+#[test]
+fn can_parse_keys_with_alt_modifier() {
+    let key = "\u{1b}[97;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a')).with_alt_modifier()),
+        "Can parse a bare 'a' keypress with alt"
+    );
+    let key = "\u{1b}[49;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('1')).with_alt_modifier()),
+        "Can parse a bare '1' keypress with alt"
+    );
+    let key = "\u{1b}[27;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Esc).with_alt_modifier()),
+        "Can parse a bare 'ESC' keypress with alt"
+    );
+    let key = "\u{1b}[13;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Enter).with_alt_modifier()),
+        "Can parse a bare 'ENTER' keypress with alt"
+    );
+    let key = "\u{1b}[9;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Tab).with_alt_modifier()),
+        "Can parse a bare 'Tab' keypress with alt"
+    );
+    let key = "\u{1b}[127;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Backspace).with_alt_modifier()),
+        "Can parse a bare 'Backspace' keypress with alt"
+    );
+    let key = "\u{1b}[57358;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::CapsLock).with_alt_modifier()),
+        "Can parse a bare 'CapsLock' keypress with alt"
+    );
+    let key = "\u{1b}[57359;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::ScrollLock).with_alt_modifier()),
+        "Can parse a bare 'ScrollLock' keypress with alt"
+    );
+    let key = "\u{1b}[57360;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::NumLock).with_alt_modifier()),
+        "Can parse a bare 'NumLock' keypress with alt"
+    );
+    let key = "\u{1b}[57361;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PrintScreen).with_alt_modifier()),
+        "Can parse a bare 'PrintScreen' keypress with alt"
+    );
+    let key = "\u{1b}[57362;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Pause).with_alt_modifier()),
+        "Can parse a bare 'Pause' keypress with alt"
+    );
+    let key = "\u{1b}[57363;3u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Menu).with_alt_modifier()),
+        "Can parse a bare 'Menu' keypress with alt"
+    );
+
+    let key = "\u{1b}[2;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Insert).with_alt_modifier()),
+        "Can parse a bare 'Insert' keypress with alt"
+    );
+    let key = "\u{1b}[3;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Delete).with_alt_modifier()),
+        "Can parse a bare 'Delete' keypress with alt"
+    );
+    let key = "\u{1b}[5;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageUp).with_alt_modifier()),
+        "Can parse a bare 'PageUp' keypress with alt"
+    );
+    let key = "\u{1b}[6;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageDown).with_alt_modifier()),
+        "Can parse a bare 'PageDown' keypress with alt"
+    );
+    let key = "\u{1b}[7;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_alt_modifier()),
+        "Can parse a bare 'Home' keypress with alt"
+    );
+    let key = "\u{1b}[8;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_alt_modifier()),
+        "Can parse a bare 'End' keypress with alt"
+    );
+    let key = "\u{1b}[11;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_alt_modifier()),
+        "Can parse a bare 'F1' keypress with alt"
+    );
+    let key = "\u{1b}[12;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_alt_modifier()),
+        "Can parse a bare 'F2' keypress with alt"
+    );
+    let key = "\u{1b}[13;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(3)).with_alt_modifier()),
+        "Can parse a bare 'F3' keypress with alt"
+    );
+    let key = "\u{1b}[14;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_alt_modifier()),
+        "Can parse a bare 'F4' keypress with alt"
+    );
+    let key = "\u{1b}[15;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(5)).with_alt_modifier()),
+        "Can parse a bare 'F5' keypress with alt"
+    );
+    let key = "\u{1b}[17;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(6)).with_alt_modifier()),
+        "Can parse a bare 'F6' keypress with alt"
+    );
+    let key = "\u{1b}[18;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(7)).with_alt_modifier()),
+        "Can parse a bare 'F7' keypress with alt"
+    );
+    let key = "\u{1b}[19;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(8)).with_alt_modifier()),
+        "Can parse a bare 'F8' keypress with alt"
+    );
+    let key = "\u{1b}[20;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(9)).with_alt_modifier()),
+        "Can parse a bare 'F9' keypress with alt"
+    );
+    let key = "\u{1b}[21;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(10)).with_alt_modifier()),
+        "Can parse a bare 'F10' keypress with alt"
+    );
+    let key = "\u{1b}[23;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(11)).with_alt_modifier()),
+        "Can parse a bare 'F11' keypress with alt"
+    );
+    let key = "\u{1b}[24;3~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(12)).with_alt_modifier()),
+        "Can parse a bare 'F12' keypress with alt"
+    );
+    let key = "\u{1b}[1;3D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Left).with_alt_modifier()),
+        "Can parse a bare 'Left' keypress with alt"
+    );
+    let key = "\u{1b}[1;3C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Right).with_alt_modifier()),
+        "Can parse a bare 'Right' keypress with alt"
+    );
+    let key = "\u{1b}[1;3A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up).with_alt_modifier()),
+        "Can parse a bare 'Up' keypress with alt"
+    );
+    let key = "\u{1b}[1;3B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Down).with_alt_modifier()),
+        "Can parse a bare 'Down' keypress with alt"
+    );
+    let key = "\u{1b}[1;3H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_alt_modifier()),
+        "Can parse a bare 'Home' keypress with alt"
+    );
+    let key = "\u{1b}[1;3F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_alt_modifier()),
+        "Can parse a bare 'End' keypress with alt"
+    );
+    let key = "\u{1b}[1;3P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_alt_modifier()),
+        "Can parse a bare 'F1 (alternate)' keypress with alt"
+    );
+    let key = "\u{1b}[1;3Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_alt_modifier()),
+        "Can parse a bare 'F2 (alternate)' keypress with alt"
+    );
+    let key = "\u{1b}[1;3S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_alt_modifier()),
+        "Can parse a bare 'F4 (alternate)' keypress with alt"
+    );
+}
+
+#[test]
+fn can_parse_keys_with_ctrl_modifier() {
+    let key = "\u{1b}[97;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a')).with_ctrl_modifier()),
+        "Can parse a bare 'a' keypress with ctrl"
+    );
+    let key = "\u{1b}[49;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('1')).with_ctrl_modifier()),
+        "Can parse a bare '1' keypress with ctrl"
+    );
+    let key = "\u{1b}[27;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Esc).with_ctrl_modifier()),
+        "Can parse a bare 'ESC' keypress with ctrl"
+    );
+    let key = "\u{1b}[13;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Enter).with_ctrl_modifier()),
+        "Can parse a bare 'ENTER' keypress with ctrl"
+    );
+    let key = "\u{1b}[9;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Tab).with_ctrl_modifier()),
+        "Can parse a bare 'Tab' keypress with ctrl"
+    );
+    let key = "\u{1b}[127;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Backspace).with_ctrl_modifier()),
+        "Can parse a bare 'Backspace' keypress with ctrl"
+    );
+    let key = "\u{1b}[57358;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::CapsLock).with_ctrl_modifier()),
+        "Can parse a bare 'CapsLock' keypress with ctrl"
+    );
+    let key = "\u{1b}[57359;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::ScrollLock).with_ctrl_modifier()),
+        "Can parse a bare 'ScrollLock' keypress with ctrl"
+    );
+    let key = "\u{1b}[57360;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::NumLock).with_ctrl_modifier()),
+        "Can parse a bare 'NumLock' keypress with ctrl"
+    );
+    let key = "\u{1b}[57361;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PrintScreen).with_ctrl_modifier()),
+        "Can parse a bare 'PrintScreen' keypress with ctrl"
+    );
+    let key = "\u{1b}[57362;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Pause).with_ctrl_modifier()),
+        "Can parse a bare 'Pause' keypress with ctrl"
+    );
+    let key = "\u{1b}[57363;5u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Menu).with_ctrl_modifier()),
+        "Can parse a bare 'Menu' keypress with ctrl"
+    );
+
+    let key = "\u{1b}[2;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Insert).with_ctrl_modifier()),
+        "Can parse a bare 'Insert' keypress with ctrl"
+    );
+    let key = "\u{1b}[3;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Delete).with_ctrl_modifier()),
+        "Can parse a bare 'Delete' keypress with ctrl"
+    );
+    let key = "\u{1b}[5;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageUp).with_ctrl_modifier()),
+        "Can parse a bare 'PageUp' keypress with ctrl"
+    );
+    let key = "\u{1b}[6;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageDown).with_ctrl_modifier()),
+        "Can parse a bare 'PageDown' keypress with ctrl"
+    );
+    let key = "\u{1b}[7;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_ctrl_modifier()),
+        "Can parse a bare 'Home' keypress with ctrl"
+    );
+    let key = "\u{1b}[8;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_ctrl_modifier()),
+        "Can parse a bare 'End' keypress with ctrl"
+    );
+    let key = "\u{1b}[11;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_ctrl_modifier()),
+        "Can parse a bare 'F1' keypress with ctrl"
+    );
+    let key = "\u{1b}[12;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_ctrl_modifier()),
+        "Can parse a bare 'F2' keypress with ctrl"
+    );
+    let key = "\u{1b}[13;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(3)).with_ctrl_modifier()),
+        "Can parse a bare 'F3' keypress with ctrl"
+    );
+    let key = "\u{1b}[14;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_ctrl_modifier()),
+        "Can parse a bare 'F4' keypress with ctrl"
+    );
+    let key = "\u{1b}[15;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(5)).with_ctrl_modifier()),
+        "Can parse a bare 'F5' keypress with ctrl"
+    );
+    let key = "\u{1b}[17;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(6)).with_ctrl_modifier()),
+        "Can parse a bare 'F6' keypress with ctrl"
+    );
+    let key = "\u{1b}[18;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(7)).with_ctrl_modifier()),
+        "Can parse a bare 'F7' keypress with ctrl"
+    );
+    let key = "\u{1b}[19;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(8)).with_ctrl_modifier()),
+        "Can parse a bare 'F8' keypress with ctrl"
+    );
+    let key = "\u{1b}[20;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(9)).with_ctrl_modifier()),
+        "Can parse a bare 'F9' keypress with ctrl"
+    );
+    let key = "\u{1b}[21;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(10)).with_ctrl_modifier()),
+        "Can parse a bare 'F10' keypress with ctrl"
+    );
+    let key = "\u{1b}[23;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(11)).with_ctrl_modifier()),
+        "Can parse a bare 'F11' keypress with ctrl"
+    );
+    let key = "\u{1b}[24;5~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(12)).with_ctrl_modifier()),
+        "Can parse a bare 'F12' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Left).with_ctrl_modifier()),
+        "Can parse a bare 'Left' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Right).with_ctrl_modifier()),
+        "Can parse a bare 'Right' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up).with_ctrl_modifier()),
+        "Can parse a bare 'Up' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Down).with_ctrl_modifier()),
+        "Can parse a bare 'Down' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_ctrl_modifier()),
+        "Can parse a bare 'Home' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_ctrl_modifier()),
+        "Can parse a bare 'End' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_ctrl_modifier()),
+        "Can parse a bare 'F1 (ctrlernate)' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_ctrl_modifier()),
+        "Can parse a bare 'F2 (ctrlernate)' keypress with ctrl"
+    );
+    let key = "\u{1b}[1;5S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_ctrl_modifier()),
+        "Can parse a bare 'F4 (ctrlernate)' keypress with ctrl"
+    );
+}
+
+#[test]
+fn can_parse_keys_with_super_modifier() {
+    let key = "\u{1b}[97;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a')).with_super_modifier()),
+        "Can parse a bare 'a' keypress with super"
+    );
+    let key = "\u{1b}[49;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('1')).with_super_modifier()),
+        "Can parse a bare '1' keypress with super"
+    );
+    let key = "\u{1b}[27;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Esc).with_super_modifier()),
+        "Can parse a bare 'ESC' keypress with super"
+    );
+    let key = "\u{1b}[13;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Enter).with_super_modifier()),
+        "Can parse a bare 'ENTER' keypress with super"
+    );
+    let key = "\u{1b}[9;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Tab).with_super_modifier()),
+        "Can parse a bare 'Tab' keypress with super"
+    );
+    let key = "\u{1b}[127;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Backspace).with_super_modifier()),
+        "Can parse a bare 'Backspace' keypress with super"
+    );
+    let key = "\u{1b}[57358;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::CapsLock).with_super_modifier()),
+        "Can parse a bare 'CapsLock' keypress with super"
+    );
+    let key = "\u{1b}[57359;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::ScrollLock).with_super_modifier()),
+        "Can parse a bare 'ScrollLock' keypress with super"
+    );
+    let key = "\u{1b}[57360;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::NumLock).with_super_modifier()),
+        "Can parse a bare 'NumLock' keypress with super"
+    );
+    let key = "\u{1b}[57361;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PrintScreen).with_super_modifier()),
+        "Can parse a bare 'PrintScreen' keypress with super"
+    );
+    let key = "\u{1b}[57362;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Pause).with_super_modifier()),
+        "Can parse a bare 'Pause' keypress with super"
+    );
+    let key = "\u{1b}[57363;9u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Menu).with_super_modifier()),
+        "Can parse a bare 'Menu' keypress with super"
+    );
+
+    let key = "\u{1b}[2;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Insert).with_super_modifier()),
+        "Can parse a bare 'Insert' keypress with super"
+    );
+    let key = "\u{1b}[3;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Delete).with_super_modifier()),
+        "Can parse a bare 'Delete' keypress with super"
+    );
+    let key = "\u{1b}[5;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageUp).with_super_modifier()),
+        "Can parse a bare 'PageUp' keypress with super"
+    );
+    let key = "\u{1b}[6;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::PageDown).with_super_modifier()),
+        "Can parse a bare 'PageDown' keypress with super"
+    );
+    let key = "\u{1b}[7;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_super_modifier()),
+        "Can parse a bare 'Home' keypress with super"
+    );
+    let key = "\u{1b}[8;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_super_modifier()),
+        "Can parse a bare 'End' keypress with super"
+    );
+    let key = "\u{1b}[11;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_super_modifier()),
+        "Can parse a bare 'F1' keypress with super"
+    );
+    let key = "\u{1b}[12;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_super_modifier()),
+        "Can parse a bare 'F2' keypress with super"
+    );
+    let key = "\u{1b}[13;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(3)).with_super_modifier()),
+        "Can parse a bare 'F3' keypress with super"
+    );
+    let key = "\u{1b}[14;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_super_modifier()),
+        "Can parse a bare 'F4' keypress with super"
+    );
+    let key = "\u{1b}[15;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(5)).with_super_modifier()),
+        "Can parse a bare 'F5' keypress with super"
+    );
+    let key = "\u{1b}[17;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(6)).with_super_modifier()),
+        "Can parse a bare 'F6' keypress with super"
+    );
+    let key = "\u{1b}[18;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(7)).with_super_modifier()),
+        "Can parse a bare 'F7' keypress with super"
+    );
+    let key = "\u{1b}[19;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(8)).with_super_modifier()),
+        "Can parse a bare 'F8' keypress with super"
+    );
+    let key = "\u{1b}[20;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(9)).with_super_modifier()),
+        "Can parse a bare 'F9' keypress with super"
+    );
+    let key = "\u{1b}[21;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(10)).with_super_modifier()),
+        "Can parse a bare 'F10' keypress with super"
+    );
+    let key = "\u{1b}[23;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(11)).with_super_modifier()),
+        "Can parse a bare 'F11' keypress with super"
+    );
+    let key = "\u{1b}[24;9~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(12)).with_super_modifier()),
+        "Can parse a bare 'F12' keypress with super"
+    );
+    let key = "\u{1b}[1;9D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Left).with_super_modifier()),
+        "Can parse a bare 'Left' keypress with super"
+    );
+    let key = "\u{1b}[1;9C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Right).with_super_modifier()),
+        "Can parse a bare 'Right' keypress with super"
+    );
+    let key = "\u{1b}[1;9A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up).with_super_modifier()),
+        "Can parse a bare 'Up' keypress with super"
+    );
+    let key = "\u{1b}[1;9B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Down).with_super_modifier()),
+        "Can parse a bare 'Down' keypress with super"
+    );
+    let key = "\u{1b}[1;9H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Home).with_super_modifier()),
+        "Can parse a bare 'Home' keypress with super"
+    );
+    let key = "\u{1b}[1;9F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::End).with_super_modifier()),
+        "Can parse a bare 'End' keypress with super"
+    );
+    let key = "\u{1b}[1;9P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(1)).with_super_modifier()),
+        "Can parse a bare 'F1 (alternate)' keypress with super"
+    );
+    let key = "\u{1b}[1;9Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(2)).with_super_modifier()),
+        "Can parse a bare 'F2 (alternate)' keypress with super"
+    );
+    let key = "\u{1b}[1;9S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::F(4)).with_super_modifier()),
+        "Can parse a bare 'F4 (alternate)' keypress with super"
+    );
+}
+
+#[test]
+fn can_parse_keys_with_multiple_modifiers() {
+    let key = "\u{1b}[97;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Char('a'))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'a' keypress with all modifiers"
+    );
+    let key = "\u{1b}[49;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Char('1'))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare '1' keypress with all modifiers"
+    );
+    let key = "\u{1b}[27;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Esc)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'ESC' keypress with all modifiers"
+    );
+    let key = "\u{1b}[13;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Enter)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'ENTER' keypress with all modifiers"
+    );
+    let key = "\u{1b}[9;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Tab)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Tab' keypress with all modifiers"
+    );
+    let key = "\u{1b}[127;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Backspace)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Backspace' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57358;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::CapsLock)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'CapsLock' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57359;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::ScrollLock)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'ScrollLock' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57360;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::NumLock)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'NumLock' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57361;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::PrintScreen)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'PrintScreen' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57362;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Pause)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Pause' keypress with all modifiers"
+    );
+    let key = "\u{1b}[57363;16u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Menu)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Menu' keypress with all modifiers"
+    );
+
+    let key = "\u{1b}[2;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Insert)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Insert' keypress with all modifiers"
+    );
+    let key = "\u{1b}[3;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Delete)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Delete' keypress with all modifiers"
+    );
+    let key = "\u{1b}[5;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::PageUp)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'PageUp' keypress with all modifiers"
+    );
+    let key = "\u{1b}[6;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::PageDown)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'PageDown' keypress with all modifiers"
+    );
+    let key = "\u{1b}[7;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Home)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Home' keypress with all modifiers"
+    );
+    let key = "\u{1b}[8;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::End)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'End' keypress with all modifiers"
+    );
+    let key = "\u{1b}[11;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(1))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F1' keypress with all modifiers"
+    );
+    let key = "\u{1b}[12;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(2))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F2' keypress with all modifiers"
+    );
+    let key = "\u{1b}[13;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(3))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F3' keypress with all modifiers"
+    );
+    let key = "\u{1b}[14;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(4))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F4' keypress with all modifiers"
+    );
+    let key = "\u{1b}[15;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(5))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F5' keypress with all modifiers"
+    );
+    let key = "\u{1b}[17;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(6))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F6' keypress with all modifiers"
+    );
+    let key = "\u{1b}[18;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(7))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F7' keypress with all modifiers"
+    );
+    let key = "\u{1b}[19;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(8))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F8' keypress with all modifiers"
+    );
+    let key = "\u{1b}[20;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(9))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F9' keypress with all modifiers"
+    );
+    let key = "\u{1b}[21;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(10))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F10' keypress with all modifiers"
+    );
+    let key = "\u{1b}[23;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(11))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F11' keypress with all modifiers"
+    );
+    let key = "\u{1b}[24;16~";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(12))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F12' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16D";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Left)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Left' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16C";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Right)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Right' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Up)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Up' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16B";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Down)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Down' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16H";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::Home)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'Home' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16F";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::End)
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'End' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16P";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(1))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F1 (superernate)' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16Q";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(2))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F2 (superernate)' keypress with all modifiers"
+    );
+    let key = "\u{1b}[1;16S";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(
+            KeyWithModifier::new(BareKey::F(4))
+                .with_super_modifier()
+                .with_ctrl_modifier()
+                .with_alt_modifier()
+                .with_shift_modifier()
+        ),
+        "Can parse a bare 'F4 (superernate)' keypress with all modifiers"
+    );
+}
+
+#[test]
+fn can_parse_key_events_of_the_progressive_enhancements() {
+    let key = "\u{1b}[97:65;2:3;65u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a')).with_shift_modifier()),
+        "Can parse a key release with alternate keys and associated text"
+    );
+    let key = "\u{1b}[1;5:2A";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Up).with_ctrl_modifier()),
+        "Can parse a repeated 'Up' keypress"
+    );
+    let key = "\u{1b}[97;65u";
+    assert_eq!(
+        parse_key(key.as_bytes()),
+        Some(KeyWithModifier::new(BareKey::Char('a'))),
+        "Can parse a bare 'a' keypress with caps lock on"
+    );
+}
@@ -148,6 +148,40 @@ fn layout_with_floating_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_pinned_floating_panes() {
+    let kdl_layout = r#"
+        layout {
+            floating_panes {
+                pane pinned=true
+                pane {
+                    pinned true
+                }
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout::default(),
+            vec![
+                FloatingPaneLayout {
+                    pinned: Some(true),
+                    ..Default::default()
+                },
+                FloatingPaneLayout {
+                    pinned: Some(true),
+                    ..Default::default()
+                },
+                FloatingPaneLayout::default(),
+            ],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
 #[test]
 fn layout_with_mixed_panes_and_floating_panes() {
     let kdl_layout = r#"
@@ -524,6 +558,50 @@ fn layout_with_command_panes_and_start_suspended() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn layout_with_command_panes_and_env() {
+    let kdl_layout = r#"
+        layout {
+            pane command="htop" {
+                env {
+                    TERM "xterm-256color"
+                    HTOPRC "/tmp/htoprc"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn layout_with_monitored_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane monitor_activity=true
+            pane command="tail" {
+                monitor_silence 30
+            }
+            floating_panes {
+                pane monitor_activity=true monitor_silence=10
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn error_on_non_positive_monitor_silence() {
+    let kdl_layout = r#"
+        layout {
+            pane monitor_silence=0
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided for monitor_silence of 0");
+}
+
 #[test]
 fn layout_with_plugin_panes() {
     let kdl_layout = r#"
@@ -1427,6 +1505,86 @@ fn close_on_exit_added_to_close_on_exit_in_template() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn env_is_merged_with_env_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="tail" {
+                command "tail"
+                env {
+                    LANG "C"
+                    TZ "UTC"
+                }
+            }
+            tail
+            tail {
+                env {
+                    TZ "Europe/Paris"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn tab_env_is_added_to_its_command_panes() {
+    let kdl_layout = r#"
+        layout {
+            tab_template name="with_env" {
+                env {
+                    EDITOR "vim"
+                    PAGER "less"
+                }
+                pane command="top"
+                children
+            }
+            with_env {
+                env {
+                    PAGER "more"
+                }
+                pane command="htop" {
+                    env {
+                        EDITOR "nano"
+                    }
+                }
+                floating_panes {
+                    pane command="tail"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let env_of = |run: &Option<Run>| match run {
+        Some(Run::Command(run_command)) => run_command.env.clone(),
+        _ => BTreeMap::new(),
+    };
+    let env = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<BTreeMap<String, String>>()
+    };
+    let (_tab_name, tiled_panes, floating_panes) = &layout.tabs[0];
+    assert_eq!(tiled_panes.children.len(), 2);
+    assert_eq!(
+        env_of(&tiled_panes.children[0].run),
+        env(&[("EDITOR", "vim"), ("PAGER", "more")]),
+        "tab env overrides the env of its template"
+    );
+    assert_eq!(
+        env_of(&tiled_panes.children[1].children[0].run),
+        env(&[("EDITOR", "nano"), ("PAGER", "more")]),
+        "pane env overrides the env of its tab"
+    );
+    assert_eq!(
+        env_of(&floating_panes[0].run),
+        env(&[("EDITOR", "vim"), ("PAGER", "more")]),
+        "tab env is added to floating panes"
+    );
+}
+
 #[test]
 fn cwd_override_cwd_in_template() {
     let kdl_layout = r#"
@@ -1515,6 +1673,363 @@ fn error_on_bare_close_on_exit_without_command() {
     assert!(layout.is_err(), "error provided");
 }
 
+#[test]
+fn error_on_bare_env_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    TERM "xterm"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_vars_are_replaced_with_given_values_or_defaults() {
+    let kdl_layout = r#"
+        layout {
+            vars {
+                service
+                port 8080
+                log_level "info"
+            }
+            pane_template name="service_logs" {
+                command "tail"
+                args "-f" "/var/log/{{ service }}.log"
+            }
+            tab name="{{service}}" {
+                pane command="./run-{{service}}" name="{{service}} on port {{port}}" {
+                    args "--log-level" "{{log_level}}"
+                }
+                service_logs
+            }
+        }
+    "#;
+    let layout_vars = BTreeMap::from([
+        ("service".to_owned(), "billing".to_owned()),
+        ("log_level".to_owned(), "debug".to_owned()),
+    ]);
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_vars,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let (tab_name, tiled_panes, _floating_panes) = &layout.tabs[0];
+    assert_eq!(tab_name.as_deref(), Some("billing"));
+    assert_eq!(
+        tiled_panes.children[0].name.as_deref(),
+        Some("billing on port 8080")
+    );
+    match &tiled_panes.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("./run-billing"));
+            assert_eq!(run_command.args, vec!["--log-level", "debug"]);
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+    match &tiled_panes.children[1].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.args, vec!["-f", "/var/log/billing.log"]);
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn error_on_undefined_layout_var() {
+    let kdl_layout = r#"
+        layout {
+            vars {
+                service "billing"
+            }
+            pane command="./run-{{service}}" name="{{ servce }}"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_layout_var_without_default_or_value() {
+    let kdl_layout = r#"
+        layout {
+            vars {
+                service
+            }
+            pane command="./run-{{service}}"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_layout_var_value_not_declared_by_layout() {
+    let kdl_layout = r#"
+        layout {
+            vars {
+                service "billing"
+            }
+            pane command="./run-{{service}}"
+        }
+    "#;
+    let layout_vars = BTreeMap::from([("sevrice".to_owned(), "payments".to_owned())]);
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_vars,
+            ..Default::default()
+        },
+    );
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn braces_are_kept_as_is_in_layouts_without_vars() {
+    let kdl_layout = r#"
+        layout {
+            pane command="docker" {
+                args "ps" "--format" "{{.Names}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    match &tiled_panes.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.args, vec!["ps", "--format", "{{.Names}}"]);
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn escaped_braces_are_kept_as_is_in_layouts_with_vars() {
+    let kdl_layout = r#"
+        layout {
+            vars {
+                format ".Names"
+            }
+            pane command="docker" {
+                args "ps" "--format" "{{format}}" r"\{{.Names}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions::default(),
+    )
+    .unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    match &tiled_panes.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(
+                run_command.args,
+                vec!["ps", "--format", ".Names", "{{.Names}}"]
+            );
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn templates_and_swap_layouts_are_imported_from_included_files() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("templates.kdl"),
+        r#"
+            include "tab_templates.kdl"
+            pane_template name="logs" {
+                command "tail"
+                args "-f" "{{log_file}}"
+            }
+            swap_tiled_layout name="stacked" {
+                tab {
+                    pane stacked=true {
+                        children
+                    }
+                }
+            }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        layout_dir.path().join("tab_templates.kdl"),
+        r#"
+            tab_template name="with_bars" {
+                pane size=1 borderless=true {
+                    plugin location="zellij:tab-bar"
+                }
+                children
+            }
+        "#,
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        layout {
+            vars {
+                log_file "/var/log/syslog"
+            }
+            include "templates"
+            with_bars name="logs" {
+                logs
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_dir: Some(layout_dir.path().to_path_buf()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let (tab_name, tiled_panes, _floating_panes) = &layout.tabs[0];
+    assert_eq!(tab_name.as_deref(), Some("logs"));
+    assert_eq!(tiled_panes.children.len(), 2);
+    match &tiled_panes.children[1].children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("tail"));
+            assert_eq!(run_command.args, vec!["-f", "/var/log/syslog"]);
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+    assert_eq!(layout.swap_tiled_layouts.len(), 1);
+    assert_eq!(layout.swap_tiled_layouts[0].1.as_deref(), Some("stacked"));
+}
+
+#[test]
+fn templates_in_layout_take_precedence_over_included_ones() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("templates.kdl"),
+        r#"
+            pane_template name="editor" command="vim"
+        "#,
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        layout {
+            include "templates.kdl"
+            pane_template name="editor" command="hx"
+            editor
+        }
+    "#;
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_dir: Some(layout_dir.path().to_path_buf()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    match &tiled_panes.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("hx"));
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn error_on_include_cycle() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(layout_dir.path().join("a.kdl"), r#"include "b.kdl""#).unwrap();
+    std::fs::write(layout_dir.path().join("b.kdl"), r#"include "a.kdl""#).unwrap();
+    let kdl_layout = r#"
+        layout {
+            include "a.kdl"
+        }
+    "#;
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_dir: Some(layout_dir.path().to_path_buf()),
+            ..Default::default()
+        },
+    );
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            assert!(kdl_error.error_message.contains("Include cycle detected"));
+            assert!(
+                format!("{:?}", kdl_error.src).contains("b.kdl"),
+                "error points at the including file"
+            );
+        },
+        layout => panic!("expected an include cycle error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn error_in_included_file_points_at_included_file() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("templates.kdl"),
+        r#"
+            pane_template name="broken" {
+                pane size="not-a-size"
+            }
+        "#,
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        layout {
+            include "templates.kdl"
+            broken
+        }
+    "#;
+    let layout = Layout::from_kdl_with_options(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        LayoutParseOptions {
+            layout_dir: Some(layout_dir.path().to_path_buf()),
+            ..Default::default()
+        },
+    );
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            assert!(
+                format!("{:?}", kdl_error.src).contains("templates.kdl"),
+                "error points at the included file"
+            );
+        },
+        layout => panic!("expected an error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn error_on_missing_include() {
+    let kdl_layout = r#"
+        layout {
+            include "no_such_file.kdl"
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
 #[test]
 fn error_on_bare_args_in_template_without_command() {
     let kdl_layout = r#"
@@ -2242,3 +2757,120 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn dynamic_tiling_is_parsed_from_tab_properties() {
+    let kdl_layout = r#"
+        layout {
+            tab tiling="master_stack" master_ratio=70 master_count=2 {
+                pane
+            }
+            tab tiling="spiral" {
+                pane
+            }
+            tab {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let dynamic_tiling: Vec<Option<DynamicTiling>> = layout
+        .tabs
+        .iter()
+        .map(|(_, tab_layout, _)| tab_layout.dynamic_tiling)
+        .collect();
+    assert_eq!(
+        dynamic_tiling,
+        vec![
+            Some(DynamicTiling {
+                algorithm: TilingAlgorithm::MasterStack,
+                master_ratio: 70,
+                master_count: 2,
+            }),
+            Some(DynamicTiling::new(TilingAlgorithm::Spiral)),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn dynamic_tiling_from_tab_template_can_be_overridden_by_tab() {
+    let kdl_layout = r#"
+        layout {
+            tab_template name="tiled" tiling="equal_columns" {
+                children
+            }
+            tiled tiling="master_stack" {
+                pane
+            }
+            tiled {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_eq!(
+        layout.tabs[0].1.dynamic_tiling,
+        Some(DynamicTiling::new(TilingAlgorithm::MasterStack))
+    );
+    assert_eq!(
+        layout.tabs[1].1.dynamic_tiling,
+        Some(DynamicTiling::new(TilingAlgorithm::EqualColumns))
+    );
+}
+
+#[test]
+fn error_on_invalid_dynamic_tiling_properties() {
+    let invalid_layouts = [
+        r#"layout { tab tiling="no_such_algorithm"; }"#,
+        r#"layout { tab tiling="master_stack" master_ratio=100; }"#,
+        r#"layout { tab tiling="master_stack" master_count=-1; }"#,
+        r#"layout { tab master_ratio=50; }"#,
+    ];
+    for kdl_layout in invalid_layouts {
+        let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+        assert!(layout.is_err(), "{} should not parse", kdl_layout);
+    }
+}
+
+#[test]
+fn master_stack_tiled_layout() {
+    let dynamic_tiling = DynamicTiling::new(TilingAlgorithm::MasterStack);
+    assert_eq!(
+        dynamic_tiling.tiled_layout(1),
+        TiledPaneLayout::default(),
+        "a single pane takes up the whole space"
+    );
+    let tiled_layout = dynamic_tiling.tiled_layout(4);
+    assert_eq!(tiled_layout.pane_count(), 4);
+    assert_eq!(
+        tiled_layout.children_split_direction,
+        SplitDirection::Vertical
+    );
+    assert_eq!(
+        tiled_layout.children[0],
+        TiledPaneLayout {
+            split_size: Some(SplitSize::Percent(DEFAULT_MASTER_RATIO)),
+            ..Default::default()
+        },
+        "master pane is first"
+    );
+    assert_eq!(tiled_layout.children[1].children.len(), 3, "stack");
+}
+
+#[test]
+fn spiral_and_equal_columns_tiled_layouts() {
+    let spiral = DynamicTiling::new(TilingAlgorithm::Spiral).tiled_layout(3);
+    assert_eq!(spiral.pane_count(), 3);
+    assert_eq!(spiral.children_split_direction, SplitDirection::Vertical);
+    assert_eq!(
+        spiral.children[1].children_split_direction,
+        SplitDirection::Horizontal
+    );
+    let equal_columns = DynamicTiling::new(TilingAlgorithm::EqualColumns).tiled_layout(3);
+    assert_eq!(equal_columns.pane_count(), 3);
+    assert_eq!(
+        equal_columns.children_split_direction,
+        SplitDirection::Vertical
+    );
+}
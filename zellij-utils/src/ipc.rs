@@ -31,9 +31,9 @@ pub struct Session {
 }
 
 // How do we want to connect to a session?
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ClientType {
-    Reader,
+    Reader, // only renders the session, input that would change it is ignored
     Writer,
 }
 
@@ -83,6 +83,7 @@ pub enum ClientToServerMsg {
         Options,
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane id to focus
+        ClientType,
     ),
     Action(Action, Option<u32>, Option<ClientId>), // u32 is the terminal id
     ClientExited,
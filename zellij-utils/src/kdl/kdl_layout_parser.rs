@@ -2,9 +2,9 @@ use crate::input::{
     command::RunCommand,
     config::ConfigError,
     layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
-        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        DynamicTiling, FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed,
+        PluginUserConfiguration, Run, RunPluginOrAlias, SplitDirection, SplitSize,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout, TilingAlgorithm,
     },
 };
 
@@ -23,7 +23,7 @@ use crate::{
     kdl_string_arguments,
 };
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::vec::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,6 +41,10 @@ pub struct KdlLayoutParser<'a> {
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     file_name: PathBuf,
+    layout_vars: BTreeMap<String, String>,
+    declares_layout_vars: bool, // only layouts with a vars block have their {{...}} replaced
+    layout_dir: Option<PathBuf>,
+    include_stack: Vec<PathBuf>, // the files currently being included, used to detect cycles
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -53,8 +57,20 @@ impl<'a> KdlLayoutParser<'a> {
             new_tab_template: None,
             global_cwd,
             file_name: PathBuf::from(file_name),
+            layout_vars: BTreeMap::new(),
+            declares_layout_vars: false,
+            layout_dir: None,
+            include_stack: vec![],
         }
     }
+    pub fn with_layout_vars(mut self, layout_vars: BTreeMap<String, String>) -> Self {
+        self.layout_vars = layout_vars;
+        self
+    }
+    pub fn with_layout_dir(mut self, layout_dir: Option<PathBuf>) -> Self {
+        self.layout_dir = layout_dir;
+        self
+    }
     fn is_a_reserved_word(&self, word: &str) -> bool {
         // note that it's important that none of these words happens to also be a config property,
         // otherwise they might collide
@@ -80,8 +96,13 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "split_direction"
             || word == "swap_tiled_layout"
             || word == "swap_floating_layout"
+            || word == "vars"
+            || word == "include"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "tiling"
+            || word == "master_ratio"
+            || word == "master_count"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -93,6 +114,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "split_direction"
@@ -102,6 +124,8 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "monitor_activity"
+            || property_name == "monitor_silence"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -112,13 +136,17 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "x"
             || property_name == "y"
             || property_name == "width"
             || property_name == "height"
+            || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "monitor_activity"
+            || property_name == "monitor_silence"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -131,6 +159,9 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "min_panes"
             || property_name == "exact_panes"
             || property_name == "hide_floating_panes"
+            || property_name == "tiling"
+            || property_name == "master_ratio"
+            || property_name == "master_count"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
@@ -205,6 +236,64 @@ impl<'a> KdlLayoutParser<'a> {
         }
         Ok(())
     }
+    fn parse_monitor_silence(&self, kdl_node: &KdlNode) -> Result<Option<u64>, ConfigError> {
+        if let Some(seconds) = kdl_get_int_property_or_child_value!(kdl_node, "monitor_silence") {
+            if seconds <= 0 {
+                return Err(kdl_parsing_error!(
+                    format!("monitor_silence should be greater than 0"),
+                    kdl_node
+                ));
+            }
+            Ok(Some(seconds as u64))
+        } else if let Some(node) = kdl_property_or_child_value_node!(kdl_node, "monitor_silence") {
+            Err(kdl_parsing_error!(
+                format!("monitor_silence should be a number of seconds (eg. 30)"),
+                node
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+    fn parse_dynamic_tiling(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<DynamicTiling>, ConfigError> {
+        let algorithm = match kdl_get_string_property_or_child_value!(kdl_node, "tiling") {
+            Some(algorithm) => TilingAlgorithm::from_str(algorithm)
+                .map_err(|e| kdl_parsing_error!(e.to_string(), kdl_node))?,
+            None => {
+                let tiling_option = kdl_property_or_child_value_node!(kdl_node, "master_ratio")
+                    .or_else(|| kdl_property_or_child_value_node!(kdl_node, "master_count"));
+                if let Some(tiling_option) = tiling_option {
+                    return Err(kdl_parsing_error!(
+                        format!("master_ratio and master_count can only be set along with a tiling algorithm"),
+                        tiling_option
+                    ));
+                }
+                return Ok(None);
+            },
+        };
+        let mut dynamic_tiling = DynamicTiling::new(algorithm);
+        if let Some(master_ratio) = kdl_get_int_property_or_child_value!(kdl_node, "master_ratio") {
+            if master_ratio <= 0 || master_ratio >= 100 {
+                return Err(kdl_parsing_error!(
+                    format!("master_ratio should be a percent between 1 and 99"),
+                    kdl_node
+                ));
+            }
+            dynamic_tiling.master_ratio = master_ratio as usize;
+        }
+        if let Some(master_count) = kdl_get_int_property_or_child_value!(kdl_node, "master_count") {
+            if master_count < 0 {
+                return Err(kdl_parsing_error!(
+                    format!("master_count should not be negative"),
+                    kdl_node
+                ));
+            }
+            dynamic_tiling.master_count = master_count as usize;
+        }
+        Ok(Some(dynamic_tiling))
+    }
     fn parse_split_size(&self, kdl_node: &KdlNode) -> Result<Option<SplitSize>, ConfigError> {
         if let Some(size) = kdl_get_string_property_or_child_value!(kdl_node, "size") {
             match SplitSize::from_str(size) {
@@ -398,6 +487,30 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<BTreeMap<String, String>>, ConfigError> {
+        match kdl_get_child!(kdl_node, "env") {
+            Some(kdl_env) => {
+                let mut env = BTreeMap::new();
+                for env_variable in kdl_children_nodes!(kdl_env).unwrap_or_default() {
+                    let name = kdl_name!(env_variable);
+                    let value =
+                        kdl_first_entry_as_string!(env_variable).ok_or(kdl_parsing_error!(
+                            format!(
+                            "environment variable {} must have a string value (eg. {} \"value\")",
+                            name, name
+                        ),
+                            env_variable
+                        ))?;
+                    env.insert(name.to_owned(), value.to_owned());
+                }
+                Ok(Some(env))
+            },
+            None => Ok(None),
+        }
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -406,6 +519,17 @@ impl<'a> KdlLayoutParser<'a> {
             (None, None) => None,
         })
     }
+    fn add_env_to_tab(
+        &self,
+        env: &BTreeMap<String, String>,
+        tab_layout: &mut TiledPaneLayout,
+        tab_floating_panes: &mut [FloatingPaneLayout],
+    ) {
+        tab_layout.add_env_to_layout(env);
+        for floating_pane in tab_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(env);
+        }
+    }
     fn parse_path(
         &self,
         kdl_node: &KdlNode,
@@ -428,6 +552,7 @@ impl<'a> KdlLayoutParser<'a> {
         let edit = self.parse_path(pane_node, "edit")?;
         let cwd = self.parse_path(pane_node, "cwd")?;
         let args = self.parse_args(pane_node)?;
+        let env = self.parse_env(pane_node)?;
         let close_on_exit =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
         let start_suspended =
@@ -436,6 +561,7 @@ impl<'a> KdlLayoutParser<'a> {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
                 &args,
+                &env,
                 &close_on_exit,
                 &start_suspended,
                 pane_node,
@@ -451,6 +577,7 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                env: env.unwrap_or_default(),
             }))),
             (None, Some(edit), Some(cwd)) => {
                 Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
@@ -526,6 +653,9 @@ impl<'a> KdlLayoutParser<'a> {
             .map(|name| name.to_string());
         let exclude_from_sync =
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+        let monitor_activity =
+            kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity");
+        let monitor_silence = self.parse_monitor_silence(kdl_node)?;
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
         let split_size = self.parse_split_size(kdl_node)?;
@@ -576,6 +706,8 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            monitor_activity,
+            monitor_silence,
             ..Default::default()
         })
     }
@@ -594,6 +726,10 @@ impl<'a> KdlLayoutParser<'a> {
             .map(|name| name.to_string());
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let monitor_activity =
+            kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity");
+        let monitor_silence = self.parse_monitor_silence(kdl_node)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
         self.assert_no_mixed_children_and_properties(kdl_node)?;
         let pane_initial_contents = contents_file.and_then(|contents_file| {
             self.file_name.parent().and_then(|parent_folder| {
@@ -609,6 +745,9 @@ impl<'a> KdlLayoutParser<'a> {
             run,
             focus,
             pane_initial_contents,
+            monitor_activity,
+            monitor_silence,
+            pinned,
             ..Default::default()
         })
     }
@@ -697,6 +836,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let is_expanded_in_stack =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -705,6 +845,9 @@ impl<'a> KdlLayoutParser<'a> {
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+                let monitor_activity =
+                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity");
+                let monitor_silence = self.parse_monitor_silence(kdl_node)?;
 
                 let external_children_index = if should_mark_external_children_index {
                     self.populate_external_children_index(kdl_node)?
@@ -715,6 +858,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -729,6 +873,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -744,6 +889,12 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(exclude_from_sync) = exclude_from_sync {
                     pane_template.exclude_from_sync = Some(exclude_from_sync);
                 }
+                if let Some(monitor_activity) = monitor_activity {
+                    pane_template.monitor_activity = Some(monitor_activity);
+                }
+                if let Some(monitor_silence) = monitor_silence {
+                    pane_template.monitor_silence = Some(monitor_silence);
+                }
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
@@ -803,6 +954,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -812,6 +964,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -821,6 +974,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -830,6 +984,19 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(monitor_activity) =
+                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity")
+                {
+                    pane_template.monitor_activity = Some(monitor_activity);
+                }
+                if let Some(monitor_silence) = self.parse_monitor_silence(kdl_node)? {
+                    pane_template.monitor_silence = Some(monitor_silence);
+                }
+                if let Some(pinned) =
+                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned")
+                {
+                    pane_template.pinned = Some(pinned);
+                }
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
@@ -854,6 +1021,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -863,6 +1031,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -872,6 +1041,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -881,11 +1051,21 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(monitor_activity) =
+                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity")
+                {
+                    pane_template.monitor_activity = Some(monitor_activity);
+                }
+                if let Some(monitor_silence) = self.parse_monitor_silence(kdl_node)? {
+                    pane_template.monitor_silence = Some(monitor_silence);
+                }
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+                let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
                 let mut floating_pane = FloatingPaneLayout::from(&pane_template);
+                floating_pane.pinned = pinned;
                 if let Some(height) = height {
                     floating_pane.height = Some(height);
                 }
@@ -937,6 +1117,7 @@ impl<'a> KdlLayoutParser<'a> {
         let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
@@ -945,7 +1126,7 @@ impl<'a> KdlLayoutParser<'a> {
             || is_expanded_in_stack.is_some()
             || has_children_nodes;
         let has_floating_pane_properties =
-            height.is_some() || width.is_some() || x.is_some() || y.is_some();
+            height.is_some() || width.is_some() || x.is_some() || y.is_some() || pinned.is_some();
         if has_pane_properties || has_floating_pane_properties {
             Ok(false)
         } else {
@@ -974,6 +1155,7 @@ impl<'a> KdlLayoutParser<'a> {
         let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
@@ -982,7 +1164,7 @@ impl<'a> KdlLayoutParser<'a> {
             || is_expanded_in_stack.is_some()
             || has_children_nodes;
         let has_floating_pane_properties =
-            height.is_some() || width.is_some() || x.is_some() || y.is_some();
+            height.is_some() || width.is_some() || x.is_some() || y.is_some() || pinned.is_some();
 
         if has_pane_properties && has_floating_pane_properties {
             let mut pane_properties = vec![];
@@ -1017,6 +1199,9 @@ impl<'a> KdlLayoutParser<'a> {
             if y.is_some() {
                 floating_pane_properties.push("y");
             }
+            if pinned.is_some() {
+                floating_pane_properties.push("pinned");
+            }
             Err(ConfigError::new_layout_kdl_error(
                 format!(
                     "A pane_template cannot have both pane ({}) and floating pane ({}) properties",
@@ -1068,6 +1253,7 @@ impl<'a> KdlLayoutParser<'a> {
             let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
             let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
             let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+            let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
             self.pane_templates.insert(
                 template_name,
                 (
@@ -1078,6 +1264,7 @@ impl<'a> KdlLayoutParser<'a> {
                         width,
                         x,
                         y,
+                        pinned,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1162,11 +1349,15 @@ impl<'a> KdlLayoutParser<'a> {
             children_split_direction,
             children,
             hide_floating_panes,
+            dynamic_tiling: self.parse_dynamic_tiling(kdl_node)?,
             ..Default::default()
         };
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
             pane_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        if let Some(tab_env) = self.parse_env(kdl_node)? {
+            self.add_env_to_tab(&tab_env, &mut pane_layout, &mut child_floating_panes);
+        }
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
     fn parse_child_pane_nodes_for_tab(
@@ -1191,6 +1382,9 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" {
+                // parsed by the tab itself
+                continue;
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1289,11 +1483,13 @@ impl<'a> KdlLayoutParser<'a> {
         }
         false
     }
+    #[allow(clippy::too_many_arguments)]
     fn assert_no_bare_attributes_in_pane_node_with_template(
         &self,
         pane_run: &Option<Run>,
         pane_template_run: &Option<Run>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1304,6 +1500,12 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_node
             ));
         }
+        if let (None, None, true) = (pane_run, pane_template_run, env.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("env can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
         if let (None, None, true) = (pane_run, pane_template_run, close_on_exit.is_some()) {
             return Err(kdl_parsing_error!(
                 format!("close_on_exit can only be specified if a command was specified either in the pane_template or in the pane"),
@@ -1322,6 +1524,7 @@ impl<'a> KdlLayoutParser<'a> {
         &self,
         command: &Option<PathBuf>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1348,6 +1551,13 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
+            if env.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "env can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
         }
         Ok(())
     }
@@ -1573,6 +1783,21 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = self.cwd_prefix(tab_cwd.as_ref())? {
             tab_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        // the tab's environment variables take precedence over those of its template, which is
+        // applied here rather than when parsing the template so that it also reaches the
+        // panes inserted into its children block
+        for env in [
+            self.parse_env(kdl_node)?,
+            self.parse_env(tab_layout_kdl_node)?,
+        ]
+        .iter()
+        .flatten()
+        {
+            self.add_env_to_tab(env, &mut tab_layout, &mut tab_template_floating_panes);
+        }
+        if let Some(dynamic_tiling) = self.parse_dynamic_tiling(kdl_node)? {
+            tab_layout.dynamic_tiling = Some(dynamic_tiling);
+        }
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1667,6 +1892,9 @@ impl<'a> KdlLayoutParser<'a> {
                 } else if kdl_name!(child) == "floating_panes" {
                     children_index_offset += 1;
                     self.populate_floating_pane_children(child, &mut tab_floating_children)?;
+                } else if kdl_name!(child) == "env" {
+                    // applied to the tab consuming this template
+                    children_index_offset += 1;
                 } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                     return Err(ConfigError::new_layout_kdl_error(
                         format!("Tab property '{}' must be placed on the tab_template title line and not in the child braces", kdl_name!(child)),
@@ -1687,6 +1915,7 @@ impl<'a> KdlLayoutParser<'a> {
                 children_split_direction,
                 children: tab_children,
                 external_children_index,
+                dynamic_tiling: self.parse_dynamic_tiling(kdl_node)?,
                 ..Default::default()
             },
             tab_floating_children,
@@ -1694,13 +1923,16 @@ impl<'a> KdlLayoutParser<'a> {
     }
     fn default_template(&self) -> Result<Option<TiledPaneLayout>, ConfigError> {
         match &self.default_tab_template {
-            Some((template, _template_floating_panes, _kdl_node)) => {
+            Some((template, _template_floating_panes, kdl_node)) => {
                 let mut template = template.clone();
                 if let Some(children_index) = template.external_children_index {
                     template
                         .children
                         .insert(children_index, TiledPaneLayout::default())
                 }
+                if let Some(env) = self.parse_env(kdl_node)? {
+                    template.add_env_to_layout(&env);
+                }
                 template.external_children_index = None;
                 Ok(Some(template))
             },
@@ -1752,7 +1984,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let child_name = kdl_name!(child);
                 if child_name == "pane" {
                     self.get_pane_template_dependencies(child, all_dependencies)?;
-                } else if !self.is_a_reserved_word(child_name) {
+                } else if child_name != "env" && !self.is_a_reserved_word(child_name) {
                     all_dependencies.insert(child_name);
                     self.get_pane_template_dependencies(child, all_dependencies)?;
                 }
@@ -1776,6 +2008,267 @@ impl<'a> KdlLayoutParser<'a> {
         }
         Ok(())
     }
+    fn populate_layout_vars(&mut self, layout_node: &mut KdlNode) -> Result<(), ConfigError> {
+        // the values we were given (eg. with --layout-var) override the defaults declared in the
+        // layout's vars block, after which all references to them are replaced in place
+        let mut layout_vars = BTreeMap::new();
+        if let Some(vars_node) = kdl_get_child!(layout_node, "vars") {
+            self.declares_layout_vars = true;
+            for var_node in kdl_children_nodes!(vars_node).unwrap_or_default() {
+                let var_name = kdl_name!(var_node);
+                let default_value = kdl_first_entry_as_string!(var_node)
+                    .map(|v| v.to_owned())
+                    .or_else(|| kdl_first_entry_as_i64!(var_node).map(|v| v.to_string()));
+                let value = self
+                    .layout_vars
+                    .get(var_name)
+                    .cloned()
+                    .or(default_value)
+                    .ok_or(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "Layout variable '{}' has no default value and was not given one (eg. --layout-var {}=<value>)",
+                            var_name, var_name
+                        ),
+                        var_node.span().offset(),
+                        var_node.span().len(),
+                    ))?;
+                layout_vars.insert(var_name.to_owned(), value);
+            }
+        }
+        if let Some(undeclared_var) = self
+            .layout_vars
+            .keys()
+            .find(|var_name| !layout_vars.contains_key(*var_name))
+        {
+            return Err(ConfigError::new_layout_kdl_error(
+                format!(
+                    "Layout variable '{}' was given a value but is not declared in the layout's vars block",
+                    undeclared_var
+                ),
+                layout_node.span().offset(),
+                layout_node.span().len(),
+            ));
+        }
+        self.layout_vars = layout_vars;
+        if let Some(children) = layout_node.children_mut() {
+            for child in children.nodes_mut() {
+                if kdl_name!(child) != "vars" {
+                    self.substitute_layout_vars(child)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn substitute_layout_vars(&self, kdl_node: &mut KdlNode) -> Result<(), ConfigError> {
+        if !self.declares_layout_vars {
+            // layouts without a vars block keep their strings as is (eg. docker's "{{.Names}}")
+            return Ok(());
+        }
+        for entry in kdl_node.entries_mut() {
+            let substituted = match entry.value().as_string() {
+                Some(value) if value.contains("{{") => {
+                    self.interpolate_layout_vars(value).map_err(|var_name| {
+                        ConfigError::new_layout_kdl_error(
+                            format!(
+                                "Undefined layout variable: '{}' (layout variables must be declared in a vars block inside the layout node, eg. vars {{ {} \"default value\"; }}, a literal {{{{ can be written as \\{{{{)",
+                                var_name, var_name
+                            ),
+                            entry.span().offset(),
+                            entry.span().len(),
+                        )
+                    })?
+                },
+                _ => continue,
+            };
+            entry.set_value(substituted);
+        }
+        if let Some(children) = kdl_node.children_mut() {
+            for child in children.nodes_mut() {
+                self.substitute_layout_vars(child)?;
+            }
+        }
+        Ok(())
+    }
+    fn interpolate_layout_vars(&self, value: &str) -> Result<String, String> {
+        // replaces every {{var_name}} with its value, returning the name of the first variable
+        // that is not defined - an escaped \{{ is kept as a literal {{
+        let mut interpolated = String::new();
+        let mut remaining = value;
+        while let Some(start) = remaining.find("{{") {
+            let after_start = &remaining[start + 2..];
+            if remaining[..start].ends_with('\\') {
+                interpolated.push_str(&remaining[..start - 1]);
+                interpolated.push_str("{{");
+                remaining = after_start;
+                continue;
+            }
+            match after_start.find("}}") {
+                Some(end) => {
+                    let var_name = after_start[..end].trim();
+                    let var_value = self
+                        .layout_vars
+                        .get(var_name)
+                        .ok_or_else(|| var_name.to_owned())?;
+                    interpolated.push_str(&remaining[..start]);
+                    interpolated.push_str(var_value);
+                    remaining = &after_start[end + 2..];
+                },
+                None => break,
+            }
+        }
+        interpolated.push_str(remaining);
+        Ok(interpolated)
+    }
+    fn populate_includes(
+        &mut self,
+        layout_children: &[KdlNode],
+        swap_tiled_layouts: &mut Vec<SwapTiledLayout>,
+        swap_floating_layouts: &mut Vec<SwapFloatingLayout>,
+    ) -> Result<(), ConfigError> {
+        for child in layout_children.iter() {
+            if kdl_name!(child) == "include" {
+                self.populate_one_include(child, swap_tiled_layouts, swap_floating_layouts)?;
+            }
+        }
+        Ok(())
+    }
+    fn populate_one_include(
+        &mut self,
+        include_node: &KdlNode,
+        swap_tiled_layouts: &mut Vec<SwapTiledLayout>,
+        swap_floating_layouts: &mut Vec<SwapFloatingLayout>,
+    ) -> Result<(), ConfigError> {
+        let include_path = kdl_first_entry_as_string!(include_node)
+            .ok_or(ConfigError::new_layout_kdl_error(
+            "include must be given the path or url of a layout file, eg. include \"templates.kdl\""
+                .into(),
+            include_node.span().offset(),
+            include_node.span().len(),
+        ))?;
+        let included_file = self.resolve_include_path(include_path);
+        let including_file = self.include_stack.last().unwrap_or(&self.file_name);
+        let is_cycle = self
+            .include_stack
+            .iter()
+            .chain(std::iter::once(&self.file_name))
+            .any(|file| canonical_path(file) == canonical_path(&included_file));
+        if is_cycle {
+            return Err(ConfigError::new_layout_kdl_error(
+                format!(
+                    "Include cycle detected: {} is already being included (via {})",
+                    included_file.display(),
+                    including_file.display()
+                ),
+                include_node.span().offset(),
+                include_node.span().len(),
+            ));
+        }
+        let (included_file_name, raw_included) =
+            self.read_included_file(&included_file).map_err(|e| {
+                ConfigError::new_layout_kdl_error(
+                    format!("Failed to include {}: {}", include_path, e),
+                    include_node.span().offset(),
+                    include_node.span().len(),
+                )
+            })?;
+        // errors in the included file point at its own source, so that they show the right
+        // file and line
+        self.include_stack.push(included_file);
+        let populated = self
+            .populate_included_file(&raw_included, swap_tiled_layouts, swap_floating_layouts)
+            .map_err(|e| match e {
+                ConfigError::KdlError(kdl_error) if kdl_error.src.is_none() => {
+                    ConfigError::KdlError(
+                        kdl_error.add_src(included_file_name.clone(), raw_included.clone()),
+                    )
+                },
+                ConfigError::KdlDeserializationError(kdl_error) => {
+                    super::kdl_layout_error(kdl_error, included_file_name.clone(), &raw_included)
+                },
+                e => e,
+            });
+        self.include_stack.pop();
+        populated
+    }
+    fn resolve_include_path(&self, include_path: &str) -> PathBuf {
+        // includes are looked for in the layout_dir, and then relative to the file including them
+        let include_path = PathBuf::from(include_path);
+        if is_url(&include_path) || include_path.is_absolute() {
+            return include_path;
+        }
+        if let Some(layout_dir) = &self.layout_dir {
+            let path_in_layout_dir = layout_dir.join(&include_path);
+            if path_in_layout_dir.exists() || path_in_layout_dir.with_extension("kdl").exists() {
+                return path_in_layout_dir;
+            }
+        }
+        match self
+            .include_stack
+            .last()
+            .unwrap_or(&self.file_name)
+            .parent()
+        {
+            Some(including_file_folder) if !is_url(including_file_folder) => {
+                including_file_folder.join(include_path)
+            },
+            _ => include_path,
+        }
+    }
+    fn read_included_file(&self, included_file: &Path) -> Result<(String, String), ConfigError> {
+        // (included_file_name, raw_included)
+        if is_url(included_file) {
+            let url = included_file.to_string_lossy().to_string();
+            let raw_included = Layout::stringified_from_url(&url)?;
+            Ok((url, raw_included))
+        } else {
+            let (included_file_name, raw_included, _swap_layouts) =
+                Layout::stringified_from_path(included_file)?;
+            Ok((included_file_name, raw_included))
+        }
+    }
+    fn populate_included_file(
+        &mut self,
+        raw_included: &str,
+        swap_tiled_layouts: &mut Vec<SwapTiledLayout>,
+        swap_floating_layouts: &mut Vec<SwapFloatingLayout>,
+    ) -> Result<(), ConfigError> {
+        let mut kdl_included: KdlDocument = raw_included.parse()?;
+        for node in kdl_included.nodes_mut() {
+            self.substitute_layout_vars(node)?;
+        }
+        for node in kdl_included.nodes() {
+            let node_name = kdl_name!(node);
+            if node_name == "pane_template"
+                || node_name == "tab_template"
+                || node_name == "default_tab_template"
+                || node_name == "new_tab_template"
+                || node_name == "swap_tiled_layout"
+                || node_name == "swap_floating_layout"
+                || node_name == "include"
+            {
+                continue;
+            } else if self.is_a_reserved_word(node_name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Included files can only contain templates, swap layouts and other includes, found: {}",
+                        node_name
+                    ),
+                    node.span().offset(),
+                    node.span().len(),
+                ));
+            }
+        }
+        self.populate_includes(
+            kdl_included.nodes(),
+            swap_tiled_layouts,
+            swap_floating_layouts,
+        )?;
+        self.populate_pane_templates(kdl_included.nodes(), &kdl_included)?;
+        self.populate_tab_templates(kdl_included.nodes())?;
+        self.populate_swap_tiled_layouts(kdl_included.nodes(), swap_tiled_layouts)?;
+        self.populate_swap_floating_layouts(kdl_included.nodes(), swap_floating_layouts)?;
+        Ok(())
+    }
     fn populate_global_cwd(&mut self, layout_node: &KdlNode) -> Result<(), ConfigError> {
         // we only populate global cwd from the layout file if another wasn't explicitly passed to us
         if self.global_cwd.is_none() {
@@ -2290,7 +2783,10 @@ impl<'a> KdlLayoutParser<'a> {
         raw_swap_layouts: &str,
         mut existing_layout: Layout,
     ) -> Result<Layout, ConfigError> {
-        let kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        let mut kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        for node in kdl_swap_layout.nodes_mut() {
+            self.substitute_layout_vars(node)?;
+        }
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
 
@@ -2300,6 +2796,7 @@ impl<'a> KdlLayoutParser<'a> {
                 || node_name == "swap_tiled_layout"
                 || node_name == "tab_template"
                 || node_name == "pane_template"
+                || node_name == "include"
             {
                 continue;
             } else if node_name == "layout" {
@@ -2320,6 +2817,11 @@ impl<'a> KdlLayoutParser<'a> {
             }
         }
 
+        self.populate_includes(
+            kdl_swap_layout.nodes(),
+            &mut swap_tiled_layouts,
+            &mut swap_floating_layouts,
+        )?;
         self.populate_pane_templates(kdl_swap_layout.nodes(), &kdl_swap_layout)?;
         self.populate_tab_templates(kdl_swap_layout.nodes())?;
         self.populate_swap_tiled_layouts(kdl_swap_layout.nodes(), &mut swap_tiled_layouts)?;
@@ -2334,7 +2836,14 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(existing_layout)
     }
     pub fn parse(&mut self) -> Result<Layout, ConfigError> {
-        let kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        if let Some(layout_node) = kdl_layout
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| kdl_name!(n) == "layout")
+        {
+            self.populate_layout_vars(layout_node)?;
+        }
         let layout_node = kdl_layout
             .nodes()
             .iter()
@@ -2375,6 +2884,13 @@ impl<'a> KdlLayoutParser<'a> {
         let mut swap_floating_layouts = vec![];
         if let Some(children) = kdl_children_nodes!(layout_node) {
             self.populate_global_cwd(layout_node)?;
+            // included templates are populated first so that the ones defined in this file take
+            // precedence over them
+            self.populate_includes(
+                children,
+                &mut swap_tiled_layouts,
+                &mut swap_floating_layouts,
+            )?;
             self.populate_pane_templates(children, &kdl_layout)?;
             self.populate_tab_templates(children)?;
             self.populate_swap_tiled_layouts(children, &mut swap_tiled_layouts)?;
@@ -2435,3 +2951,14 @@ impl<'a> KdlLayoutParser<'a> {
         }
     }
 }
+
+fn is_url(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path)
+        .or_else(|_| std::fs::canonicalize(path.with_extension("kdl")))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
@@ -7,7 +7,9 @@ use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::keybinds::Keybinds;
-use crate::input::layout::{Layout, RunPlugin, RunPluginOrAlias};
+use crate::input::layout::{
+    Layout, LayoutParseOptions, RunPlugin, RunPluginOrAlias, TilingAlgorithm,
+};
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::PluginAliases;
@@ -47,6 +49,8 @@ macro_rules! parse_kdl_action_arguments {
                 "ScrollDown" => Ok(Action::ScrollDown),
                 "ScrollToBottom" => Ok(Action::ScrollToBottom),
                 "ScrollToTop" => Ok(Action::ScrollToTop),
+                "ScrollToPreviousPrompt" => Ok(Action::ScrollToPreviousPrompt),
+                "ScrollToNextPrompt" => Ok(Action::ScrollToNextPrompt),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
@@ -55,6 +59,11 @@ macro_rules! parse_kdl_action_arguments {
                 "TogglePaneFrames" => Ok(Action::TogglePaneFrames),
                 "ToggleActiveSyncTab" => Ok(Action::ToggleActiveSyncTab),
                 "TogglePaneEmbedOrFloating" => Ok(Action::TogglePaneEmbedOrFloating),
+                "TogglePanePinned" => Ok(Action::TogglePanePinned),
+                "UndoLayoutChange" => Ok(Action::UndoLayoutChange),
+                "RedoLayoutChange" => Ok(Action::RedoLayoutChange),
+                "UnstackPane" => Ok(Action::UnstackPane),
+                "StackAllPanes" => Ok(Action::StackAllPanes),
                 "ToggleFloatingPanes" => Ok(Action::ToggleFloatingPanes),
                 "CloseFocus" => Ok(Action::CloseFocus),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
@@ -66,11 +75,15 @@ macro_rules! parse_kdl_action_arguments {
                 "UndoRenameTab" => Ok(Action::UndoRenameTab),
                 "Detach" => Ok(Action::Detach),
                 "Copy" => Ok(Action::Copy),
+                "CopyCommandOutput" => Ok(Action::CopyCommandOutput),
                 "Confirm" => Ok(Action::Confirm),
                 "Deny" => Ok(Action::Deny),
                 "ToggleMouseMode" => Ok(Action::ToggleMouseMode),
                 "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
                 "NextSwapLayout" => Ok(Action::NextSwapLayout),
+                "PromoteToMaster" => Ok(Action::PromoteToMaster),
+                "IncreaseMasterCount" => Ok(Action::IncreaseMasterCount),
+                "DecreaseMasterCount" => Ok(Action::DecreaseMasterCount),
                 "Clear" => Ok(Action::ClearScreen),
                 _ => Err(ConfigError::new_kdl_error(
                     format!("Unsupported action: {:?}", $action_name),
@@ -402,6 +415,16 @@ impl Action {
                 })? as u32;
                 Ok(Action::GoToTab(tab_index))
             },
+            "MergeTab" => {
+                let tab_index = *bytes.first().ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        "Missing tab index".into(),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::MergeTab(None, tab_index))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 "Failed to parse action".into(),
                 action_node.span().offset(),
@@ -460,6 +483,16 @@ impl Action {
                 })?;
                 Ok(Action::MoveFocus(direction))
             },
+            "StackPane" => {
+                let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid direction: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::StackPane(direction))
+            },
             "MoveFocusOrTab" => {
                 let direction = Direction::from_str(string.as_str()).map_err(|_| {
                     ConfigError::new_kdl_error(
@@ -504,7 +537,7 @@ impl Action {
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
             "DumpScreen" => Ok(Action::DumpScreen(string, false)),
-            "DumpLayout" => Ok(Action::DumpLayout),
+            "DumpLayout" => Ok(Action::DumpLayout(false)),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane(None, None));
@@ -541,6 +574,19 @@ impl Action {
                 Ok(Action::Search(search_direction))
             },
             "RenameSession" => Ok(Action::RenameSession(string)),
+            "SetTiling" => {
+                if string.eq_ignore_ascii_case("none") {
+                    return Ok(Action::SetTiling(None));
+                }
+                let algorithm = TilingAlgorithm::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid tiling algorithm: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::SetTiling(Some(algorithm)))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -693,6 +739,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ScrollToTop" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ScrollToPreviousPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollToNextPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "PageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -717,6 +769,21 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "TogglePaneEmbedOrFloating" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "TogglePanePinned" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "UndoLayoutChange" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "RedoLayoutChange" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "UnstackPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "StackAllPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "ToggleFloatingPanes" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -739,6 +806,9 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             },
             "Detach" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Copy" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "CopyCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "Clear" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Confirm" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Deny" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
@@ -773,6 +843,11 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "StackPane" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MoveTab" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -894,6 +969,7 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 }
             },
             "GoToTab" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
+            "MergeTab" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
             "TabNameInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -946,6 +1022,7 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    ..Default::default()
                 };
                 let x = command_metadata
                     .and_then(|c_m| kdl_child_string_value_for_entry(c_m, "x"))
@@ -1074,6 +1151,14 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             },
             "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
             "NextSwapLayout" => Ok(Action::NextSwapLayout),
+            "SetTiling" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "PromoteToMaster" => Ok(Action::PromoteToMaster),
+            "IncreaseMasterCount" => Ok(Action::IncreaseMasterCount),
+            "DecreaseMasterCount" => Ok(Action::DecreaseMasterCount),
             "BreakPane" => Ok(Action::BreakPane),
             "BreakPaneRight" => Ok(Action::BreakPaneRight),
             "BreakPaneLeft" => Ok(Action::BreakPaneLeft),
@@ -1572,6 +1657,9 @@ impl Options {
         let scroll_buffer_size =
             kdl_property_first_arg_as_i64_or_error!(kdl_options, "scroll_buffer_size")
                 .map(|(scroll_buffer_size, _entry)| scroll_buffer_size as usize);
+        let scrollback_to_disk =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "scrollback_to_disk")
+                .map(|(v, _)| v);
         let copy_command = kdl_property_first_arg_as_string_or_error!(kdl_options, "copy_command")
             .map(|(copy_command, _entry)| copy_command.to_string());
         let copy_clipboard =
@@ -1633,6 +1721,7 @@ impl Options {
             mirror_session,
             on_force_close,
             scroll_buffer_size,
+            scrollback_to_disk,
             copy_command,
             copy_clipboard,
             copy_on_select,
@@ -1658,9 +1747,29 @@ impl Layout {
         raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
         cwd: Option<PathBuf>,
     ) -> Result<Self, ConfigError> {
-        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone());
+        Layout::from_kdl_with_options(
+            raw_layout,
+            file_name,
+            raw_swap_layouts,
+            LayoutParseOptions {
+                cwd,
+                ..Default::default()
+            },
+        )
+    }
+    pub fn from_kdl_with_options(
+        raw_layout: &str,
+        file_name: String,
+        raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
+        options: LayoutParseOptions,
+    ) -> Result<Self, ConfigError> {
+        let mut kdl_layout_parser =
+            KdlLayoutParser::new(raw_layout, options.cwd, file_name.clone())
+                .with_layout_vars(options.layout_vars)
+                .with_layout_dir(options.layout_dir);
         let layout = kdl_layout_parser.parse().map_err(|e| match e {
-            ConfigError::KdlError(kdl_error) => {
+            // errors in included files already carry their own source
+            ConfigError::KdlError(kdl_error) if kdl_error.src.is_none() => {
                 ConfigError::KdlError(kdl_error.add_src(file_name, String::from(raw_layout)))
             },
             ConfigError::KdlDeserializationError(kdl_error) => {
@@ -1675,7 +1784,7 @@ impl Layout {
                 kdl_layout_parser
                     .parse_external_swap_layouts(raw_swap_layout, layout)
                     .map_err(|e| match e {
-                        ConfigError::KdlError(kdl_error) => {
+                        ConfigError::KdlError(kdl_error) if kdl_error.src.is_none() => {
                             ConfigError::KdlError(kdl_error.add_src(
                                 String::from(raw_swap_layout_filename),
                                 String::from(raw_swap_layout),
@@ -2252,6 +2361,10 @@ impl TabInfo {
         }
         let active_swap_layout_name = optional_string_node!("active_swap_layout_name");
         let is_swap_layout_dirty = bool_node!("is_swap_layout_dirty");
+        let has_unseen_notification = bool_node!("has_unseen_notification");
+        let has_bell = bool_node!("has_bell");
+        let has_activity = bool_node!("has_activity");
+        let has_silence = bool_node!("has_silence");
         Ok(TabInfo {
             position,
             name,
@@ -2263,6 +2376,10 @@ impl TabInfo {
             other_focused_clients,
             active_swap_layout_name,
             is_swap_layout_dirty,
+            has_unseen_notification,
+            has_bell,
+            has_activity,
+            has_silence,
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -2314,6 +2431,17 @@ impl TabInfo {
         is_swap_layout_dirty.push(self.is_swap_layout_dirty);
         kdl_doucment.nodes_mut().push(is_swap_layout_dirty);
 
+        for (name, value) in [
+            ("has_unseen_notification", self.has_unseen_notification),
+            ("has_bell", self.has_bell),
+            ("has_activity", self.has_activity),
+            ("has_silence", self.has_silence),
+        ] {
+            let mut node = KdlNode::new(name);
+            node.push(value);
+            kdl_doucment.nodes_mut().push(node);
+        }
+
         kdl_doucment
     }
 }
@@ -2404,6 +2532,15 @@ impl PaneInfo {
                     .map(|s| s.to_owned())
             }};
         }
+        macro_rules! optional_bool_node {
+            ($name:expr) => {{
+                kdl_document
+                    .get($name)
+                    .and_then(|n| n.entries().iter().next())
+                    .and_then(|e| e.value().as_bool())
+                    .unwrap_or(false)
+            }};
+        }
         let tab_position = int_node!("tab_position", usize);
         let id = int_node!("id", u32);
 
@@ -2411,10 +2548,13 @@ impl PaneInfo {
         let is_focused = bool_node!("is_focused");
         let is_fullscreen = bool_node!("is_fullscreen");
         let is_floating = bool_node!("is_floating");
+        let is_pinned = optional_bool_node!("is_pinned");
         let is_suppressed = bool_node!("is_suppressed");
         let title = string_node!("title");
         let exited = bool_node!("exited");
         let exit_status = optional_int_node!("exit_status", i32);
+        let last_command_exit_code = optional_int_node!("last_command_exit_code", i32);
+        let selected_command_exit_code = optional_int_node!("selected_command_exit_code", i32);
         let is_held = bool_node!("is_held");
         let pane_x = int_node!("pane_x", usize);
         let pane_content_x = int_node!("pane_content_x", usize);
@@ -2440,6 +2580,10 @@ impl PaneInfo {
             });
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
+        let cwd = optional_string_node!("cwd").map(PathBuf::from);
+        let has_bell = optional_bool_node!("has_bell");
+        let has_activity = optional_bool_node!("has_activity");
+        let has_silence = optional_bool_node!("has_silence");
         let is_selectable = bool_node!("is_selectable");
 
         let pane_info = PaneInfo {
@@ -2448,10 +2592,17 @@ impl PaneInfo {
             is_focused,
             is_fullscreen,
             is_floating,
+            is_pinned,
             is_suppressed,
             title,
             exited,
             exit_status,
+            last_command_exit_code,
+            selected_command_exit_code,
+            cwd,
+            has_bell,
+            has_activity,
+            has_silence,
             is_held,
             pane_x,
             pane_content_x,
@@ -2503,6 +2654,12 @@ impl PaneInfo {
         if let Some(exit_status) = self.exit_status {
             int_node!("exit_status", exit_status);
         }
+        if let Some(last_command_exit_code) = self.last_command_exit_code {
+            int_node!("last_command_exit_code", last_command_exit_code);
+        }
+        if let Some(selected_command_exit_code) = self.selected_command_exit_code {
+            int_node!("selected_command_exit_code", selected_command_exit_code);
+        }
         bool_node!("is_held", self.is_held);
         int_node!("pane_x", self.pane_x);
         int_node!("pane_content_x", self.pane_content_x);
@@ -2524,6 +2681,21 @@ impl PaneInfo {
         if let Some(plugin_url) = &self.plugin_url {
             string_node!("plugin_url", plugin_url.to_string());
         }
+        if let Some(cwd) = &self.cwd {
+            string_node!("cwd", cwd.display().to_string());
+        }
+        if self.is_pinned {
+            bool_node!("is_pinned", self.is_pinned);
+        }
+        if self.has_bell {
+            bool_node!("has_bell", self.has_bell);
+        }
+        if self.has_activity {
+            bool_node!("has_activity", self.has_activity);
+        }
+        if self.has_silence {
+            bool_node!("has_silence", self.has_silence);
+        }
         bool_node!("is_selectable", self.is_selectable);
         kdl_doucment
     }
@@ -2595,10 +2767,17 @@ fn serialize_and_deserialize_session_info_with_data() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
             exit_status: None,
+            last_command_exit_code: None,
+            selected_command_exit_code: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
             is_held: false,
             pane_x: 0,
             pane_content_x: 1,
@@ -2619,10 +2798,17 @@ fn serialize_and_deserialize_session_info_with_data() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
             exit_status: None,
+            last_command_exit_code: None,
+            selected_command_exit_code: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
             is_held: false,
             pane_x: 0,
             pane_content_x: 1,
@@ -2654,6 +2840,10 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: Some("BASE".to_owned()),
                 is_swap_layout_dirty: true,
+                has_unseen_notification: false,
+                has_bell: false,
+                has_activity: false,
+                has_silence: false,
             },
             TabInfo {
                 position: 1,
@@ -2666,6 +2856,10 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: None,
                 is_swap_layout_dirty: false,
+                has_unseen_notification: false,
+                has_bell: false,
+                has_activity: false,
+                has_silence: false,
             },
         ],
         panes: PaneManifest { panes },
@@ -1,31 +1,34 @@
 pub use super::generated_api::api::{
     action::{
         action::OptionalPayload, Action as ProtobufAction, ActionName as ProtobufActionName,
-        DumpScreenPayload, EditFilePayload, GoToTabNamePayload, IdAndName,
-        LaunchOrFocusPluginPayload, MovePanePayload, MoveTabDirection as ProtobufMoveTabDirection,
+        ChangeFloatingPaneCoordinatesPayload, DumpScreenPayload, EditFilePayload,
+        GoToTabNamePayload, IdAndName, LaunchOrFocusPluginPayload, MergeTabPayload,
+        MovePanePayload, MovePaneToTabPayload, MoveTabDirection as ProtobufMoveTabDirection,
         NameAndValue as ProtobufNameAndValue, NewFloatingPanePayload, NewPanePayload,
         NewPluginPanePayload, NewTiledPanePayload, PaneIdAndShouldFloat,
         PluginConfiguration as ProtobufPluginConfiguration, Position as ProtobufPosition,
         RunCommandAction as ProtobufRunCommandAction, ScrollAtPayload,
         SearchDirection as ProtobufSearchDirection, SearchOption as ProtobufSearchOption,
-        SwitchToModePayload, WriteCharsPayload, WritePayload,
+        SplitSizeValue, SwitchToModePayload, WriteCharsPayload, WritePayload,
     },
     input_mode::InputMode as ProtobufInputMode,
     resize::{Resize as ProtobufResize, ResizeDirection as ProtobufResizeDirection},
 };
-use crate::data::{Direction, InputMode, ResizeStrategy};
+use crate::data::{Direction, FloatingPaneCoordinates, InputMode, PaneId, ResizeStrategy};
 use crate::errors::prelude::*;
 use crate::input::actions::Action;
 use crate::input::actions::{SearchDirection, SearchOption};
 use crate::input::command::RunCommandAction;
 use crate::input::layout::{
-    PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias,
+    PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitSize,
+    TilingAlgorithm,
 };
 use crate::position::Position;
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 impl TryFrom<ProtobufAction> for Action {
     type Error = &'static str;
@@ -179,6 +182,18 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("ScrollToTop should not have a payload"),
                 None => Ok(Action::ScrollToTop),
             },
+            Some(ProtobufActionName::ScrollToPreviousPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToPreviousPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToPreviousPrompt),
+                }
+            },
+            Some(ProtobufActionName::ScrollToNextPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToNextPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToNextPrompt),
+                }
+            },
             Some(ProtobufActionName::PageScrollUp) => match protobuf_action.optional_payload {
                 Some(_) => Err("PageScrollUp should not have a payload"),
                 None => Ok(Action::PageScrollUp),
@@ -289,6 +304,36 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::TogglePaneEmbedOrFloating),
                 }
             },
+            Some(ProtobufActionName::TogglePanePinned) => match protobuf_action.optional_payload {
+                Some(_) => Err("TogglePanePinned should not have a payload"),
+                None => Ok(Action::TogglePanePinned),
+            },
+            Some(ProtobufActionName::UndoLayoutChange) => match protobuf_action.optional_payload {
+                Some(_) => Err("UndoLayoutChange should not have a payload"),
+                None => Ok(Action::UndoLayoutChange),
+            },
+            Some(ProtobufActionName::RedoLayoutChange) => match protobuf_action.optional_payload {
+                Some(_) => Err("RedoLayoutChange should not have a payload"),
+                None => Ok(Action::RedoLayoutChange),
+            },
+            Some(ProtobufActionName::StackPane) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::StackPanePayload(stack_pane_payload)) => {
+                    let direction: Direction =
+                        ProtobufResizeDirection::from_i32(stack_pane_payload)
+                            .ok_or("Malformed direction for Action::StackPane")?
+                            .try_into()?;
+                    Ok(Action::StackPane(direction))
+                },
+                _ => Err("Wrong payload for Action::StackPane"),
+            },
+            Some(ProtobufActionName::UnstackPane) => match protobuf_action.optional_payload {
+                Some(_) => Err("UnstackPane should not have a payload"),
+                None => Ok(Action::UnstackPane),
+            },
+            Some(ProtobufActionName::StackAllPanes) => match protobuf_action.optional_payload {
+                Some(_) => Err("StackAllPanes should not have a payload"),
+                None => Ok(Action::StackAllPanes),
+            },
             Some(ProtobufActionName::ToggleFloatingPanes) => {
                 match protobuf_action.optional_payload {
                     Some(_) => Err("ToggleFloatingPanes should not have a payload"),
@@ -544,7 +589,7 @@ impl TryFrom<ProtobufAction> for Action {
             },
             Some(ProtobufActionName::QueryTabNames) => match protobuf_action.optional_payload {
                 Some(_) => Err("QueryTabNames should not have a payload"),
-                None => Ok(Action::QueryTabNames),
+                None => Ok(Action::QueryTabNames(false)),
             },
             Some(ProtobufActionName::NewTiledPluginPane) => {
                 match protobuf_action.optional_payload {
@@ -689,6 +734,79 @@ impl TryFrom<ProtobufAction> for Action {
                 },
                 _ => Err("Wrong payload for Action::RenameSession"),
             },
+            Some(ProtobufActionName::CopyCommandOutput) => match protobuf_action.optional_payload {
+                Some(_) => Err("CopyCommandOutput should not have a payload"),
+                None => Ok(Action::CopyCommandOutput),
+            },
+            Some(ProtobufActionName::SetTiling) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SetTilingPayload(algorithm)) => {
+                    let algorithm = TilingAlgorithm::from_str(&algorithm)
+                        .map_err(|_| "Malformed payload for Action::SetTiling")?;
+                    Ok(Action::SetTiling(Some(algorithm)))
+                },
+                None => Ok(Action::SetTiling(None)),
+                _ => Err("Wrong payload for Action::SetTiling"),
+            },
+            Some(ProtobufActionName::MovePaneToTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MovePaneToTabPayload(payload)) => {
+                    let pane_id = if payload.is_plugin {
+                        PaneId::Plugin(payload.pane_id)
+                    } else {
+                        PaneId::Terminal(payload.pane_id)
+                    };
+                    Ok(Action::MovePaneToTab(
+                        pane_id,
+                        payload.tab_position,
+                        payload.tab_name,
+                        payload.should_float,
+                    ))
+                },
+                _ => Err("Wrong payload for Action::MovePaneToTab"),
+            },
+            Some(ProtobufActionName::MergeTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MergeTabPayload(payload)) => Ok(Action::MergeTab(
+                    payload.source_tab_position,
+                    payload.target_tab_position,
+                )),
+                _ => Err("Wrong payload for Action::MergeTab"),
+            },
+            Some(ProtobufActionName::ChangeFloatingPaneCoordinates) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::ChangeFloatingPaneCoordinatesPayload(payload)) => {
+                        let pane_id = if payload.is_plugin {
+                            PaneId::Plugin(payload.pane_id)
+                        } else {
+                            PaneId::Terminal(payload.pane_id)
+                        };
+                        Ok(Action::ChangeFloatingPaneCoordinates(
+                            pane_id,
+                            FloatingPaneCoordinates {
+                                x: payload.x.map(|x| x.into()),
+                                y: payload.y.map(|y| y.into()),
+                                width: payload.width.map(|width| width.into()),
+                                height: payload.height.map(|height| height.into()),
+                            },
+                        ))
+                    },
+                    _ => Err("Wrong payload for Action::ChangeFloatingPaneCoordinates"),
+                }
+            },
+            Some(ProtobufActionName::PromoteToMaster) => match protobuf_action.optional_payload {
+                Some(_) => Err("PromoteToMaster should not have a payload"),
+                None => Ok(Action::PromoteToMaster),
+            },
+            Some(ProtobufActionName::IncreaseMasterCount) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("IncreaseMasterCount should not have a payload"),
+                    None => Ok(Action::IncreaseMasterCount),
+                }
+            },
+            Some(ProtobufActionName::DecreaseMasterCount) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("DecreaseMasterCount should not have a payload"),
+                    None => Ok(Action::DecreaseMasterCount),
+                }
+            },
             Some(ProtobufActionName::KeybindPipe) => match protobuf_action.optional_payload {
                 Some(_) => Err("KeybindPipe should not have a payload"),
                 // TODO: at some point we might want to support a payload here
@@ -857,6 +975,14 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ScrollToTop as i32,
                 optional_payload: None,
             }),
+            Action::ScrollToPreviousPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToPreviousPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::ScrollToNextPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToNextPrompt as i32,
+                optional_payload: None,
+            }),
             Action::PageScrollUp => Ok(ProtobufAction {
                 name: ProtobufActionName::PageScrollUp as i32,
                 optional_payload: None,
@@ -961,6 +1087,33 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::TogglePaneEmbedOrFloating as i32,
                 optional_payload: None,
             }),
+            Action::TogglePanePinned => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePanePinned as i32,
+                optional_payload: None,
+            }),
+            Action::UndoLayoutChange => Ok(ProtobufAction {
+                name: ProtobufActionName::UndoLayoutChange as i32,
+                optional_payload: None,
+            }),
+            Action::RedoLayoutChange => Ok(ProtobufAction {
+                name: ProtobufActionName::RedoLayoutChange as i32,
+                optional_payload: None,
+            }),
+            Action::StackPane(direction) => {
+                let direction: ProtobufResizeDirection = direction.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::StackPane as i32,
+                    optional_payload: Some(OptionalPayload::StackPanePayload(direction as i32)),
+                })
+            },
+            Action::UnstackPane => Ok(ProtobufAction {
+                name: ProtobufActionName::UnstackPane as i32,
+                optional_payload: None,
+            }),
+            Action::StackAllPanes => Ok(ProtobufAction {
+                name: ProtobufActionName::StackAllPanes as i32,
+                optional_payload: None,
+            }),
             Action::ToggleFloatingPanes => Ok(ProtobufAction {
                 name: ProtobufActionName::ToggleFloatingPanes as i32,
                 optional_payload: None,
@@ -1176,7 +1329,7 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::NextSwapLayout as i32,
                 optional_payload: None,
             }),
-            Action::QueryTabNames => Ok(ProtobufAction {
+            Action::QueryTabNames(false) => Ok(ProtobufAction {
                 name: ProtobufActionName::QueryTabNames as i32,
                 optional_payload: None,
             }),
@@ -1281,6 +1434,71 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::RenameSession as i32,
                 optional_payload: Some(OptionalPayload::RenameSessionPayload(session_name)),
             }),
+            Action::CopyCommandOutput => Ok(ProtobufAction {
+                name: ProtobufActionName::CopyCommandOutput as i32,
+                optional_payload: None,
+            }),
+            Action::SetTiling(algorithm) => Ok(ProtobufAction {
+                name: ProtobufActionName::SetTiling as i32,
+                optional_payload: algorithm
+                    .map(|algorithm| OptionalPayload::SetTilingPayload(algorithm.to_string())),
+            }),
+            Action::MovePaneToTab(pane_id, tab_position, tab_name, should_float) => {
+                let (pane_id, is_plugin) = match pane_id {
+                    PaneId::Terminal(id) => (id, false),
+                    PaneId::Plugin(id) => (id, true),
+                };
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::MovePaneToTab as i32,
+                    optional_payload: Some(OptionalPayload::MovePaneToTabPayload(
+                        MovePaneToTabPayload {
+                            pane_id,
+                            is_plugin,
+                            tab_position,
+                            tab_name,
+                            should_float,
+                        },
+                    )),
+                })
+            },
+            Action::MergeTab(source_tab_position, target_tab_position) => Ok(ProtobufAction {
+                name: ProtobufActionName::MergeTab as i32,
+                optional_payload: Some(OptionalPayload::MergeTabPayload(MergeTabPayload {
+                    source_tab_position,
+                    target_tab_position,
+                })),
+            }),
+            Action::ChangeFloatingPaneCoordinates(pane_id, coordinates) => {
+                let (pane_id, is_plugin) = match pane_id {
+                    PaneId::Terminal(id) => (id, false),
+                    PaneId::Plugin(id) => (id, true),
+                };
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::ChangeFloatingPaneCoordinates as i32,
+                    optional_payload: Some(OptionalPayload::ChangeFloatingPaneCoordinatesPayload(
+                        ChangeFloatingPaneCoordinatesPayload {
+                            pane_id,
+                            is_plugin,
+                            x: coordinates.x.map(|x| x.into()),
+                            y: coordinates.y.map(|y| y.into()),
+                            width: coordinates.width.map(|width| width.into()),
+                            height: coordinates.height.map(|height| height.into()),
+                        },
+                    )),
+                })
+            },
+            Action::PromoteToMaster => Ok(ProtobufAction {
+                name: ProtobufActionName::PromoteToMaster as i32,
+                optional_payload: None,
+            }),
+            Action::IncreaseMasterCount => Ok(ProtobufAction {
+                name: ProtobufActionName::IncreaseMasterCount as i32,
+                optional_payload: None,
+            }),
+            Action::DecreaseMasterCount => Ok(ProtobufAction {
+                name: ProtobufActionName::DecreaseMasterCount as i32,
+                optional_payload: None,
+            }),
             Action::KeybindPipe { .. } => Ok(ProtobufAction {
                 name: ProtobufActionName::KeybindPipe as i32,
                 optional_payload: None,
@@ -1291,10 +1509,40 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::NewInPlacePluginPane(..)
             | Action::Deny
             | Action::Copy
-            | Action::DumpLayout
+            | Action::DumpLayout(..)
+            | Action::QueryTabNames(true)
             | Action::CliPipe { .. }
-            | Action::ListClients
-            | Action::SkipConfirm(..) => Err("Unsupported action"),
+            | Action::ListClients(..)
+            | Action::ListPanes(..)
+            | Action::ListTabs(..)
+            | Action::SkipConfirm(..)
+            | Action::ApplyToTarget(..)
+            | Action::WaitFor { .. } => Err("Unsupported action"),
+        }
+    }
+}
+
+impl From<SplitSizeValue> for SplitSize {
+    fn from(split_size_value: SplitSizeValue) -> Self {
+        if split_size_value.is_percent {
+            SplitSize::Percent(split_size_value.value as usize)
+        } else {
+            SplitSize::Fixed(split_size_value.value as usize)
+        }
+    }
+}
+
+impl From<SplitSize> for SplitSizeValue {
+    fn from(split_size: SplitSize) -> Self {
+        match split_size {
+            SplitSize::Percent(value) => SplitSizeValue {
+                value: value as u32,
+                is_percent: true,
+            },
+            SplitSize::Fixed(value) => SplitSizeValue {
+                value: value as u32,
+                is_percent: false,
+            },
         }
     }
 }
@@ -1306,6 +1554,7 @@ impl TryFrom<ProtobufSearchOption> for SearchOption {
             ProtobufSearchOption::CaseSensitivity => Ok(SearchOption::CaseSensitivity),
             ProtobufSearchOption::WholeWord => Ok(SearchOption::WholeWord),
             ProtobufSearchOption::Wrap => Ok(SearchOption::Wrap),
+            ProtobufSearchOption::Regex => Ok(SearchOption::Regex),
         }
     }
 }
@@ -1317,6 +1566,7 @@ impl TryFrom<SearchOption> for ProtobufSearchOption {
             SearchOption::CaseSensitivity => Ok(ProtobufSearchOption::CaseSensitivity),
             SearchOption::WholeWord => Ok(ProtobufSearchOption::WholeWord),
             SearchOption::Wrap => Ok(ProtobufSearchOption::Wrap),
+            SearchOption::Regex => Ok(ProtobufSearchOption::Regex),
         }
     }
 }
@@ -1378,6 +1628,11 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let env = protobuf_run_command_action
+            .env
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
         Ok(RunCommandAction {
             command,
             args,
@@ -1385,6 +1640,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            env,
         })
     }
 }
@@ -1401,6 +1657,11 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let env = run_command_action
+            .env
+            .into_iter()
+            .map(|(name, value)| ProtobufNameAndValue { name, value })
+            .collect();
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -1409,6 +1670,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             hold_on_close,
             hold_on_start,
             pane_name: None,
+            env,
         })
     }
 }
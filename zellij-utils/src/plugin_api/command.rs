@@ -1,4 +1,6 @@
-pub use super::generated_api::api::command::Command as ProtobufCommand;
+pub use super::generated_api::api::command::{
+    Command as ProtobufCommand, EnvVariable as ProtobufEnvVariable,
+};
 use crate::data::CommandToRun;
 
 use std::convert::TryFrom;
@@ -10,7 +12,17 @@ impl TryFrom<ProtobufCommand> for CommandToRun {
         let path = PathBuf::from(protobuf_command.path);
         let args = protobuf_command.args;
         let cwd = protobuf_command.cwd.map(|c| PathBuf::from(c));
-        Ok(CommandToRun { path, args, cwd })
+        let env = protobuf_command
+            .env_variables
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        Ok(CommandToRun {
+            path,
+            args,
+            cwd,
+            env,
+        })
     }
 }
 
@@ -21,6 +33,11 @@ impl TryFrom<CommandToRun> for ProtobufCommand {
             path: command_to_run.path.display().to_string(),
             args: command_to_run.args,
             cwd: command_to_run.cwd.map(|c| c.display().to_string()),
+            env_variables: command_to_run
+                .env
+                .into_iter()
+                .map(|(name, value)| ProtobufEnvVariable { name, value })
+                .collect(),
         })
     }
 }
@@ -235,6 +235,16 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for the WebRequestResult Event"),
             },
+            Some(ProtobufEventType::PaneNotification) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneNotificationPayload(pane_notification_payload)) => {
+                    Ok(Event::PaneNotification {
+                        pane_id: pane_notification_payload.pane_id,
+                        title: pane_notification_payload.title,
+                        body: pane_notification_payload.body,
+                    })
+                },
+                _ => Err("Malformed payload for the PaneNotification Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -460,6 +470,23 @@ impl TryFrom<Event> for ProtobufEvent {
                     )),
                 })
             },
+            Event::PaneNotification {
+                pane_id,
+                title,
+                body,
+            } => {
+                let pane_notification_payload = PaneNotificationPayload {
+                    pane_id,
+                    title,
+                    body,
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PaneNotification as i32,
+                    payload: Some(event::Payload::PaneNotificationPayload(
+                        pane_notification_payload,
+                    )),
+                })
+            },
         }
     }
 }
@@ -702,10 +729,17 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             is_focused: protobuf_pane_info.is_focused,
             is_fullscreen: protobuf_pane_info.is_fullscreen,
             is_floating: protobuf_pane_info.is_floating,
+            is_pinned: protobuf_pane_info.is_pinned,
             is_suppressed: protobuf_pane_info.is_suppressed,
             title: protobuf_pane_info.title,
             exited: protobuf_pane_info.exited,
             exit_status: protobuf_pane_info.exit_status,
+            last_command_exit_code: protobuf_pane_info.last_command_exit_code,
+            selected_command_exit_code: protobuf_pane_info.selected_command_exit_code,
+            cwd: protobuf_pane_info.cwd.map(PathBuf::from),
+            has_bell: protobuf_pane_info.has_bell,
+            has_activity: protobuf_pane_info.has_activity,
+            has_silence: protobuf_pane_info.has_silence,
             is_held: protobuf_pane_info.is_held,
             pane_x: protobuf_pane_info.pane_x as usize,
             pane_content_x: protobuf_pane_info.pane_content_x as usize,
@@ -734,10 +768,17 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             is_focused: pane_info.is_focused,
             is_fullscreen: pane_info.is_fullscreen,
             is_floating: pane_info.is_floating,
+            is_pinned: pane_info.is_pinned,
             is_suppressed: pane_info.is_suppressed,
             title: pane_info.title,
             exited: pane_info.exited,
             exit_status: pane_info.exit_status,
+            last_command_exit_code: pane_info.last_command_exit_code,
+            selected_command_exit_code: pane_info.selected_command_exit_code,
+            cwd: pane_info.cwd.map(|cwd| cwd.display().to_string()),
+            has_bell: pane_info.has_bell,
+            has_activity: pane_info.has_activity,
+            has_silence: pane_info.has_silence,
             is_held: pane_info.is_held,
             pane_x: pane_info.pane_x as u32,
             pane_content_x: pane_info.pane_content_x as u32,
@@ -778,6 +819,10 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
                 .collect(),
             active_swap_layout_name: protobuf_tab_info.active_swap_layout_name,
             is_swap_layout_dirty: protobuf_tab_info.is_swap_layout_dirty,
+            has_unseen_notification: protobuf_tab_info.has_unseen_notification,
+            has_bell: protobuf_tab_info.has_bell,
+            has_activity: protobuf_tab_info.has_activity,
+            has_silence: protobuf_tab_info.has_silence,
         })
     }
 }
@@ -800,6 +845,10 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
                 .collect(),
             active_swap_layout_name: tab_info.active_swap_layout_name,
             is_swap_layout_dirty: tab_info.is_swap_layout_dirty,
+            has_unseen_notification: tab_info.has_unseen_notification,
+            has_bell: tab_info.has_bell,
+            has_activity: tab_info.has_activity,
+            has_silence: tab_info.has_silence,
         })
     }
 }
@@ -950,6 +999,7 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::SessionUpdate => EventType::SessionUpdate,
             ProtobufEventType::RunCommandResult => EventType::RunCommandResult,
             ProtobufEventType::WebRequestResult => EventType::WebRequestResult,
+            ProtobufEventType::PaneNotification => EventType::PaneNotification,
         })
     }
 }
@@ -977,6 +1027,7 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::SessionUpdate => ProtobufEventType::SessionUpdate,
             EventType::RunCommandResult => ProtobufEventType::RunCommandResult,
             EventType::WebRequestResult => ProtobufEventType::WebRequestResult,
+            EventType::PaneNotification => ProtobufEventType::PaneNotification,
         })
     }
 }
@@ -1154,6 +1205,10 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            has_unseen_notification: false,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
         },
         TabInfo {
             position: 1,
@@ -1166,6 +1221,10 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            has_unseen_notification: false,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
         },
         TabInfo::default(),
     ]);
@@ -1403,6 +1462,25 @@ fn serialize_file_system_delete_event() {
     );
 }
 
+#[test]
+fn serialize_pane_notification_event() {
+    use prost::Message;
+    let pane_notification_event = Event::PaneNotification {
+        pane_id: 1,
+        title: Some("Build".to_owned()),
+        body: "Build finished".to_owned(),
+    };
+    let protobuf_event: ProtobufEvent = pane_notification_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_notification_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_session_update_event() {
     use prost::Message;
@@ -1433,6 +1511,10 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            has_unseen_notification: false,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
         },
         TabInfo {
             position: 1,
@@ -1445,6 +1527,10 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            has_unseen_notification: false,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
         },
         TabInfo::default(),
     ];
@@ -1456,10 +1542,17 @@ fn serialize_session_update_event_with_non_default_values() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
             exit_status: None,
+            last_command_exit_code: None,
+            selected_command_exit_code: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
             is_held: false,
             pane_x: 0,
             pane_content_x: 1,
@@ -1480,10 +1573,17 @@ fn serialize_session_update_event_with_non_default_values() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
             exit_status: None,
+            last_command_exit_code: None,
+            selected_command_exit_code: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            has_silence: false,
             is_held: false,
             pane_x: 0,
             pane_content_x: 1,
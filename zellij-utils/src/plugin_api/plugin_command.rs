@@ -1,16 +1,18 @@
 pub use super::generated_api::api::{
-    action::{PaneIdAndShouldFloat, SwitchToModePayload},
+    action::{MovePaneToTabPayload, PaneIdAndShouldFloat, SwitchToModePayload},
     event::{EventNameList as ProtobufEventNameList, Header},
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
-        plugin_command::Payload, CliPipeOutputPayload, CommandName, ContextItem, EnvVariable,
-        ExecCmdPayload, FixedOrPercent as ProtobufFixedOrPercent,
+        plugin_command::Payload, ChangeFloatingPanesCoordinatesPayload, CliPipeOutputPayload,
+        CommandName, ContextItem, EnvVariable, ExecCmdPayload,
+        FixedOrPercent as ProtobufFixedOrPercent,
         FixedOrPercentValue as ProtobufFixedOrPercentValue,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, HttpVerb as ProtobufHttpVerb,
         IdAndNewName, KillSessionsPayload, MessageToPluginPayload, MovePayload,
-        NewPluginArgs as ProtobufNewPluginArgs, NewTabsWithLayoutInfoPayload,
-        OpenCommandPanePayload, OpenFilePayload, PaneId as ProtobufPaneId,
-        PaneType as ProtobufPaneType, PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
+        NewPluginArgs as ProtobufNewPluginArgs, NewTabsWithLayoutAndVarsPayload,
+        NewTabsWithLayoutInfoPayload, OpenCommandPanePayload, OpenFilePayload,
+        PaneId as ProtobufPaneId, PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType,
+        PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
         RequestPluginPermissionPayload, ResizePayload, RunCommandPayload, SetTimeoutPayload,
         SubscribePayload, SwitchSessionPayload, SwitchTabToPayload, UnsubscribePayload,
         WebRequestPayload,
@@ -888,6 +890,81 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for NewTabsWithLayoutInfo"),
             },
+            Some(CommandName::NewTabsWithLayoutAndVars) => match protobuf_plugin_command.payload {
+                Some(Payload::NewTabsWithLayoutAndVarsPayload(
+                    new_tabs_with_layout_and_vars_payload,
+                )) => {
+                    let layout_vars = new_tabs_with_layout_and_vars_payload
+                        .layout_vars
+                        .into_iter()
+                        .map(|context_item| (context_item.name, context_item.value))
+                        .collect();
+                    Ok(PluginCommand::NewTabsWithLayoutAndVars(
+                        new_tabs_with_layout_and_vars_payload.layout,
+                        layout_vars,
+                    ))
+                },
+                _ => Err("Mismatched payload for NewTabsWithLayoutAndVars"),
+            },
+            Some(CommandName::MovePaneToTab) => match protobuf_plugin_command.payload {
+                Some(Payload::MovePaneToTabPayload(payload)) => {
+                    let pane_id = if payload.is_plugin {
+                        PaneId::Plugin(payload.pane_id)
+                    } else {
+                        PaneId::Terminal(payload.pane_id)
+                    };
+                    Ok(PluginCommand::MovePaneToTab(
+                        pane_id,
+                        payload.tab_position,
+                        payload.tab_name,
+                        payload.should_float,
+                    ))
+                },
+                _ => Err("Mismatched payload for MovePaneToTab"),
+            },
+            Some(CommandName::ChangeFloatingPanesCoordinates) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::ChangeFloatingPanesCoordinatesPayload(payload)) => {
+                        let mut pane_ids_and_coordinates = vec![];
+                        for pane_id_and_coordinates in
+                            payload.pane_ids_and_floating_panes_coordinates
+                        {
+                            let pane_id: PaneId = pane_id_and_coordinates
+                                .pane_id
+                                .ok_or("No pane id for ChangeFloatingPanesCoordinates")?
+                                .try_into()?;
+                            let coordinates: FloatingPaneCoordinates = pane_id_and_coordinates
+                                .floating_pane_coordinates
+                                .ok_or("No coordinates for ChangeFloatingPanesCoordinates")?
+                                .into();
+                            pane_ids_and_coordinates.push((pane_id, coordinates));
+                        }
+                        Ok(PluginCommand::ChangeFloatingPanesCoordinates(
+                            pane_ids_and_coordinates,
+                        ))
+                    },
+                    _ => Err("Mismatched payload for ChangeFloatingPanesCoordinates"),
+                }
+            },
+            Some(CommandName::StackPane) => match protobuf_plugin_command.payload {
+                Some(Payload::StackPanePayload(move_payload)) => match move_payload.direction {
+                    Some(direction) => Ok(PluginCommand::StackPane(direction.try_into()?)),
+                    None => Err("Malformed stack pane payload"),
+                },
+                _ => Err("Mismatched payload for StackPane"),
+            },
+            Some(CommandName::UnstackPane) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    return Err("UnstackPane should not have a payload");
+                }
+                Ok(PluginCommand::UnstackPane)
+            },
+            Some(CommandName::StackAllPanes) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    return Err("StackAllPanes should not have a payload");
+                }
+                Ok(PluginCommand::StackAllPanes)
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -1420,6 +1497,69 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
+            PluginCommand::NewTabsWithLayoutAndVars(raw_layout, layout_vars) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::NewTabsWithLayoutAndVars as i32,
+                    payload: Some(Payload::NewTabsWithLayoutAndVarsPayload(
+                        NewTabsWithLayoutAndVarsPayload {
+                            layout: raw_layout,
+                            layout_vars: layout_vars
+                                .into_iter()
+                                .map(|(name, value)| ContextItem { name, value })
+                                .collect(),
+                        },
+                    )),
+                })
+            },
+            PluginCommand::MovePaneToTab(pane_id, tab_position, tab_name, should_float) => {
+                let (pane_id, is_plugin) = match pane_id {
+                    PaneId::Terminal(id) => (id, false),
+                    PaneId::Plugin(id) => (id, true),
+                };
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::MovePaneToTab as i32,
+                    payload: Some(Payload::MovePaneToTabPayload(MovePaneToTabPayload {
+                        pane_id,
+                        is_plugin,
+                        tab_position,
+                        tab_name,
+                        should_float,
+                    })),
+                })
+            },
+            PluginCommand::ChangeFloatingPanesCoordinates(pane_ids_and_coordinates) => {
+                let mut pane_ids_and_floating_panes_coordinates = vec![];
+                for (pane_id, coordinates) in pane_ids_and_coordinates {
+                    pane_ids_and_floating_panes_coordinates.push(
+                        PaneIdAndFloatingPaneCoordinates {
+                            pane_id: Some(pane_id.try_into()?),
+                            floating_pane_coordinates: Some(coordinates.into()),
+                        },
+                    );
+                }
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ChangeFloatingPanesCoordinates as i32,
+                    payload: Some(Payload::ChangeFloatingPanesCoordinatesPayload(
+                        ChangeFloatingPanesCoordinatesPayload {
+                            pane_ids_and_floating_panes_coordinates,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::StackPane(direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::StackPane as i32,
+                payload: Some(Payload::StackPanePayload(MovePayload {
+                    direction: Some(direction.try_into()?),
+                })),
+            }),
+            PluginCommand::UnstackPane => Ok(ProtobufPluginCommand {
+                name: CommandName::UnstackPane as i32,
+                payload: None,
+            }),
+            PluginCommand::StackAllPanes => Ok(ProtobufPluginCommand {
+                name: CommandName::StackAllPanes as i32,
+                payload: None,
+            }),
         }
     }
 }
@@ -1,11 +1,12 @@
+use kdl::{KdlIdentifier, KdlNode, KdlValue};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::{
     input::layout::PluginUserConfiguration,
     input::layout::{
-        FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginOrAlias, SplitDirection,
-        SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+        DynamicTiling, FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginOrAlias,
+        SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
     pane_size::{Constraint, PaneGeom},
 };
@@ -40,6 +41,7 @@ pub struct TabLayoutManifest {
     pub floating_panes: Vec<PaneLayoutManifest>,
     pub is_focused: bool,
     pub hide_floating_panes: bool,
+    pub dynamic_tiling: Option<DynamicTiling>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -50,6 +52,9 @@ pub struct PaneLayoutManifest {
     pub is_borderless: bool,
     pub title: Option<String>,
     pub is_focused: bool,
+    pub is_pinned: bool,
+    pub monitor_activity: bool,
+    pub monitor_silence: Option<u64>, // seconds
     pub pane_contents: Option<String>,
 }
 
@@ -90,6 +95,7 @@ fn stringify_tab(
     tab_name: String,
     is_focused: bool,
     hide_floating_panes: bool,
+    dynamic_tiling: Option<DynamicTiling>,
     tiled_panes: &Vec<PaneLayoutManifest>,
     floating_panes: &Vec<PaneLayoutManifest>,
     pane_contents: &mut BTreeMap<String, String>,
@@ -113,6 +119,11 @@ fn stringify_tab(
             if hide_floating_panes {
                 tab_attributes.push(format!("hide_floating_panes=true"));
             }
+            if let Some(dynamic_tiling) = dynamic_tiling {
+                tab_attributes.push(format!("tiling=\"{}\"", dynamic_tiling.algorithm));
+                tab_attributes.push(format!("master_ratio={}", dynamic_tiling.master_ratio));
+                tab_attributes.push(format!("master_count={}", dynamic_tiling.master_count));
+            }
             kdl_string.push_str(&kdl_string_from_tab(
                 &tiled_panes,
                 &floating_panes_layout,
@@ -195,6 +206,7 @@ fn kdl_string_from_tiled_pane(
     if has_child_attributes {
         kdl_string.push_str(" {\n");
         stringify_args(args, &mut kdl_string);
+        stringify_env(extract_env(&layout.run), &mut kdl_string);
         stringify_start_suspended(&command, &mut kdl_string);
         stringify_plugin(plugin, plugin_config, &mut kdl_string);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
@@ -225,6 +237,12 @@ pub fn extract_command_and_args(layout_run: &Option<Run>) -> (Option<String>, Ve
         _ => (None, vec![]),
     }
 }
+pub fn extract_env(layout_run: &Option<Run>) -> BTreeMap<String, String> {
+    match layout_run {
+        Some(Run::Command(run_command)) => run_command.env.clone(),
+        _ => BTreeMap::new(),
+    }
+}
 pub fn extract_plugin_and_config(
     layout_run: &Option<Run>,
 ) -> (Option<String>, Option<PluginUserConfiguration>) {
@@ -304,6 +322,35 @@ fn stringify_args(args: Vec<String>, kdl_string: &mut String) {
     }
 }
 
+fn stringify_env(env: BTreeMap<String, String>, kdl_string: &mut String) {
+    if !env.is_empty() {
+        kdl_string.push_str(&indent("env {\n", INDENT));
+        for (name, value) in env {
+            let mut env_variable = KdlNode::new(kdl_identifier(&name));
+            env_variable.push(value);
+            kdl_string.push_str(&indent(&format!("{}\n", env_variable), DOUBLE_INDENT));
+        }
+        kdl_string.push_str(&indent("}\n", INDENT));
+    }
+}
+
+fn kdl_identifier(name: &str) -> KdlIdentifier {
+    // anything but the usual environment variable names (eg. 1FOO or A-B.C) is quoted, with the
+    // KDL string escapes
+    let mut identifier = KdlIdentifier::from(name);
+    let is_bare_identifier = name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(name, "true" | "false" | "null");
+    if !is_bare_identifier {
+        identifier.set_repr(KdlValue::String(name.to_owned()).to_string());
+    }
+    identifier
+}
+
 fn stringify_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
@@ -351,6 +398,12 @@ fn stringify_tiled_layout_attributes(
     if layout.children_are_stacked {
         kdl_string.push_str(&" stacked=true");
     }
+    if layout.monitor_activity == Some(true) {
+        kdl_string.push_str(" monitor_activity=true");
+    }
+    if let Some(monitor_silence) = layout.monitor_silence {
+        kdl_string.push_str(&format!(" monitor_silence={monitor_silence}"));
+    }
     if layout.is_expanded_in_stack {
         kdl_string.push_str(&" expanded=true");
     }
@@ -400,6 +453,18 @@ fn stringify_floating_layout_attributes(layout: &FloatingPaneLayout, kdl_string:
         },
         None => {},
     }
+    if layout.pinned == Some(true) {
+        kdl_string.push_str(&indent("pinned true\n", INDENT));
+    }
+    if layout.monitor_activity == Some(true) {
+        kdl_string.push_str(&indent("monitor_activity true\n", INDENT));
+    }
+    if let Some(monitor_silence) = layout.monitor_silence {
+        kdl_string.push_str(&indent(
+            &format!("monitor_silence {}\n", monitor_silence),
+            INDENT,
+        ));
+    }
 }
 
 fn stringify_start_suspended(command: &Option<String>, kdl_string: &mut String) {
@@ -530,6 +595,7 @@ fn stringify_multiple_tabs(
             tab_name.clone(),
             tab_layout_manifest.is_focused,
             hide_floating_panes,
+            tab_layout_manifest.dynamic_tiling,
             &tiled_panes,
             &floating_panes,
             pane_contents,
@@ -569,6 +635,7 @@ fn kdl_string_from_floating_pane(
     stringify_start_suspended(&command, &mut kdl_string);
     stringify_floating_layout_attributes(&layout, &mut kdl_string);
     stringify_args(args, &mut kdl_string);
+    stringify_env(extract_env(&layout.run), &mut kdl_string);
     stringify_plugin(plugin, plugin_config, &mut kdl_string);
     kdl_string.push_str("}\n");
     kdl_string
@@ -578,7 +645,16 @@ fn tiled_pane_layout_from_manifest(
     manifest: Option<&PaneLayoutManifest>,
     split_size: Option<SplitSize>,
 ) -> TiledPaneLayout {
-    let (run, borderless, is_expanded_in_stack, name, focus, pane_initial_contents) = manifest
+    let (
+        run,
+        borderless,
+        is_expanded_in_stack,
+        name,
+        focus,
+        pane_initial_contents,
+        monitor_activity,
+        monitor_silence,
+    ) = manifest
         .map(|g| {
             let mut run = g.run.clone();
             if let Some(cwd) = &g.cwd {
@@ -595,9 +671,11 @@ fn tiled_pane_layout_from_manifest(
                 g.title.clone(),
                 Some(g.is_focused),
                 g.pane_contents.clone(),
+                if g.monitor_activity { Some(true) } else { None },
+                g.monitor_silence,
             )
         })
-        .unwrap_or((None, false, false, None, None, None));
+        .unwrap_or((None, false, false, None, None, None, None, None));
     TiledPaneLayout {
         split_size,
         run,
@@ -606,6 +684,8 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        monitor_activity,
+        monitor_silence,
         ..Default::default()
     }
 }
@@ -695,6 +775,9 @@ fn get_floating_panes_layout_from_panegeoms(
                 focus: Some(m.is_focused),
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
+                monitor_activity: if m.monitor_activity { Some(true) } else { None },
+                monitor_silence: m.monitor_silence,
+                pinned: if m.is_pinned { Some(true) } else { None },
             }
         })
         .collect()
@@ -921,6 +1004,7 @@ fn get_split_sizes(constraints: &Vec<Constraint>) -> Vec<Option<SplitSize>> {
 mod tests {
 
     use super::*;
+    use crate::input::command::RunCommand;
     use crate::pane_size::Dimension;
     use expect_test::expect;
     use serde_json::Value;
@@ -1124,6 +1208,123 @@ mod tests {
 }"#]]
         .assert_eq(&kdl.0);
     }
+    #[test]
+    fn command_pane_env() {
+        let mut env = BTreeMap::new();
+        env.insert("PAGER".to_owned(), "less -R".to_owned());
+        env.insert("QUOTED".to_owned(), "say \"hi\"".to_owned());
+        env.insert("1FOO".to_owned(), "starts with a digit".to_owned());
+        env.insert("A-B.C".to_owned(), "back\\slash".to_owned());
+        env.insert("WITH SPACE".to_owned(), "new\nline\ttab".to_owned());
+        env.insert("true".to_owned(), "keyword".to_owned());
+        let run_command = RunCommand {
+            command: PathBuf::from("htop"),
+            env,
+            ..Default::default()
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: PANEGEOMS_JSON[0]
+                .iter()
+                .map(|pg| PaneLayoutManifest {
+                    geom: parse_panegeom_from_json(pg),
+                    run: Some(Run::Command(run_command.clone())),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane command="htop" size=1 {
+                        env {
+                            "1FOO" "starts with a digit"
+                            "A-B.C" "back\\slash"
+                            PAGER "less -R"
+                            QUOTED "say \"hi\""
+                            "WITH SPACE" "new\nline\ttab"
+                            "true" "keyword"
+                        }
+                        start_suspended true
+                    }
+                    pane command="htop" {
+                        env {
+                            "1FOO" "starts with a digit"
+                            "A-B.C" "back\\slash"
+                            PAGER "less -R"
+                            QUOTED "say \"hi\""
+                            "WITH SPACE" "new\nline\ttab"
+                            "true" "keyword"
+                        }
+                        start_suspended true
+                    }
+                    pane command="htop" size=2 {
+                        env {
+                            "1FOO" "starts with a digit"
+                            "A-B.C" "back\\slash"
+                            PAGER "less -R"
+                            QUOTED "say \"hi\""
+                            "WITH SPACE" "new\nline\ttab"
+                            "true" "keyword"
+                        }
+                        start_suspended true
+                    }
+                }
+            }"#]]
+        .assert_eq(&kdl.0);
+        let layout = Layout::from_kdl(&kdl.0, "session_layout".into(), None, None).unwrap();
+        for pane in &layout.tabs[0].1.children {
+            match &pane.run {
+                Some(Run::Command(resurrected_command)) => {
+                    assert_eq!(resurrected_command.env, run_command.env)
+                },
+                run => panic!("expected a command pane, got: {:?}", run),
+            }
+        }
+    }
+    #[test]
+    fn pane_monitor_settings() {
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: PANEGEOMS_JSON[0]
+                .iter()
+                .enumerate()
+                .map(|(i, pg)| PaneLayoutManifest {
+                    geom: parse_panegeom_from_json(pg),
+                    monitor_activity: i == 0,
+                    ..Default::default()
+                })
+                .collect(),
+            floating_panes: vec![PaneLayoutManifest {
+                geom: parse_panegeom_from_json(PANEGEOMS_JSON[1][1]),
+                monitor_silence: Some(30),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        let layout = Layout::from_kdl(&kdl.0, "session_layout".into(), None, None).unwrap();
+        let (_, tiled_panes, floating_panes) = &layout.tabs[0];
+        let monitored_panes: Vec<_> = tiled_panes
+            .children
+            .iter()
+            .map(|pane| (pane.monitor_activity, pane.monitor_silence))
+            .collect();
+        assert_eq!(
+            monitored_panes,
+            vec![(None, None), (Some(true), None), (None, None)]
+        );
+        assert_eq!(floating_panes[0].monitor_activity, None);
+        assert_eq!(floating_panes[0].monitor_silence, Some(30));
+    }
     // utility functions
     fn parse_panegeom_from_json(data_str: &str) -> PaneGeom {
         //
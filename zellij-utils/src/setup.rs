@@ -21,6 +21,7 @@ use directories::BaseDirs;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
     fmt::Write as FmtWrite,
     fs,
@@ -671,6 +672,7 @@ impl Setup {
                     .and_then(|cli_options| cli_options.default_layout.clone())
             })
             .or_else(|| config.options.default_layout.clone());
+        let layout_vars: BTreeMap<String, String> = cli_args.layout_var.iter().cloned().collect();
         if let Some(layout_url) = chosen_layout
             .as_ref()
             .and_then(|l| l.to_str())
@@ -682,11 +684,16 @@ impl Setup {
                 }
             })
         {
-            Layout::from_url(layout_url, config)
+            Layout::from_url(layout_url, config, layout_vars)
         } else {
             // we merge-override the config here because the layout might contain configuration
             // that needs to take precedence
-            Layout::from_path_or_default(chosen_layout.as_ref(), layout_dir.clone(), config)
+            Layout::from_path_or_default(
+                chosen_layout.as_ref(),
+                layout_dir.clone(),
+                config,
+                layout_vars,
+            )
         }
     }
     fn handle_setup_commands(cli_args: &CliArgs) {